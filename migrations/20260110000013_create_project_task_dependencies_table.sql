@@ -0,0 +1,23 @@
+-- 创建任务依赖关系表
+-- dependency_type: 0=finish_to_start, 1=start_to_start, 2=finish_to_finish, 3=start_to_finish
+-- lag_days: 滞后天数，负数表示提前量（lead）
+CREATE TABLE IF NOT EXISTS project_task_dependencies (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    predecessor_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    successor_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    dependency_type INTEGER NOT NULL DEFAULT 0,
+    lag_days INTEGER NOT NULL DEFAULT 0,
+    creator_id BIGINT NOT NULL,
+    updater_id BIGINT,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    update_date_time TIMESTAMP,
+
+    UNIQUE(predecessor_id, successor_id),
+    CHECK (predecessor_id <> successor_id)
+);
+
+-- 创建索引
+CREATE INDEX idx_project_task_dependencies_project_id ON project_task_dependencies(project_id);
+CREATE INDEX idx_project_task_dependencies_predecessor_id ON project_task_dependencies(predecessor_id);
+CREATE INDEX idx_project_task_dependencies_successor_id ON project_task_dependencies(successor_id);
@@ -0,0 +1,8 @@
+-- 创建日历订阅令牌表（日历客户端无法携带 JWT Cookie，使用每用户一个的订阅令牌鉴权）
+CREATE TABLE IF NOT EXISTS calendar_feed_tokens (
+    id BIGINT PRIMARY KEY,
+    user_id BIGINT NOT NULL UNIQUE,
+    token TEXT NOT NULL UNIQUE,
+    create_date_time TIMESTAMP NOT NULL DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
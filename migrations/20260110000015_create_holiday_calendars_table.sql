@@ -0,0 +1,29 @@
+-- 创建假期日历表（不同地区办公室可使用不同的假期日历）
+CREATE TABLE IF NOT EXISTS holiday_calendars (
+    id BIGINT PRIMARY KEY,
+    calendar_name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    creator_id BIGINT NOT NULL,
+    updater_id BIGINT,
+    create_date_time TIMESTAMP NOT NULL DEFAULT NOW(),
+    update_date_time TIMESTAMP
+);
+
+-- 默认日历（id 固定为 1），已有假期全部归入默认日历
+INSERT INTO holiday_calendars (id, calendar_name, description, creator_id)
+VALUES (1, 'Default', 'Default holiday calendar', 0)
+ON CONFLICT (id) DO NOTHING;
+
+ALTER TABLE holidays
+    ADD COLUMN calendar_id BIGINT NOT NULL DEFAULT 1
+    REFERENCES holiday_calendars(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_holidays_calendar_id_holiday_date ON holidays(calendar_id, holiday_date);
+
+-- 项目 / 部门 / 团队引用的假期日历，NULL 表示使用默认日历
+ALTER TABLE projects
+    ADD COLUMN holiday_calendar_id BIGINT REFERENCES holiday_calendars(id) ON DELETE SET NULL;
+ALTER TABLE departments
+    ADD COLUMN holiday_calendar_id BIGINT REFERENCES holiday_calendars(id) ON DELETE SET NULL;
+ALTER TABLE teams
+    ADD COLUMN holiday_calendar_id BIGINT REFERENCES holiday_calendars(id) ON DELETE SET NULL;
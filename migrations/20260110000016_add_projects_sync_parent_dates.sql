@@ -0,0 +1,3 @@
+-- 开启后，任务写入时父任务的起止时间自动与子任务保持一致（最早开始 / 最晚结束）
+ALTER TABLE projects
+    ADD COLUMN sync_parent_dates BOOLEAN NOT NULL DEFAULT FALSE;
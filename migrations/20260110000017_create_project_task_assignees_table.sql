@@ -0,0 +1,14 @@
+-- 任务指派人（一个任务可指派给多个用户）；被指派人与创建者同为任务负责人，享有 *_own 权限
+CREATE TABLE IF NOT EXISTS project_task_assignees (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    task_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    creator_id BIGINT NOT NULL,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE(task_id, user_id)
+);
+
+CREATE INDEX idx_project_task_assignees_project_id ON project_task_assignees(project_id);
+CREATE INDEX idx_project_task_assignees_user_id ON project_task_assignees(user_id);
@@ -0,0 +1,28 @@
+-- 项目基线：保存某一时刻的计划，用于与当前计划对比
+CREATE TABLE IF NOT EXISTS project_baselines (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    baseline_name VARCHAR(100) NOT NULL,
+    description TEXT,
+    task_count INTEGER NOT NULL DEFAULT 0,
+    creator_id BIGINT NOT NULL,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE(project_id, baseline_name)
+);
+
+CREATE INDEX idx_project_baselines_project_id ON project_baselines(project_id);
+
+-- 基线任务快照；task_id 不设外键，任务删除后仍保留快照用于对比
+CREATE TABLE IF NOT EXISTS project_baseline_tasks (
+    baseline_id BIGINT NOT NULL REFERENCES project_baselines(id) ON DELETE CASCADE,
+    task_id BIGINT NOT NULL,
+    task_name VARCHAR(255) NOT NULL,
+    parent_id BIGINT,
+    task_type INTEGER NOT NULL,
+    start_date_time TIMESTAMP NOT NULL,
+    end_date_time TIMESTAMP NOT NULL,
+    custom_attributes JSONB NOT NULL DEFAULT '{}',
+
+    PRIMARY KEY (baseline_id, task_id)
+);
@@ -0,0 +1,16 @@
+-- 任务 / 任务属性配置变更历史
+-- entity_type: task, attribute_config；action: create, update, delete
+-- changes: [{"field": "...", "before": ..., "after": ...}]，自定义属性按 customAttributes.<key> 逐项记录
+CREATE TABLE IF NOT EXISTS project_task_histories (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    entity_type VARCHAR(30) NOT NULL,
+    entity_id BIGINT NOT NULL,
+    action VARCHAR(20) NOT NULL,
+    changes JSONB NOT NULL DEFAULT '[]',
+    actor_id BIGINT NOT NULL,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_project_task_histories_entity ON project_task_histories(entity_type, entity_id, create_date_time DESC);
+CREATE INDEX idx_project_task_histories_project_id ON project_task_histories(project_id);
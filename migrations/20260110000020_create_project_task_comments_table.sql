@@ -0,0 +1,20 @@
+-- 任务评论；parent_id 为回复的评论，root_id 为所在讨论串的顶层评论（顶层评论两者均为空）
+-- 删除评论会级联删除其下的回复
+CREATE TABLE IF NOT EXISTS project_task_comments (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    task_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    parent_id BIGINT REFERENCES project_task_comments(id) ON DELETE CASCADE,
+    root_id BIGINT REFERENCES project_task_comments(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    -- 评论中 @ 提及的用户
+    mention_user_ids BIGINT[] NOT NULL DEFAULT '{}',
+    creator_id BIGINT NOT NULL,
+    updater_id BIGINT,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    update_date_time TIMESTAMP
+);
+
+CREATE INDEX idx_project_task_comments_task_id ON project_task_comments(task_id, create_date_time);
+CREATE INDEX idx_project_task_comments_root_id ON project_task_comments(root_id);
+CREATE INDEX idx_project_task_comments_mention_user_ids ON project_task_comments USING GIN (mention_user_ids);
@@ -0,0 +1,15 @@
+-- 任务附件；文件内容保存在存储后端，storage_key 为文件在存储中的路径
+CREATE TABLE IF NOT EXISTS project_task_attachments (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    task_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    file_size BIGINT NOT NULL,
+    storage_key VARCHAR(500) NOT NULL,
+    creator_id BIGINT NOT NULL,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_project_task_attachments_task_id ON project_task_attachments(task_id);
+CREATE INDEX idx_project_task_attachments_project_id ON project_task_attachments(project_id);
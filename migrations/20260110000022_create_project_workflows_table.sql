@@ -0,0 +1,35 @@
+-- 项目任务状态工作流：状态定义
+CREATE TABLE IF NOT EXISTS project_workflow_statuses (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    status_name VARCHAR(64) NOT NULL,
+    -- 状态分类：0 待办，1 进行中，2 已完成
+    category SMALLINT NOT NULL,
+    -- 新建任务的初始状态，每个项目有且仅有一个（由应用层校验）
+    is_initial BOOLEAN NOT NULL DEFAULT false,
+    "order" DOUBLE PRECISION NOT NULL,
+    creator_id BIGINT NOT NULL,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- 延迟校验，允许在同一事务内互换状态名
+    CONSTRAINT uq_project_workflow_statuses_name UNIQUE (project_id, status_name)
+        DEFERRABLE INITIALLY DEFERRED
+);
+
+-- 状态间允许的流转及可执行流转的项目角色
+CREATE TABLE IF NOT EXISTS project_workflow_transitions (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    from_status_id BIGINT NOT NULL REFERENCES project_workflow_statuses(id) ON DELETE CASCADE,
+    to_status_id BIGINT NOT NULL REFERENCES project_workflow_statuses(id) ON DELETE CASCADE,
+    -- ProjectRole 数值：0 owner，1 admin，2 maintainer，3 member，4 viewer
+    allowed_roles SMALLINT[] NOT NULL,
+    UNIQUE(from_status_id, to_status_id)
+);
+
+CREATE INDEX idx_project_workflow_statuses_project_id ON project_workflow_statuses(project_id);
+CREATE INDEX idx_project_workflow_transitions_project_id ON project_workflow_transitions(project_id);
+
+-- 任务当前状态；NULL 表示尚未进入工作流（视为初始状态）
+ALTER TABLE project_tasks ADD COLUMN status_id BIGINT REFERENCES project_workflow_statuses(id);
+
+CREATE INDEX idx_project_tasks_status_id ON project_tasks(status_id);
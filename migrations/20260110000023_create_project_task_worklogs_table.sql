@@ -0,0 +1,19 @@
+-- 任务工时记录
+CREATE TABLE IF NOT EXISTS project_task_worklogs (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    task_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    -- 工时所属用户（可由管理者代为登记）
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    work_date DATE NOT NULL,
+    duration_minutes INT NOT NULL CHECK (duration_minutes > 0 AND duration_minutes <= 1440),
+    note TEXT,
+    creator_id BIGINT NOT NULL,
+    updater_id BIGINT,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    update_date_time TIMESTAMP
+);
+
+CREATE INDEX idx_project_task_worklogs_task_id ON project_task_worklogs(task_id, work_date);
+CREATE INDEX idx_project_task_worklogs_user_id ON project_task_worklogs(user_id, work_date);
+CREATE INDEX idx_project_task_worklogs_project_id ON project_task_worklogs(project_id, work_date);
@@ -0,0 +1,5 @@
+-- 任务完成百分比（0-100）；父任务的进度由子任务按工期加权推导，自身取值不参与汇总
+ALTER TABLE project_tasks
+    ADD COLUMN percent_complete DOUBLE PRECISION NOT NULL DEFAULT 0
+        CHECK (percent_complete >= 0 AND percent_complete <= 100);
+
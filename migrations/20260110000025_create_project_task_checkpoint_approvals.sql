@@ -0,0 +1,14 @@
+-- 检查点任务审批记录（签核 / 驳回），按时间追加，最新一条为当前签核状态
+CREATE TABLE IF NOT EXISTS project_task_checkpoint_approvals (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    task_id BIGINT NOT NULL REFERENCES project_tasks(id) ON DELETE CASCADE,
+    -- 1 = 签核通过, 2 = 驳回
+    decision SMALLINT NOT NULL CHECK (decision IN (1, 2)),
+    comment TEXT,
+    approver_id BIGINT NOT NULL,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_project_task_checkpoint_approvals_task_id
+    ON project_task_checkpoint_approvals(task_id, create_date_time);
@@ -0,0 +1,37 @@
+-- 重复任务系列：重复规则及生成任务所用的模板
+CREATE TABLE IF NOT EXISTS project_task_recurrences (
+    id BIGINT PRIMARY KEY,
+    project_id BIGINT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    -- RRULE 子集，如 FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10
+    rrule VARCHAR(255) NOT NULL,
+    -- 遇非工作日：0 = 照常, 1 = 跳过, 2 = 顺延到下一个工作日, 3 = 提前到上一个工作日
+    holiday_policy SMALLINT NOT NULL DEFAULT 2 CHECK (holiday_policy BETWEEN 0 AND 3),
+    -- 系列起始日期（即首个任务的日期）
+    start_date DATE NOT NULL,
+    -- 任务模板
+    task_name VARCHAR(255) NOT NULL,
+    parent_id BIGINT REFERENCES project_tasks(id) ON DELETE SET NULL,
+    task_type INT NOT NULL,
+    custom_attributes JSONB NOT NULL DEFAULT '{}',
+    start_time TIME NOT NULL,
+    end_time TIME NOT NULL,
+    -- 工期（工作日）
+    duration_days INT NOT NULL CHECK (duration_days > 0),
+    -- 已生成到的日期（按规则计算的日期，含端点）
+    generated_until DATE NOT NULL,
+    creator_id BIGINT NOT NULL,
+    updater_id BIGINT,
+    create_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    update_date_time TIMESTAMP
+);
+
+CREATE INDEX idx_project_task_recurrences_project_id ON project_task_recurrences(project_id);
+
+-- 系列与其生成的任务
+CREATE TABLE IF NOT EXISTS project_task_occurrences (
+    task_id BIGINT PRIMARY KEY REFERENCES project_tasks(id) ON DELETE CASCADE,
+    recurrence_id BIGINT NOT NULL REFERENCES project_task_recurrences(id) ON DELETE CASCADE,
+    -- 按规则计算的日期（假期调整前）
+    occurrence_date DATE NOT NULL,
+    UNIQUE(recurrence_id, occurrence_date)
+);
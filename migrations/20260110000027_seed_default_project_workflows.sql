@@ -0,0 +1,41 @@
+-- 为工作流功能上线前创建的项目补建默认工作流（与 definition.rs 中 default_workflow 一致），
+-- 并将这些项目中尚未进入工作流的任务置为初始状态。
+-- 应用生成的雪花 ID 均不小于 2^22（时间戳部分自 2020-01-01 起算），临时序列分配的小整数 ID 不会与之冲突。
+CREATE TEMPORARY SEQUENCE workflow_seed_ids;
+
+CREATE TEMPORARY TABLE workflow_seed_statuses AS
+SELECT nextval('workflow_seed_ids') AS id, p.id AS project_id, p.creator_id,
+       s.status_name, s.category, s.is_initial, s.position
+FROM projects p
+CROSS JOIN (VALUES
+    ('To Do', 0, true, 1),
+    ('In Progress', 1, false, 2),
+    ('Done', 2, false, 3)
+) AS s(status_name, category, is_initial, position)
+WHERE NOT EXISTS (SELECT 1 FROM project_workflow_statuses w WHERE w.project_id = p.id);
+
+INSERT INTO project_workflow_statuses
+    (id, project_id, status_name, category, is_initial, "order", creator_id)
+SELECT id, project_id, status_name, category::SMALLINT, is_initial, position::DOUBLE PRECISION, creator_id
+FROM workflow_seed_statuses;
+
+-- 完成与重新打开仅限维护者及以上（ProjectRole：0 owner，1 admin，2 maintainer，3 member）
+INSERT INTO project_workflow_transitions (id, project_id, from_status_id, to_status_id, allowed_roles)
+SELECT nextval('workflow_seed_ids'), f.project_id, f.id, t.id, tr.allowed_roles
+FROM (VALUES
+    ('To Do', 'In Progress', ARRAY[0, 1, 2, 3]::SMALLINT[]),
+    ('In Progress', 'To Do', ARRAY[0, 1, 2, 3]::SMALLINT[]),
+    ('In Progress', 'Done', ARRAY[0, 1, 2, 3]::SMALLINT[]),
+    ('To Do', 'Done', ARRAY[0, 1, 2]::SMALLINT[]),
+    ('Done', 'In Progress', ARRAY[0, 1, 2]::SMALLINT[])
+) AS tr(from_status, to_status, allowed_roles)
+INNER JOIN workflow_seed_statuses f ON f.status_name = tr.from_status
+INNER JOIN workflow_seed_statuses t ON t.project_id = f.project_id AND t.status_name = tr.to_status;
+
+UPDATE project_tasks
+SET status_id = s.id
+FROM workflow_seed_statuses s
+WHERE s.project_id = project_tasks.project_id AND s.is_initial AND project_tasks.status_id IS NULL;
+
+DROP TABLE workflow_seed_statuses;
+DROP SEQUENCE workflow_seed_ids;
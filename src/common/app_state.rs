@@ -1,5 +1,6 @@
 use crate::common::snowflake::SnowflakeIdBucket;
-use crate::config::JwtConfig;
+use crate::common::storage::FileStorage;
+use crate::config::{JwtConfig, StorageConfig};
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -8,14 +9,24 @@ pub struct AppState {
     pub pool: PgPool,
     pub jwt_config: JwtConfig,
     pub id_generator: Arc<SnowflakeIdBucket>,
+    pub storage: Arc<dyn FileStorage>,
+    pub storage_config: StorageConfig,
 }
 
 impl AppState {
-    pub fn new(pool: PgPool, jwt_config: JwtConfig, id_generator: Arc<SnowflakeIdBucket>) -> Self {
+    pub fn new(
+        pool: PgPool,
+        jwt_config: JwtConfig,
+        id_generator: Arc<SnowflakeIdBucket>,
+        storage: Arc<dyn FileStorage>,
+        storage_config: StorageConfig,
+    ) -> Self {
         Self {
             pool,
             jwt_config,
             id_generator,
+            storage,
+            storage_config,
         }
     }
 
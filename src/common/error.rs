@@ -26,11 +26,18 @@ pub struct FieldError {
     pub code: String,
 }
 
+pub fn field_error(field: impl Into<String>, code: &str, message: String) -> FieldError {
+    FieldError {
+        field: field.into(),
+        message,
+        code: code.to_string(),
+    }
+}
+
 #[derive(Debug)]
 pub enum AppError {
     NotFound(String),
     BadRequest(String),
-    #[allow(dead_code)]
     ValidationError(String, Vec<FieldError>),
     Unauthorized(String),
     Forbidden(String),
@@ -110,3 +117,9 @@ impl From<sqlx::Error> for AppError {
         AppError::DatabaseError(err)
     }
 }
+
+impl From<axum::extract::multipart::MultipartError> for AppError {
+    fn from(err: axum::extract::multipart::MultipartError) -> Self {
+        AppError::BadRequest(format!("Invalid multipart body: {}", err))
+    }
+}
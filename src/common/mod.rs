@@ -6,3 +6,4 @@ pub mod middleware;
 pub mod response;
 pub mod serde_helpers;
 pub mod snowflake;
+pub mod storage;
@@ -0,0 +1,129 @@
+use crate::common::error::{AppError, AppResult};
+use std::future::Future;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+pub type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = AppResult<T>> + Send + 'a>>;
+
+/// 文件存储后端
+///
+/// key 为以 `/` 分隔的相对路径（如 `projects/1/tasks/2/3`），由调用方生成。
+pub trait FileStorage: Send + Sync {
+    fn put<'a>(&'a self, key: &'a str, data: &'a [u8]) -> StorageFuture<'a, ()>;
+
+    /// 文件不存在时返回 NotFound
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Vec<u8>>;
+
+    /// 文件不存在时视为成功
+    fn delete<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()>;
+
+    /// 删除 prefix 下的全部文件，不存在时视为成功
+    fn delete_prefix<'a>(&'a self, prefix: &'a str) -> StorageFuture<'a, ()>;
+}
+
+/// 本地文件系统存储
+pub struct LocalFileStorage {
+    root: PathBuf,
+}
+
+impl LocalFileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// 将 key 解析为 root 下的路径，拒绝空段、`.`、`..` 等可能越出 root 的 key
+    fn resolve(&self, key: &str) -> AppResult<PathBuf> {
+        let mut path = self.root.clone();
+        for segment in key.split('/') {
+            let valid = !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && !segment.contains(['\\', ':', '\0']);
+            if !valid {
+                return Err(AppError::InternalError(format!(
+                    "Invalid storage key: {}",
+                    key
+                )));
+            }
+            path.push(segment);
+        }
+        Ok(path)
+    }
+}
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> AppError {
+    AppError::InternalError(format!("Failed to {} {}: {}", action, path.display(), e))
+}
+
+impl FileStorage for LocalFileStorage {
+    fn put<'a>(&'a self, key: &'a str, data: &'a [u8]) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.resolve(key)?;
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| io_error("create directory", parent, e))?;
+            }
+            tokio::fs::write(&path, data)
+                .await
+                .map_err(|e| io_error("write", &path, e))
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let path = self.resolve(key)?;
+            tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+                ErrorKind::NotFound => AppError::NotFound(format!("File not found: {}", key)),
+                _ => io_error("read", &path, e),
+            })
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.resolve(key)?;
+            match tokio::fs::remove_file(&path).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error("delete", &path, e)),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn delete_prefix<'a>(&'a self, prefix: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.resolve(prefix)?;
+            match tokio::fs::remove_dir_all(&path).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error("delete", &path, e)),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_storage_roundtrip() {
+        let root = std::env::temp_dir().join(format!("demeter-storage-{}", uuid::Uuid::new_v4()));
+        let storage = LocalFileStorage::new(&root);
+
+        storage.put("projects/1/tasks/2/3", b"hello").await.unwrap();
+        assert_eq!(storage.get("projects/1/tasks/2/3").await.unwrap(), b"hello");
+
+        assert!(storage.put("projects/../../etc/passwd", b"x").await.is_err());
+        assert!(storage.get("projects//1").await.is_err());
+
+        storage.delete_prefix("projects/1").await.unwrap();
+        assert!(matches!(
+            storage.get("projects/1/tasks/2/3").await,
+            Err(AppError::NotFound(_))
+        ));
+        storage.delete("projects/1/tasks/2/3").await.unwrap();
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}
@@ -8,6 +8,7 @@ pub struct AppConfig {
     pub database: DatabaseConfig,
     pub jwt: JwtConfig,
     pub snowflake: SnowflakeConfig,
+    pub storage: StorageConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,6 +46,31 @@ impl Default for SnowflakeConfig {
     }
 }
 
+/// 附件存储配置
+#[derive(Debug, Deserialize, Clone)]
+pub struct StorageConfig {
+    /// 本地存储根目录
+    pub root: String,
+    /// 单个附件最大字节数
+    pub max_file_size: u64,
+    /// 允许上传的 MIME 类型，逗号分隔，支持 `image/*` 形式的通配
+    pub allowed_content_types: String,
+}
+
+impl StorageConfig {
+    pub fn is_allowed_content_type(&self, content_type: &str) -> bool {
+        let content_type = content_type.trim().to_ascii_lowercase();
+        self.allowed_content_types
+            .split(',')
+            .map(|t| t.trim().to_ascii_lowercase())
+            .filter(|t| !t.is_empty())
+            .any(|allowed| match allowed.strip_suffix('*') {
+                Some(prefix) => content_type.starts_with(prefix),
+                None => content_type == allowed,
+            })
+    }
+}
+
 impl AppConfig {
     pub fn from_env() -> Result<Self, config::ConfigError> {
         dotenvy::dotenv().ok();
@@ -54,6 +80,14 @@ impl AppConfig {
             .set_default("snowflake.datacenter_id", 1)?
             .set_default("snowflake.machine_id", 1)?
             .set_default("server.cors_origin", "http://localhost:3000")?
+            .set_default("storage.root", "uploads")?
+            .set_default("storage.max_file_size", 20 * 1024 * 1024)?
+            .set_default(
+                "storage.allowed_content_types",
+                "image/*,text/plain,text/csv,text/markdown,application/pdf,application/zip,\
+                 application/msword,application/vnd.ms-excel,application/vnd.ms-powerpoint,\
+                 application/vnd.openxmlformats-officedocument.*",
+            )?
             .build()?;
 
         config.try_deserialize()
@@ -6,15 +6,14 @@ use axum::http::{header, HeaderValue, Method};
 use axum::Router;
 use common::app_state::AppState;
 use common::snowflake::SnowflakeIdBucket;
+use common::storage::LocalFileStorage;
 use modules::{business, organization};
 use sqlx::postgres::PgPoolOptions;
 use std::sync::Arc;
 use std::time::Duration;
 use tower_http::cors::CorsLayer;
 use tower_http::timeout::TimeoutLayer;
-use tower_http::trace::{
-    DefaultMakeSpan, DefaultOnFailure, DefaultOnRequest, DefaultOnResponse, TraceLayer,
-};
+use tower_http::trace::{DefaultOnFailure, DefaultOnRequest, DefaultOnResponse, TraceLayer};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -63,8 +62,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         config.snowflake.machine_id
     );
 
+    // Initialize attachment storage (local filesystem)
+    let storage = Arc::new(LocalFileStorage::new(&config.storage.root));
+    tracing::info!("Attachment storage root: {}", config.storage.root);
+
     // Create global application state
-    let app_state = AppState::new(pool.clone(), config.jwt.clone(), id_generator);
+    let app_state = AppState::new(
+        pool.clone(),
+        config.jwt.clone(),
+        id_generator,
+        storage,
+        config.storage.clone(),
+    );
 
     // Configure CORS
     let cors = CorsLayer::new()
@@ -88,19 +97,58 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             app_state.clone(),
         ))
         .merge(modules::holiday::holiday_routes(app_state.clone()))
+        .merge(modules::holiday::holiday_calendar::holiday_calendar_routes(
+            app_state.clone(),
+        ))
+        .merge(modules::calendar_feed::calendar_feed_routes(
+            app_state.clone(),
+        ))
         .merge(organization::team::team_routes(app_state.clone()))
         .merge(business::project::project_routes(app_state.clone()))
         .merge(business::project::task::task_routes(app_state.clone()))
+        .merge(business::project::task::dependency::task_dependency_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::assignee::task_assignee_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::baseline::baseline_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::history::task_history_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::comment::task_comment_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::attachment::task_attachment_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::worklog::task_worklog_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::checkpoint::task_checkpoint_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::task::recurrence::task_recurrence_routes(
+            app_state.clone(),
+        ))
+        .merge(business::project::workflow::workflow_routes(app_state.clone()))
         .merge(business::project::permission::permission_routes(
             app_state.clone(),
         ))
+        .merge(business::workload::workload_routes(app_state.clone()))
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(
-                    DefaultMakeSpan::new()
-                        .include_headers(false)
-                        .level(tracing::Level::DEBUG),
-                )
+                // Custom span instead of DefaultMakeSpan so feed tokens never reach the logs
+                .make_span_with(|request: &axum::extract::Request| {
+                    tracing::debug_span!(
+                        "request",
+                        method = %request.method(),
+                        uri = %modules::calendar_feed::middleware::redact_feed_token(request.uri()),
+                        version = ?request.version(),
+                    )
+                })
                 .on_request(DefaultOnRequest::new().level(tracing::Level::DEBUG))
                 .on_response(
                     DefaultOnResponse::new()
@@ -10,6 +10,9 @@ use crate::modules::business::project::models::{
 use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
 use crate::modules::business::project::permission::repository::ProjectMemberRepository;
 use crate::modules::business::project::repository::{ProjectRepository, ProjectVisitRepository};
+use crate::modules::business::project::task::attachment::handlers::cleanup_project_files;
+use crate::modules::business::project::workflow::definition::{build_workflow, default_workflow};
+use crate::modules::holiday::holiday_calendar::handlers::ensure_calendar_exists;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -119,8 +122,14 @@ pub async fn create_project(
     let project_id = state
         .generate_id()
         .map_err(|e| AppError::InternalError(format!("Failed to generate project ID: {}", e)))?;
+    let workflow = build_workflow(&default_workflow(), || {
+        state
+            .generate_id()
+            .map_err(|e| AppError::InternalError(format!("Failed to generate workflow ID: {}", e)))
+    })?;
     let project =
-        ProjectRepository::create_project(&state.pool, project_id, params, creator_id).await?;
+        ProjectRepository::create_project(&state.pool, project_id, params, &workflow, creator_id)
+            .await?;
 
     // 自动将创建者添加为项目 Owner
     let member_id = state
@@ -148,6 +157,9 @@ pub async fn update_project(
     Json(params): Json<UpdateProjectParams>,
 ) -> AppResult<Json<ApiResponse<Project>>> {
     perm.require(Permission::ProjectEdit)?;
+    if let Some(Some(calendar_id)) = params.holiday_calendar_id {
+        ensure_calendar_exists(&state.pool, calendar_id).await?;
+    }
     let updater_id = claims.sub;
     let project = ProjectRepository::update_project(&state.pool, project_id.0, params, updater_id)
         .await?
@@ -171,6 +183,7 @@ pub async fn delete_project(
             project_id
         )));
     }
+    cleanup_project_files(&state, &[project_id.0]).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -185,7 +198,8 @@ pub async fn batch_delete_projects(
         ));
     }
     let project_ids: Vec<i64> = params.ids.into_iter().map(|id| id.0).collect();
-    ProjectRepository::batch_delete_projects(&state.pool, project_ids).await?;
+    ProjectRepository::batch_delete_projects(&state.pool, project_ids.clone()).await?;
+    cleanup_project_files(&state, &project_ids).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -4,5 +4,6 @@ pub mod permission;
 pub mod repository;
 pub mod routes;
 pub mod task;
+pub mod workflow;
 
 pub use routes::*;
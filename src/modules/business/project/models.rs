@@ -15,6 +15,10 @@ pub struct Project {
     pub order: Option<f64>,
     /// 可见性: 0=private, 1=internal, 2=public
     pub visibility: i32,
+    /// 使用的假期日历，NULL 表示默认日历
+    pub holiday_calendar_id: Option<Id>,
+    /// 任务写入时是否自动同步父任务起止时间
+    pub sync_parent_dates: bool,
     pub creator_id: Id,
     pub updater_id: Option<Id>,
     pub create_date_time: chrono::NaiveDateTime,
@@ -62,6 +66,11 @@ pub struct UpdateProjectParams {
     pub order: Option<Option<f64>>,
     /// NOT NULL 字段
     pub visibility: Option<i32>,
+    /// 可空字段，双层 Option：Some(None) = 恢复为默认假期日历
+    #[serde(default, deserialize_with = "crate::common::serde_helpers::double_option::deserialize")]
+    pub holiday_calendar_id: Option<Option<Id>>,
+    /// NOT NULL 字段
+    pub sync_parent_dates: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
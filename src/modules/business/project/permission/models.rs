@@ -71,7 +71,6 @@ impl ProjectRole {
         }
     }
 
-    #[allow(unused)]
     pub fn as_i32(self) -> i32 {
         self as i32
     }
@@ -138,6 +137,17 @@ pub enum Permission {
     TaskDeleteAll,
     TaskDeleteOwn,
     TaskBatchOperate,
+    // 评论
+    TaskComment,
+    /// 删除他人评论
+    TaskCommentManage,
+    // 工时
+    Worklog,
+    /// 代他人登记、修改他人工时
+    WorklogManage,
+    // 检查点
+    /// 审批（签核 / 驳回）检查点任务
+    CheckpointApprove,
 }
 
 impl Permission {
@@ -161,6 +171,11 @@ impl Permission {
                 Permission::TaskDeleteAll,
                 Permission::TaskDeleteOwn,
                 Permission::TaskBatchOperate,
+                Permission::TaskComment,
+                Permission::TaskCommentManage,
+                Permission::Worklog,
+                Permission::WorklogManage,
+                Permission::CheckpointApprove,
             ],
             ProjectRole::Admin => vec![
                 Permission::ProjectView,
@@ -177,6 +192,11 @@ impl Permission {
                 Permission::TaskDeleteAll,
                 Permission::TaskDeleteOwn,
                 Permission::TaskBatchOperate,
+                Permission::TaskComment,
+                Permission::TaskCommentManage,
+                Permission::Worklog,
+                Permission::WorklogManage,
+                Permission::CheckpointApprove,
             ],
             ProjectRole::Maintainer => vec![
                 Permission::ProjectView,
@@ -191,11 +211,19 @@ impl Permission {
                 Permission::TaskDeleteAll,
                 Permission::TaskDeleteOwn,
                 Permission::TaskBatchOperate,
+                Permission::TaskComment,
+                Permission::TaskCommentManage,
+                Permission::Worklog,
+                Permission::CheckpointApprove,
             ],
             ProjectRole::Member => vec![
                 Permission::ProjectView,
                 Permission::AttributeConfigView,
                 Permission::TaskView,
+                Permission::TaskEditOwn,
+                Permission::TaskDeleteOwn,
+                Permission::TaskComment,
+                Permission::Worklog,
             ],
             ProjectRole::Viewer => vec![
                 Permission::ProjectView,
@@ -229,12 +257,12 @@ impl ProjectPermission {
         Ok(())
     }
 
-    /// 检查是否可操作某资源（考虑 own 类型权限）
-    pub fn can_operate(&self, edit_all: Permission, edit_own: Permission, creator_id: i64) -> bool {
+    /// 检查是否可操作某资源（考虑 own 类型权限）；is_owner 表示当前用户是否为资源负责人
+    pub fn can_operate(&self, edit_all: Permission, edit_own: Permission, is_owner: bool) -> bool {
         if self.has_permission(edit_all) {
             return true;
         }
-        if self.has_permission(edit_own) && creator_id == self.user_id {
+        if self.has_permission(edit_own) && is_owner {
             return true;
         }
         false
@@ -10,6 +10,22 @@ pub struct ProjectTeamRoleRepository;
 pub struct ProjectDepartmentRoleRepository;
 pub struct ProjectPermissionResolver;
 
+/// 用户 u 是项目 $1 的有效成员（项目创建者，或通过个人 / 团队 / 部门获得角色）
+const EFFECTIVE_MEMBER_CONDITION: &str = r#"(
+    EXISTS (SELECT 1 FROM projects p WHERE p.id = $1 AND p.creator_id = u.id)
+    OR EXISTS (SELECT 1 FROM project_members pm WHERE pm.project_id = $1 AND pm.user_id = u.id)
+    OR EXISTS (
+        SELECT 1 FROM project_team_roles ptr
+        JOIN user_teams ut ON ut.team_id = ptr.team_id
+        WHERE ptr.project_id = $1 AND ut.user_id = u.id
+    )
+    OR EXISTS (
+        SELECT 1 FROM project_department_roles pdr
+        JOIN user_departments ud ON ud.department_id = pdr.department_id
+        WHERE pdr.project_id = $1 AND ud.user_id = u.id
+    )
+)"#;
+
 // ──────────────── 权限解析：多源取最高 ────────────────
 
 impl ProjectPermissionResolver {
@@ -104,6 +120,43 @@ impl ProjectPermissionResolver {
         Ok(row.and_then(|r| r.0))
     }
 
+    /// 从 user_ids 中筛选出项目的有效成员（项目创建者，或通过个人 / 团队 / 部门获得角色）
+    pub async fn get_effective_member_ids(
+        pool: &PgPool,
+        project_id: i64,
+        user_ids: &[i64],
+    ) -> AppResult<Vec<i64>> {
+        let sql = format!(
+            "SELECT u.id FROM users u WHERE u.id = ANY($2) AND {}",
+            EFFECTIVE_MEMBER_CONDITION,
+        );
+        let rows: Vec<(i64,)> = sqlx::query_as(&sql)
+            .bind(project_id)
+            .bind(user_ids)
+            .fetch_all(pool)
+            .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    /// 按用户名查询项目中已启用的有效成员，返回 (username, user_id)
+    pub async fn get_active_members_by_usernames(
+        pool: &PgPool,
+        project_id: i64,
+        usernames: &[String],
+    ) -> AppResult<Vec<(String, i64)>> {
+        let sql = format!(
+            "SELECT u.username, u.id FROM users u \
+             WHERE u.username = ANY($2) AND u.is_active AND {}",
+            EFFECTIVE_MEMBER_CONDITION,
+        );
+        let rows: Vec<(String, i64)> = sqlx::query_as(&sql)
+            .bind(project_id)
+            .bind(usernames)
+            .fetch_all(pool)
+            .await?;
+        Ok(rows)
+    }
+
     /// 获取所有角色来源（用于 my-permissions 详情）
     pub async fn get_role_sources(
         pool: &PgPool,
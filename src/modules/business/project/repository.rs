@@ -3,7 +3,9 @@ use crate::modules::business::project::models::{
     CreateProjectParams, Project, ProjectQueryParams,
     RecentlyVisitedQueryParams, UpdateProjectParams,
 };
-use sqlx::PgPool;
+use crate::modules::business::project::workflow::models::NewWorkflow;
+use crate::modules::business::project::workflow::repository::WorkflowRepository;
+use sqlx::{PgConnection, PgPool};
 use sqlx::QueryBuilder;
 
 pub struct ProjectVisitRepository;
@@ -12,12 +14,12 @@ pub struct ProjectRepository;
 
 /// projects 表 SELECT 列
 const PROJECT_COLUMNS: &str = r#"id, project_name, description, start_date_time, end_date_time, 
-    project_status, version, "order", visibility, creator_id, updater_id, 
+    project_status, version, "order", visibility, holiday_calendar_id, sync_parent_dates, creator_id, updater_id, 
     create_date_time, update_date_time"#;
 
 /// projects 表 RETURNING 列
 const PROJECT_RETURNING: &str = r#" RETURNING id, project_name, description, start_date_time, end_date_time, 
-    project_status, version, "order", visibility, creator_id, updater_id, 
+    project_status, version, "order", visibility, holiday_calendar_id, sync_parent_dates, creator_id, updater_id, 
     create_date_time, update_date_time"#;
 
 impl ProjectRepository {
@@ -116,6 +118,20 @@ impl ProjectRepository {
         Ok(project)
     }
 
+    /// 项目是否开启父任务起止时间同步；在调用方事务内读取，项目不存在时为 false
+    pub async fn is_sync_parent_dates_enabled(
+        conn: &mut PgConnection,
+        project_id: i64,
+    ) -> AppResult<bool> {
+        let enabled: Option<(bool,)> =
+            sqlx::query_as("SELECT sync_parent_dates FROM projects WHERE id = $1")
+                .bind(project_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+
+        Ok(enabled.is_some_and(|(enabled,)| enabled))
+    }
+
     pub async fn get_project_by_name(
         pool: &PgPool,
         project_name: &str,
@@ -216,7 +232,7 @@ impl ProjectRepository {
         let projects = sqlx::query_as::<_, Project>(
             r#"
             SELECT DISTINCT p.id, p.project_name, p.description, p.start_date_time, p.end_date_time,
-                p.project_status, p.version, p."order", p.visibility, p.creator_id, p.updater_id,
+                p.project_status, p.version, p."order", p.visibility, p.holiday_calendar_id, p.sync_parent_dates, p.creator_id, p.updater_id,
                 p.create_date_time, p.update_date_time,
                 LEAST(
                     CASE WHEN p.creator_id = $1 THEN 0 END,
@@ -252,10 +268,12 @@ impl ProjectRepository {
         Ok(projects)
     }
 
+    /// 创建项目并写入默认工作流（同一事务）
     pub async fn create_project(
         pool: &PgPool,
         project_id: i64,
         params: CreateProjectParams,
+        workflow: &NewWorkflow,
         creator_id: i64,
     ) -> AppResult<Project> {
         let sql = format!(
@@ -265,6 +283,7 @@ impl ProjectRepository {
             "#,
             PROJECT_RETURNING,
         );
+        let mut tx = pool.begin().await?;
         let project = sqlx::query_as::<_, Project>(&sql)
         .bind(project_id)
         .bind(&params.project_name)
@@ -276,9 +295,12 @@ impl ProjectRepository {
         .bind(params.order)
         .bind(params.visibility.unwrap_or(0))
         .bind(creator_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        WorkflowRepository::insert_workflow(&mut tx, project_id, workflow, creator_id).await?;
+        tx.commit().await?;
+
         Ok(project)
     }
 
@@ -349,6 +371,20 @@ impl ProjectRepository {
             has_set = true;
         }
 
+        if let Some(ref calendar_opt) = params.holiday_calendar_id {
+            if has_set { qb.push(", "); }
+            qb.push("holiday_calendar_id = ");
+            qb.push_bind(calendar_opt.map(|id| id.0));
+            has_set = true;
+        }
+
+        if let Some(ref sync) = params.sync_parent_dates {
+            if has_set { qb.push(", "); }
+            qb.push("sync_parent_dates = ");
+            qb.push_bind(*sync);
+            has_set = true;
+        }
+
         // 若没有任何业务字段出现，仅更新 updater_id + update_date_time
         if has_set { qb.push(", "); }
         qb.push("updater_id = ");
@@ -367,6 +403,10 @@ impl ProjectRepository {
 
     pub async fn delete_project(pool: &PgPool, project_id: i64) -> AppResult<bool> {
         let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM project_task_dependencies WHERE project_id = $1")
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
         sqlx::query("DELETE FROM project_tasks WHERE project_id = $1")
             .bind(project_id)
             .execute(&mut *tx)
@@ -405,6 +445,10 @@ impl ProjectRepository {
             return Ok(0);
         }
         let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM project_task_dependencies WHERE project_id = ANY($1)")
+            .bind(&project_ids)
+            .execute(&mut *tx)
+            .await?;
         sqlx::query("DELETE FROM project_tasks WHERE project_id = ANY($1)")
             .bind(&project_ids)
             .execute(&mut *tx)
@@ -501,7 +545,7 @@ impl ProjectVisitRepository {
         let projects = sqlx::query_as::<_, Project>(
             r#"
             SELECT p.id, p.project_name, p.description, p.start_date_time, p.end_date_time,
-                   p.project_status, p.version, p."order", p.visibility, p.creator_id, p.updater_id,
+                   p.project_status, p.version, p."order", p.visibility, p.holiday_calendar_id, p.sync_parent_dates, p.creator_id, p.updater_id,
                    p.create_date_time, p.update_date_time,
                    LEAST(
                        CASE WHEN p.creator_id = $1 THEN 0 END,
@@ -0,0 +1,138 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{field_error, AppError, AppResult, FieldError};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::{ApiResponse, PaginatedResponse};
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::permission::repository::ProjectPermissionResolver;
+use crate::modules::business::project::task::assignee::models::{
+    MyTask, MyTaskQueryParams, SetTaskAssigneesParams, TaskAssignee, TaskAssigneeQueryParams,
+};
+use crate::modules::business::project::task::assignee::repository::TaskAssigneeRepository;
+use crate::modules::business::project::task::handlers::require_task_permission;
+use crate::modules::business::project::task::repository::TaskRepository;
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+
+pub async fn get_assignees(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Query(params): Query<TaskAssigneeQueryParams>,
+) -> AppResult<Json<ApiResponse<Vec<TaskAssignee>>>> {
+    perm.require(Permission::TaskView)?;
+    let assignees = TaskAssigneeRepository::get_assignees(
+        &state.pool,
+        project_id.0,
+        params.task_id.map(|id| id.0),
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(assignees)))
+}
+
+pub async fn get_task_assignees(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<Vec<TaskAssignee>>>> {
+    perm.require(Permission::TaskView)?;
+    TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    let assignees =
+        TaskAssigneeRepository::get_assignees(&state.pool, project_id.0, Some(task_id.0)).await?;
+    Ok(Json(ApiResponse::success(assignees)))
+}
+
+pub async fn set_task_assignees(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<SetTaskAssigneesParams>,
+) -> AppResult<Json<ApiResponse<Vec<TaskAssignee>>>> {
+    let task = TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[task.id.0],
+        "edit",
+    )
+    .await?;
+
+    let mut user_ids: Vec<i64> = Vec::with_capacity(params.user_ids.len());
+    for id in &params.user_ids {
+        if !user_ids.contains(&id.0) {
+            user_ids.push(id.0);
+        }
+    }
+
+    // 指派人必须是项目的有效成员（个人 / 团队 / 部门授权）
+    let members =
+        ProjectPermissionResolver::get_effective_member_ids(&state.pool, project_id.0, &user_ids)
+            .await?;
+    let errors: Vec<FieldError> = params
+        .user_ids
+        .iter()
+        .enumerate()
+        .filter(|(_, id)| !members.contains(&id.0))
+        .map(|(i, id)| {
+            field_error(
+                format!("userIds[{}]", i),
+                "not_project_member",
+                format!("User {} is not a member of this project", id),
+            )
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            "Invalid assignees".to_string(),
+            errors,
+        ));
+    }
+
+    let mut assignees = Vec::with_capacity(user_ids.len());
+    for user_id in user_ids {
+        let assignee_id = state.generate_id().map_err(|e| {
+            AppError::InternalError(format!("Failed to generate task assignee ID: {}", e))
+        })?;
+        assignees.push((assignee_id, user_id));
+    }
+
+    let assignees = TaskAssigneeRepository::set_assignees(
+        &state.pool,
+        project_id.0,
+        task_id.0,
+        assignees,
+        claims.sub,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(assignees)))
+}
+
+/// 跨项目查询指派给当前用户的任务
+pub async fn get_my_tasks(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<MyTaskQueryParams>,
+) -> AppResult<Json<PaginatedResponse<MyTask>>> {
+    let page = params.page.unwrap_or(1);
+    let per_page = params.per_page.unwrap_or(20);
+    let (tasks, total) = TaskAssigneeRepository::get_my_tasks(
+        &state.pool,
+        claims.sub,
+        claims.is_super_admin(),
+        &params,
+    )
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        tasks,
+        total,
+        page,
+        per_page,
+        "/api/v1/tasks/my",
+    )))
+}
@@ -0,0 +1,6 @@
+pub mod handlers;
+pub mod models;
+pub mod repository;
+pub mod routes;
+
+pub use routes::*;
@@ -0,0 +1,54 @@
+use crate::common::id::Id;
+use crate::modules::business::project::task::models::Task;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAssignee {
+    pub id: Id,
+    pub project_id: Id,
+    pub task_id: Id,
+    pub user_id: Id,
+    pub creator_id: Id,
+    pub create_date_time: chrono::NaiveDateTime,
+    // JOIN 字段
+    pub username: Option<String>,
+    pub full_name: Option<String>,
+}
+
+/// 设置任务指派人（整体替换），空列表表示取消所有指派
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTaskAssigneesParams {
+    pub user_ids: Vec<Id>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAssigneeQueryParams {
+    pub task_id: Option<Id>,
+}
+
+/// 我的任务查询参数
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MyTaskQueryParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub project_id: Option<Id>,
+    pub task_type: Option<i32>,
+    /// 日期范围（含两端），返回与该范围有交集的任务
+    pub date_from: Option<chrono::NaiveDate>,
+    pub date_to: Option<chrono::NaiveDate>,
+}
+
+/// 我的任务（附带所属项目名称）
+#[derive(Debug, Clone, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MyTask {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub task: Task,
+    pub project_name: String,
+}
@@ -0,0 +1,148 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::assignee::models::{
+    MyTask, MyTaskQueryParams, TaskAssignee,
+};
+use sqlx::PgPool;
+
+pub struct TaskAssigneeRepository;
+
+/// project_task_assignees 表 SELECT 列（含用户 JOIN 字段）
+const ASSIGNEE_COLUMNS: &str = r#"a.id, a.project_id, a.task_id, a.user_id, a.creator_id,
+    a.create_date_time, u.username, u.full_name"#;
+
+/// 我的任务：指派给当前用户、且当前用户仍可访问所属项目的任务
+/// $1 = user_id，$2 = 是否超级管理员（跳过项目访问检查）
+const MY_TASK_CONDITIONS: &str = r#"
+    FROM project_tasks t
+    JOIN projects p ON p.id = t.project_id
+    WHERE EXISTS (
+        SELECT 1 FROM project_task_assignees a WHERE a.task_id = t.id AND a.user_id = $1
+    )
+    AND (
+        $2
+        OR p.creator_id = $1
+        OR p.visibility IN (1, 2)
+        OR EXISTS (SELECT 1 FROM project_members pm WHERE pm.project_id = p.id AND pm.user_id = $1)
+        OR EXISTS (
+            SELECT 1 FROM project_team_roles ptr
+            JOIN user_teams ut ON ut.team_id = ptr.team_id
+            WHERE ptr.project_id = p.id AND ut.user_id = $1
+        )
+        OR EXISTS (
+            SELECT 1 FROM project_department_roles pdr
+            JOIN user_departments ud ON ud.department_id = pdr.department_id
+            WHERE pdr.project_id = p.id AND ud.user_id = $1
+        )
+    )
+    AND ($3::BIGINT IS NULL OR t.project_id = $3)
+    AND ($4::INTEGER IS NULL OR t.task_type = $4)
+    AND ($5::DATE IS NULL OR t.end_date_time >= $5)
+    AND ($6::DATE IS NULL OR t.start_date_time < $6::DATE + 1)"#;
+
+impl TaskAssigneeRepository {
+    pub async fn get_assignees(
+        pool: &PgPool,
+        project_id: i64,
+        task_id: Option<i64>,
+    ) -> AppResult<Vec<TaskAssignee>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_assignees a
+               LEFT JOIN users u ON u.id = a.user_id
+               WHERE a.project_id = $1 AND ($2::BIGINT IS NULL OR a.task_id = $2)
+               ORDER BY a.task_id ASC, a.create_date_time ASC"#,
+            ASSIGNEE_COLUMNS,
+        );
+        let assignees = sqlx::query_as::<_, TaskAssignee>(&sql)
+            .bind(project_id)
+            .bind(task_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(assignees)
+    }
+
+    /// 整体替换任务指派人（同一事务）：删除不在列表中的指派，新增缺失的指派
+    pub async fn set_assignees(
+        pool: &PgPool,
+        project_id: i64,
+        task_id: i64,
+        assignees: Vec<(i64, i64)>,
+        creator_id: i64,
+    ) -> AppResult<Vec<TaskAssignee>> {
+        let (ids, user_ids): (Vec<i64>, Vec<i64>) = assignees.into_iter().unzip();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query(
+            "DELETE FROM project_task_assignees WHERE task_id = $1 AND NOT (user_id = ANY($2))",
+        )
+        .bind(task_id)
+        .bind(&user_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"INSERT INTO project_task_assignees (id, project_id, task_id, user_id, creator_id)
+               SELECT data.id, $1, $2, data.user_id, $5
+               FROM unnest($3::bigint[], $4::bigint[]) AS data(id, user_id)
+               ON CONFLICT (task_id, user_id) DO NOTHING"#,
+        )
+        .bind(project_id)
+        .bind(task_id)
+        .bind(&ids)
+        .bind(&user_ids)
+        .bind(creator_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::get_assignees(pool, project_id, Some(task_id)).await
+    }
+
+    pub async fn get_my_tasks(
+        pool: &PgPool,
+        user_id: i64,
+        is_super_admin: bool,
+        params: &MyTaskQueryParams,
+    ) -> AppResult<(Vec<MyTask>, i64)> {
+        let page = params.page.unwrap_or(1);
+        let page_size = params.per_page.unwrap_or(20);
+        let offset = (page - 1) * page_size;
+
+        let sql = format!(
+            r#"SELECT t.id, t.task_name, t.parent_id, t.project_id, t."order",
+                   t.custom_attributes,
+                   t.start_date_time, t.end_date_time, t.task_type, t.percent_complete, t.status_id,
+                   t.creator_id, t.updater_id, t.create_date_time, t.update_date_time,
+                   p.project_name
+               {}
+               ORDER BY t.start_date_time ASC, t.id ASC
+               LIMIT $7 OFFSET $8"#,
+            MY_TASK_CONDITIONS,
+        );
+        let tasks = sqlx::query_as::<_, MyTask>(&sql)
+            .bind(user_id)
+            .bind(is_super_admin)
+            .bind(params.project_id)
+            .bind(params.task_type)
+            .bind(params.date_from)
+            .bind(params.date_to)
+            .bind(page_size)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        let total: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) {}", MY_TASK_CONDITIONS))
+            .bind(user_id)
+            .bind(is_super_admin)
+            .bind(params.project_id)
+            .bind(params.task_type)
+            .bind(params.date_from)
+            .bind(params.date_to)
+            .fetch_one(pool)
+            .await?;
+
+        Ok((tasks, total.0))
+    }
+}
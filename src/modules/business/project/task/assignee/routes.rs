@@ -0,0 +1,43 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::assignee::handlers;
+use axum::{
+    middleware,
+    routing::{get, put},
+    Router,
+};
+
+pub fn task_assignee_routes(state: AppState) -> Router {
+    let project_routes = Router::new()
+        .route(
+            "/projects/{project_id}/task-assignees",
+            get(handlers::get_assignees),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/assignees",
+            get(handlers::get_task_assignees),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/assignees",
+            put(handlers::set_task_assignees),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ));
+
+    // 我的任务：跨项目，仅需登录
+    let my_routes = Router::new().route("/tasks/my", get(handlers::get_my_tasks));
+
+    Router::new()
+        .merge(project_routes)
+        .merge(my_routes)
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
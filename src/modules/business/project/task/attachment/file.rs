@@ -0,0 +1,105 @@
+/// 文件名最大长度（字符）
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+pub fn project_prefix(project_id: i64) -> String {
+    format!("projects/{}", project_id)
+}
+
+pub fn task_prefix(project_id: i64, task_id: i64) -> String {
+    format!("{}/tasks/{}", project_prefix(project_id), task_id)
+}
+
+/// 附件在存储中的 key；不使用原始文件名，避免路径注入
+pub fn attachment_key(project_id: i64, task_id: i64, attachment_id: i64) -> String {
+    format!("{}/{}", task_prefix(project_id, task_id), attachment_id)
+}
+
+/// 只保留文件名部分（去掉客户端路径）与可见字符，结果为空时返回 None
+pub fn sanitize_file_name(name: &str) -> Option<String> {
+    let name: String = name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_FILE_NAME_LENGTH)
+        .collect();
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// 客户端未提供具体类型时按扩展名推断
+pub fn guess_content_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "md" => "text/markdown",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Content-Disposition 头：ASCII 回退文件名 + RFC 5987 编码的 UTF-8 文件名
+pub fn content_disposition(file_name: &str) -> String {
+    let fallback: String = file_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() && c != '"' && c != '\\' || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_helpers() {
+        assert_eq!(
+            sanitize_file_name("C:\\Users\\a\\需求 v2.pdf").as_deref(),
+            Some("需求 v2.pdf")
+        );
+        assert_eq!(sanitize_file_name("../"), None);
+        assert_eq!(guess_content_type("Screen.PNG"), "image/png");
+        assert_eq!(guess_content_type("README"), "application/octet-stream");
+        assert_eq!(
+            content_disposition("需求 \"v2\".pdf"),
+            "attachment; filename=\"__ _v2_.pdf\"; \
+             filename*=UTF-8''%E9%9C%80%E6%B1%82%20%22v2%22.pdf"
+        );
+    }
+}
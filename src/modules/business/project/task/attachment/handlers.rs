@@ -0,0 +1,253 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::attachment::file::{
+    attachment_key, content_disposition, guess_content_type, project_prefix, sanitize_file_name,
+    task_prefix,
+};
+use crate::modules::business::project::task::attachment::models::{
+    NewTaskAttachment, TaskAttachment, FILE_FIELD_NAME, MAX_FILES_PER_UPLOAD,
+};
+use crate::modules::business::project::task::attachment::repository::TaskAttachmentRepository;
+use crate::modules::business::project::task::handlers::require_task_permission;
+use crate::modules::business::project::task::models::Task;
+use crate::modules::business::project::task::repository::TaskRepository;
+use axum::{
+    extract::{multipart::Field, Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use sqlx::PgPool;
+
+/// 查询附件并校验其属于当前项目的指定任务
+async fn get_task_attachment(
+    pool: &PgPool,
+    project_id: i64,
+    task_id: Id,
+    attachment_id: Id,
+) -> AppResult<TaskAttachment> {
+    TaskAttachmentRepository::get_attachment_by_id(pool, attachment_id.0)
+        .await?
+        .filter(|a| a.project_id.0 == project_id && a.task_id == task_id)
+        .ok_or_else(|| AppError::NotFound(format!("Attachment not found: {}", attachment_id)))
+}
+
+/// 逐块读取文件内容，超过大小限制时立即中止
+async fn read_field(field: &mut Field<'_>, file_name: &str, max_size: u64) -> AppResult<Vec<u8>> {
+    let mut data = Vec::new();
+    while let Some(chunk) = field.chunk().await? {
+        if (data.len() + chunk.len()) as u64 > max_size {
+            return Err(AppError::BadRequest(format!(
+                "File {} exceeds the maximum size of {} bytes",
+                file_name, max_size
+            )));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+/// 删除任务的附件文件（数据库记录随任务级联删除）；清理失败只记录日志
+pub async fn cleanup_task_files(state: &AppState, tasks: &[Task]) {
+    for task in tasks {
+        let prefix = task_prefix(task.project_id.0, task.id.0);
+        if let Err(e) = state.storage.delete_prefix(&prefix).await {
+            tracing::warn!("Failed to clean up attachments of task {}: {:?}", task.id, e);
+        }
+    }
+}
+
+/// 删除项目的全部附件文件；清理失败只记录日志
+pub async fn cleanup_project_files(state: &AppState, project_ids: &[i64]) {
+    for project_id in project_ids {
+        if let Err(e) = state.storage.delete_prefix(&project_prefix(*project_id)).await {
+            tracing::warn!(
+                "Failed to clean up attachments of project {}: {:?}",
+                project_id,
+                e
+            );
+        }
+    }
+}
+
+pub async fn get_attachments(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<Vec<TaskAttachment>>>> {
+    perm.require(Permission::TaskView)?;
+    TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    let attachments = TaskAttachmentRepository::get_attachments(&state.pool, task_id.0).await?;
+    Ok(Json(ApiResponse::success(attachments)))
+}
+
+/// 上传附件（multipart/form-data，文件字段名为 file，可重复）
+pub async fn upload_attachments(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<ApiResponse<Vec<TaskAttachment>>>)> {
+    let task = TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[task.id.0],
+        "edit",
+    )
+    .await?;
+
+    // 文件逐个写入存储，记录在一个事务中写入；任一步失败时删除本次已写入的文件
+    let mut storage_keys = Vec::new();
+    let result = async {
+        let new_attachments =
+            receive_files(&state, &mut multipart, project_id.0, task_id.0, &mut storage_keys)
+                .await?;
+        let mut tx = state.pool.begin().await?;
+        let mut attachments = Vec::with_capacity(new_attachments.len());
+        for new_attachment in new_attachments {
+            attachments.push(
+                TaskAttachmentRepository::create_attachment(&mut tx, new_attachment, claims.sub)
+                    .await?,
+            );
+        }
+        tx.commit().await?;
+        Ok(attachments)
+    }
+    .await;
+
+    match result {
+        Ok(attachments) => Ok((StatusCode::CREATED, Json(ApiResponse::success(attachments)))),
+        Err(e) => {
+            for storage_key in &storage_keys {
+                if let Err(err) = state.storage.delete(storage_key).await {
+                    tracing::warn!("Failed to clean up attachment {}: {:?}", storage_key, err);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+/// 读取并保存上传的文件，已写入存储的 key 记录到 `storage_keys`
+async fn receive_files(
+    state: &AppState,
+    multipart: &mut Multipart,
+    project_id: i64,
+    task_id: i64,
+    storage_keys: &mut Vec<String>,
+) -> AppResult<Vec<NewTaskAttachment>> {
+    let config = &state.storage_config;
+    let mut new_attachments = Vec::new();
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some(FILE_FIELD_NAME) {
+            continue;
+        }
+        if new_attachments.len() >= MAX_FILES_PER_UPLOAD {
+            return Err(AppError::BadRequest(format!(
+                "At most {} files can be uploaded at once",
+                MAX_FILES_PER_UPLOAD
+            )));
+        }
+        let file_name = field
+            .file_name()
+            .and_then(sanitize_file_name)
+            .ok_or_else(|| AppError::BadRequest("File name is required".to_string()))?;
+        let content_type = match field.content_type() {
+            Some(t) if !t.is_empty() && t != "application/octet-stream" => t.to_string(),
+            _ => guess_content_type(&file_name).to_string(),
+        };
+        if !config.is_allowed_content_type(&content_type) {
+            return Err(AppError::BadRequest(format!(
+                "File type {} is not allowed",
+                content_type
+            )));
+        }
+        let data = read_field(&mut field, &file_name, config.max_file_size).await?;
+        if data.is_empty() {
+            return Err(AppError::BadRequest(format!("File {} is empty", file_name)));
+        }
+
+        let attachment_id = state.generate_id().map_err(|e| {
+            AppError::InternalError(format!("Failed to generate attachment ID: {}", e))
+        })?;
+        let storage_key = attachment_key(project_id, task_id, attachment_id);
+        state.storage.put(&storage_key, &data).await?;
+        storage_keys.push(storage_key.clone());
+
+        new_attachments.push(NewTaskAttachment {
+            id: attachment_id,
+            task_id,
+            file_name,
+            content_type,
+            file_size: data.len() as i64,
+            storage_key,
+        });
+    }
+
+    if new_attachments.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "No file provided in field '{}'",
+            FILE_FIELD_NAME
+        )));
+    }
+    Ok(new_attachments)
+}
+
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id, attachment_id)): Path<(Id, Id, Id)>,
+) -> AppResult<Response> {
+    perm.require(Permission::TaskView)?;
+    let attachment =
+        get_task_attachment(&state.pool, project_id.0, task_id, attachment_id).await?;
+    let data = state.storage.get(&attachment.storage_key).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (header::CONTENT_DISPOSITION, content_disposition(&attachment.file_name)),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id, attachment_id)): Path<(Id, Id, Id)>,
+) -> AppResult<StatusCode> {
+    let attachment =
+        get_task_attachment(&state.pool, project_id.0, task_id, attachment_id).await?;
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[attachment.task_id.0],
+        "edit",
+    )
+    .await?;
+
+    let deleted = TaskAttachmentRepository::delete_attachment(&state.pool, attachment_id.0).await?;
+    if !deleted {
+        return Err(AppError::NotFound(format!(
+            "Attachment not found: {}",
+            attachment_id
+        )));
+    }
+    if let Err(e) = state.storage.delete(&attachment.storage_key).await {
+        tracing::warn!("Failed to delete attachment file {}: {:?}", attachment_id, e);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
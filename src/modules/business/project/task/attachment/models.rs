@@ -0,0 +1,39 @@
+use crate::common::id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 单次上传最多的文件数
+pub const MAX_FILES_PER_UPLOAD: usize = 10;
+
+/// multipart 中文件字段的名称
+pub const FILE_FIELD_NAME: &str = "file";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAttachment {
+    pub id: Id,
+    pub project_id: Id,
+    pub task_id: Id,
+    pub file_name: String,
+    pub content_type: String,
+    /// 文件大小（字节）
+    pub file_size: i64,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub creator_id: Id,
+    pub create_date_time: chrono::NaiveDateTime,
+    // JOIN 字段
+    pub username: Option<String>,
+    pub full_name: Option<String>,
+}
+
+/// 已写入存储、待保存记录的附件
+#[derive(Debug, Clone)]
+pub struct NewTaskAttachment {
+    pub id: i64,
+    pub task_id: i64,
+    pub file_name: String,
+    pub content_type: String,
+    pub file_size: i64,
+    pub storage_key: String,
+}
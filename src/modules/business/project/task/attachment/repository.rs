@@ -0,0 +1,91 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::attachment::models::{
+    NewTaskAttachment, TaskAttachment,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct TaskAttachmentRepository;
+
+/// project_task_attachments 表 SELECT 列（含上传人 JOIN 字段）
+const ATTACHMENT_COLUMNS: &str = r#"a.id, a.project_id, a.task_id, a.file_name, a.content_type,
+    a.file_size, a.storage_key, a.creator_id, a.create_date_time, u.username, u.full_name"#;
+
+impl TaskAttachmentRepository {
+    pub async fn get_attachments(pool: &PgPool, task_id: i64) -> AppResult<Vec<TaskAttachment>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_attachments a
+               LEFT JOIN users u ON u.id = a.creator_id
+               WHERE a.task_id = $1
+               ORDER BY a.create_date_time ASC, a.id ASC"#,
+            ATTACHMENT_COLUMNS,
+        );
+        let attachments = sqlx::query_as::<_, TaskAttachment>(&sql)
+            .bind(task_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(attachments)
+    }
+
+    pub async fn get_attachment_by_id(
+        pool: &PgPool,
+        attachment_id: i64,
+    ) -> AppResult<Option<TaskAttachment>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_attachments a
+               LEFT JOIN users u ON u.id = a.creator_id
+               WHERE a.id = $1"#,
+            ATTACHMENT_COLUMNS,
+        );
+        let attachment = sqlx::query_as::<_, TaskAttachment>(&sql)
+            .bind(attachment_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(attachment)
+    }
+
+    pub async fn create_attachment(
+        conn: &mut PgConnection,
+        attachment: NewTaskAttachment,
+        creator_id: i64,
+    ) -> AppResult<TaskAttachment> {
+        let sql = format!(
+            r#"WITH a AS (
+                   INSERT INTO project_task_attachments
+                       (id, project_id, task_id, file_name, content_type, file_size, storage_key,
+                        creator_id)
+                   SELECT $1, t.project_id, t.id, $3, $4, $5, $6, $7
+                   FROM project_tasks t
+                   WHERE t.id = $2
+                   RETURNING *
+               )
+               SELECT {}
+               FROM a
+               LEFT JOIN users u ON u.id = a.creator_id"#,
+            ATTACHMENT_COLUMNS,
+        );
+        sqlx::query_as::<_, TaskAttachment>(&sql)
+            .bind(attachment.id)
+            .bind(attachment.task_id)
+            .bind(&attachment.file_name)
+            .bind(&attachment.content_type)
+            .bind(attachment.file_size)
+            .bind(&attachment.storage_key)
+            .bind(creator_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task not found: {}", attachment.task_id)))
+    }
+
+    pub async fn delete_attachment(pool: &PgPool, attachment_id: i64) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM project_task_attachments WHERE id = $1")
+            .bind(attachment_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
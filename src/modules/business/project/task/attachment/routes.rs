@@ -0,0 +1,48 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::attachment::handlers;
+use crate::modules::business::project::task::attachment::models::MAX_FILES_PER_UPLOAD;
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, post},
+    Router,
+};
+
+/// multipart 边界、字段头等额外开销
+const MULTIPART_OVERHEAD: usize = 1024 * 1024;
+
+pub fn task_attachment_routes(state: AppState) -> Router {
+    let body_limit =
+        state.storage_config.max_file_size as usize * MAX_FILES_PER_UPLOAD + MULTIPART_OVERHEAD;
+
+    Router::new()
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/attachments",
+            get(handlers::get_attachments),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/attachments",
+            post(handlers::upload_attachments).layer(DefaultBodyLimit::max(body_limit)),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/attachments/{attachment_id}",
+            get(handlers::download_attachment),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/attachments/{attachment_id}",
+            delete(handlers::delete_attachment),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
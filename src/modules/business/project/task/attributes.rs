@@ -0,0 +1,288 @@
+use crate::common::error::{field_error, FieldError};
+use crate::modules::business::project::task::models::{AttributeType, TaskAttributeConfig};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::{Map, Number, Value};
+
+/// 标量转字符串；null、空字符串、数组、对象返回 None
+pub(crate) fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+}
+
+pub fn parse_datetime(s: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+}
+
+/// select 配置的可选值：options 为标量数组或 `{label, value}` 对象数组
+pub fn option_values(config: &TaskAttributeConfig) -> Vec<String> {
+    let Some(Value::Array(options)) = &config.options else {
+        return Vec::new();
+    };
+    options
+        .iter()
+        .filter_map(|o| match o {
+            Value::Object(obj) => obj.get("value").and_then(scalar_to_string),
+            other => scalar_to_string(other),
+        })
+        .collect()
+}
+
+/// 将单个值规范化为属性类型对应的 JSON 表示；前端以字符串提交的数字 / 布尔值会被转换
+///
+/// 返回 Err 时为错误码与描述。
+fn normalize_value(
+    config: &TaskAttributeConfig,
+    attribute_type: AttributeType,
+    value: &Value,
+) -> Result<Value, (&'static str, String)> {
+    let invalid = |expected: &str| {
+        Err((
+            "invalid_type",
+            format!("{} must be a {}", config.attribute_label, expected),
+        ))
+    };
+    match attribute_type {
+        AttributeType::Text | AttributeType::User => match scalar_to_string(value) {
+            Some(s) => Ok(Value::String(s)),
+            None => invalid("string"),
+        },
+        AttributeType::Number => {
+            let number = match value {
+                Value::Number(n) => Some(n.clone()),
+                Value::String(s) => {
+                    let s = s.trim();
+                    s.parse::<i64>().ok().map(Number::from).or_else(|| {
+                        s.parse::<f64>().ok().and_then(Number::from_f64)
+                    })
+                }
+                _ => None,
+            };
+            match number {
+                Some(n) => Ok(Value::Number(n)),
+                None => invalid("number"),
+            }
+        }
+        AttributeType::Boolean => match value {
+            Value::Bool(b) => Ok(Value::Bool(*b)),
+            Value::String(s) if s.trim() == "true" => Ok(Value::Bool(true)),
+            Value::String(s) if s.trim() == "false" => Ok(Value::Bool(false)),
+            _ => invalid("boolean"),
+        },
+        AttributeType::Date => match value.as_str().map(str::trim).and_then(parse_date) {
+            Some(d) => Ok(Value::String(d.format("%Y-%m-%d").to_string())),
+            None => invalid("date (YYYY-MM-DD)"),
+        },
+        AttributeType::DateTime => match value.as_str().map(str::trim).and_then(parse_datetime) {
+            Some(dt) => Ok(Value::String(dt.format("%Y-%m-%dT%H:%M:%S").to_string())),
+            None => invalid("datetime (YYYY-MM-DDTHH:mm:ss)"),
+        },
+        AttributeType::Select => {
+            let Some(s) = scalar_to_string(value) else {
+                return invalid("string");
+            };
+            let options = option_values(config);
+            if !options.contains(&s) {
+                return Err((
+                    "invalid_option",
+                    format!(
+                        "{} must be one of: {}",
+                        config.attribute_label,
+                        options.join(", ")
+                    ),
+                ));
+            }
+            Ok(Value::String(s))
+        }
+    }
+}
+
+fn is_empty(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => true,
+        Some(Value::String(s)) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// 按项目的属性配置校验并规范化 custom_attributes
+///
+/// - 值按 attribute_type 校验并转换为对应的 JSON 类型，select 值必须在 options 中
+/// - 缺失的属性使用 default_value 填充；is_required 且无默认值时报错
+/// - 未配置的属性报错；已归档属性的值原样保留，不做校验
+/// - null / 空字符串视为未填写，不写入结果
+///
+/// 错误追加到 `errors`，字段名形如 `{field_prefix}.{attribute_name}`。
+pub fn validate_custom_attributes(
+    configs: &[TaskAttributeConfig],
+    value: Option<&Value>,
+    field_prefix: &str,
+    errors: &mut Vec<FieldError>,
+) -> Value {
+    let empty = Map::new();
+    let input = match value {
+        None | Some(Value::Null) => &empty,
+        Some(Value::Object(map)) => map,
+        Some(_) => {
+            errors.push(field_error(
+                field_prefix.to_string(),
+                "invalid_type",
+                "customAttributes must be an object".to_string(),
+            ));
+            return Value::Object(Map::new());
+        }
+    };
+
+    let mut output = Map::new();
+
+    for (key, v) in input {
+        if is_empty(Some(v)) {
+            continue;
+        }
+        match configs.iter().find(|c| &c.attribute_name == key) {
+            None => errors.push(field_error(
+                format!("{}.{}", field_prefix, key),
+                "unknown_attribute",
+                format!("Unknown custom attribute: {}", key),
+            )),
+            Some(config) if config.is_archived => {
+                output.insert(key.clone(), v.clone());
+            }
+            Some(_) => {}
+        }
+    }
+
+    for config in configs.iter().filter(|c| !c.is_archived) {
+        let field = format!("{}.{}", field_prefix, config.attribute_name);
+        let attribute_type = AttributeType::from_str(&config.attribute_type);
+        let provided = input.get(&config.attribute_name);
+
+        if !is_empty(provided) {
+            let v = provided.cloned().unwrap_or(Value::Null);
+            match attribute_type {
+                Some(t) => match normalize_value(config, t, &v) {
+                    Ok(normalized) => {
+                        output.insert(config.attribute_name.clone(), normalized);
+                    }
+                    Err((code, message)) => errors.push(field_error(field, code, message)),
+                },
+                // 未知类型不做校验
+                None => {
+                    output.insert(config.attribute_name.clone(), v);
+                }
+            }
+            continue;
+        }
+
+        let default = config
+            .default_value
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .and_then(|d| {
+                let d = Value::String(d.to_string());
+                match attribute_type {
+                    Some(t) => normalize_value(config, t, &d).ok(),
+                    None => Some(d),
+                }
+            });
+        match default {
+            Some(d) => {
+                output.insert(config.attribute_name.clone(), d);
+            }
+            None if config.is_required => errors.push(field_error(
+                field,
+                "required",
+                format!("{} is required", config.attribute_label),
+            )),
+            None => {}
+        }
+    }
+
+    Value::Object(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(name: &str, attribute_type: &str, is_required: bool) -> TaskAttributeConfig {
+        TaskAttributeConfig {
+            is_required,
+            ..TaskAttributeConfig::test(name, attribute_type)
+        }
+    }
+
+    #[test]
+    fn test_normalizes_typed_values() {
+        let mut status = config("status", "select", false);
+        status.options = Some(json!([{"label": "Open", "value": "open"}, "closed"]));
+        let configs = vec![
+            config("points", "number", false),
+            config("done", "boolean", false),
+            config("due", "date", false),
+            status,
+        ];
+        let mut errors = Vec::new();
+        let value = json!({"points": "3", "done": "true", "due": "2026-01-05", "status": "closed"});
+        let result = validate_custom_attributes(&configs, Some(&value), "customAttributes", &mut errors);
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            json!({"points": 3, "done": true, "due": "2026-01-05", "status": "closed"})
+        );
+    }
+
+    #[test]
+    fn test_reports_field_errors() {
+        let mut status = config("status", "select", false);
+        status.options = Some(json!(["open"]));
+        let configs = vec![
+            config("owner", "text", true),
+            config("points", "number", false),
+            status,
+        ];
+        let mut errors = Vec::new();
+        let value = json!({"points": "abc", "status": "closed", "extra": 1});
+        validate_custom_attributes(&configs, Some(&value), "tasks[0].customAttributes", &mut errors);
+
+        let mut codes: Vec<(String, String)> =
+            errors.into_iter().map(|e| (e.field, e.code)).collect();
+        codes.sort();
+        assert_eq!(
+            codes,
+            vec![
+                ("tasks[0].customAttributes.extra".to_string(), "unknown_attribute".to_string()),
+                ("tasks[0].customAttributes.owner".to_string(), "required".to_string()),
+                ("tasks[0].customAttributes.points".to_string(), "invalid_type".to_string()),
+                ("tasks[0].customAttributes.status".to_string(), "invalid_option".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_value_and_archived() {
+        let mut priority = config("priority", "number", true);
+        priority.default_value = Some("2".to_string());
+        let mut legacy = config("legacy", "number", true);
+        legacy.is_archived = true;
+        let configs = vec![priority, legacy];
+        let mut errors = Vec::new();
+        let value = json!({"legacy": "kept as-is"});
+        let result = validate_custom_attributes(&configs, Some(&value), "customAttributes", &mut errors);
+
+        assert!(errors.is_empty());
+        assert_eq!(result, json!({"priority": 2, "legacy": "kept as-is"}));
+    }
+}
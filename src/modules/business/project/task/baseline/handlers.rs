@@ -0,0 +1,128 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::baseline::models::{
+    Baseline, BaselineDetail, BaselineVariance, CreateBaselineParams,
+};
+use crate::modules::business::project::task::baseline::repository::BaselineRepository;
+use crate::modules::business::project::task::baseline::variance::compute_variance;
+use crate::modules::business::project::task::models::TaskQueryParams;
+use crate::modules::business::project::task::query::TaskListQuery;
+use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use sqlx::PgPool;
+
+/// 查询基线并校验其属于当前项目
+pub async fn get_project_baseline(
+    pool: &PgPool,
+    project_id: i64,
+    baseline_id: Id,
+) -> AppResult<Baseline> {
+    BaselineRepository::get_baseline_by_id(pool, baseline_id.0)
+        .await?
+        .filter(|b| b.project_id.0 == project_id)
+        .ok_or_else(|| AppError::NotFound(format!("Baseline not found: {}", baseline_id)))
+}
+
+pub async fn get_baselines(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+) -> AppResult<Json<ApiResponse<Vec<Baseline>>>> {
+    perm.require(Permission::TaskView)?;
+    let baselines = BaselineRepository::get_baselines(&state.pool, project_id.0).await?;
+    Ok(Json(ApiResponse::success(baselines)))
+}
+
+pub async fn get_baseline_by_id(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, baseline_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<BaselineDetail>>> {
+    perm.require(Permission::TaskView)?;
+    let baseline = get_project_baseline(&state.pool, project_id.0, baseline_id).await?;
+    let tasks = BaselineRepository::get_baseline_tasks(&state.pool, baseline_id.0).await?;
+    Ok(Json(ApiResponse::success(BaselineDetail { baseline, tasks })))
+}
+
+pub async fn create_baseline(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<CreateBaselineParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<Baseline>>)> {
+    perm.require(Permission::ProjectEdit)?;
+    let baseline_name = params.baseline_name.trim();
+    if baseline_name.is_empty() {
+        return Err(AppError::BadRequest(
+            "Baseline name cannot be empty".to_string(),
+        ));
+    }
+    let baseline_name = baseline_name.to_string();
+
+    let baseline_id = state.generate_id().map_err(|e| {
+        AppError::InternalError(format!("Failed to generate baseline ID: {}", e))
+    })?;
+    let mut tx = state.pool.begin().await?;
+    let baseline =
+        BaselineRepository::create_baseline(&mut tx, baseline_id, project_id.0, params, claims.sub)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict(format!("Baseline already exists: {}", baseline_name))
+            })?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(baseline))))
+}
+
+pub async fn delete_baseline(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, baseline_id)): Path<(Id, Id)>,
+) -> AppResult<StatusCode> {
+    perm.require(Permission::ProjectEdit)?;
+    get_project_baseline(&state.pool, project_id.0, baseline_id).await?;
+    let deleted = BaselineRepository::delete_baseline(&state.pool, baseline_id.0).await?;
+    if !deleted {
+        return Err(AppError::NotFound(format!(
+            "Baseline not found: {}",
+            baseline_id
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 当前计划相对基线的偏差（按项目工作日历计算工作日）
+pub async fn get_baseline_variance(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, baseline_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<BaselineVariance>>> {
+    perm.require(Permission::TaskView)?;
+    let baseline = get_project_baseline(&state.pool, project_id.0, baseline_id).await?;
+    let baseline_tasks = BaselineRepository::get_baseline_tasks(&state.pool, baseline_id.0).await?;
+    let tasks = TaskRepository::get_all_tasks(
+        &state.pool,
+        project_id.0,
+        TaskQueryParams::default(),
+        &TaskListQuery::default(),
+    )
+    .await?;
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+    let (summary, tasks) = compute_variance(&baseline_tasks, &tasks, &calendar)?;
+
+    Ok(Json(ApiResponse::success(BaselineVariance {
+        baseline,
+        summary,
+        tasks,
+    })))
+}
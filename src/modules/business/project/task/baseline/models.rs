@@ -0,0 +1,95 @@
+use crate::common::id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Baseline {
+    pub id: Id,
+    pub project_id: Id,
+    pub baseline_name: String,
+    pub description: Option<String>,
+    /// 快照中的任务数量
+    pub task_count: i32,
+    pub creator_id: Id,
+    pub create_date_time: chrono::NaiveDateTime,
+}
+
+/// 基线中的任务快照
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineTask {
+    pub task_id: Id,
+    pub task_name: String,
+    pub parent_id: Option<Id>,
+    pub task_type: i32,
+    pub start_date_time: chrono::NaiveDateTime,
+    pub end_date_time: chrono::NaiveDateTime,
+    pub custom_attributes: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineDetail {
+    #[serde(flatten)]
+    pub baseline: Baseline,
+    pub tasks: Vec<BaselineTask>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBaselineParams {
+    pub baseline_name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VarianceStatus {
+    /// 基线与当前计划中都存在
+    Matched,
+    /// 基线之后新增的任务
+    Added,
+    /// 基线之后删除的任务
+    Removed,
+}
+
+/// 单个任务相对基线的偏差，正数表示延后（按工作日计）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskVariance {
+    pub task_id: Id,
+    pub task_name: String,
+    pub status: VarianceStatus,
+    pub baseline_start_date_time: Option<chrono::NaiveDateTime>,
+    pub baseline_end_date_time: Option<chrono::NaiveDateTime>,
+    pub current_start_date_time: Option<chrono::NaiveDateTime>,
+    pub current_end_date_time: Option<chrono::NaiveDateTime>,
+    pub start_variance_days: Option<i64>,
+    pub finish_variance_days: Option<i64>,
+    /// 取值发生变化的自定义属性
+    pub changed_attributes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VarianceSummary {
+    pub matched_count: i64,
+    pub added_count: i64,
+    pub removed_count: i64,
+    /// 完成时间晚于基线的任务数
+    pub delayed_count: i64,
+    /// 完成时间早于基线的任务数
+    pub ahead_count: i64,
+    pub max_finish_variance_days: i64,
+    /// 项目整体完成时间相对基线的偏差
+    pub project_finish_variance_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineVariance {
+    pub baseline: Baseline,
+    pub summary: VarianceSummary,
+    pub tasks: Vec<TaskVariance>,
+}
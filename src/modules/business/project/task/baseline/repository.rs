@@ -0,0 +1,123 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::baseline::models::{
+    Baseline, BaselineTask, CreateBaselineParams,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct BaselineRepository;
+
+/// project_baselines 表 SELECT 列
+const BASELINE_COLUMNS: &str =
+    "id, project_id, baseline_name, description, task_count, creator_id, create_date_time";
+
+impl BaselineRepository {
+    pub async fn get_baselines(pool: &PgPool, project_id: i64) -> AppResult<Vec<Baseline>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_baselines
+               WHERE project_id = $1
+               ORDER BY create_date_time DESC"#,
+            BASELINE_COLUMNS,
+        );
+        let baselines = sqlx::query_as::<_, Baseline>(&sql)
+            .bind(project_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(baselines)
+    }
+
+    pub async fn get_baseline_by_id(
+        pool: &PgPool,
+        baseline_id: i64,
+    ) -> AppResult<Option<Baseline>> {
+        let sql = format!(
+            "SELECT {} FROM project_baselines WHERE id = $1",
+            BASELINE_COLUMNS,
+        );
+        let baseline = sqlx::query_as::<_, Baseline>(&sql)
+            .bind(baseline_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(baseline)
+    }
+
+    /// 创建基线并快照项目当前全部任务；项目中已有同名基线时返回 None（由唯一约束判定）
+    pub async fn create_baseline(
+        conn: &mut PgConnection,
+        baseline_id: i64,
+        project_id: i64,
+        params: CreateBaselineParams,
+        creator_id: i64,
+    ) -> AppResult<Option<Baseline>> {
+        let inserted = sqlx::query(
+            r#"INSERT INTO project_baselines (id, project_id, baseline_name, description, creator_id)
+               VALUES ($1, $2, $3, $4, $5)"#,
+        )
+        .bind(baseline_id)
+        .bind(project_id)
+        .bind(params.baseline_name.trim())
+        .bind(params.description)
+        .bind(creator_id)
+        .execute(&mut *conn)
+        .await;
+        match inserted {
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => return Ok(None),
+            result => result?,
+        };
+
+        let result = sqlx::query(
+            r#"INSERT INTO project_baseline_tasks
+                   (baseline_id, task_id, task_name, parent_id, task_type,
+                    start_date_time, end_date_time, custom_attributes)
+               SELECT $1, id, task_name, parent_id, task_type,
+                      start_date_time, end_date_time, custom_attributes
+               FROM project_tasks
+               WHERE project_id = $2"#,
+        )
+        .bind(baseline_id)
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await?;
+
+        let sql = format!(
+            "UPDATE project_baselines SET task_count = $2 WHERE id = $1 RETURNING {}",
+            BASELINE_COLUMNS,
+        );
+        let baseline = sqlx::query_as::<_, Baseline>(&sql)
+            .bind(baseline_id)
+            .bind(result.rows_affected() as i32)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(Some(baseline))
+    }
+
+    pub async fn get_baseline_tasks(
+        pool: &PgPool,
+        baseline_id: i64,
+    ) -> AppResult<Vec<BaselineTask>> {
+        let tasks = sqlx::query_as::<_, BaselineTask>(
+            r#"SELECT task_id, task_name, parent_id, task_type,
+                   start_date_time, end_date_time, custom_attributes
+               FROM project_baseline_tasks
+               WHERE baseline_id = $1
+               ORDER BY start_date_time ASC, task_id ASC"#,
+        )
+        .bind(baseline_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tasks)
+    }
+
+    pub async fn delete_baseline(pool: &PgPool, baseline_id: i64) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM project_baselines WHERE id = $1")
+            .bind(baseline_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,38 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::baseline::handlers;
+use axum::{
+    middleware,
+    routing::{delete, get, post},
+    Router,
+};
+
+pub fn baseline_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/projects/{project_id}/baselines", get(handlers::get_baselines))
+        .route("/projects/{project_id}/baselines", post(handlers::create_baseline))
+        .route(
+            "/projects/{project_id}/baselines/{baseline_id}",
+            get(handlers::get_baseline_by_id),
+        )
+        .route(
+            "/projects/{project_id}/baselines/{baseline_id}",
+            delete(handlers::delete_baseline),
+        )
+        .route(
+            "/projects/{project_id}/baselines/{baseline_id}/variance",
+            get(handlers::get_baseline_variance),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
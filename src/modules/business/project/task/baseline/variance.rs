@@ -0,0 +1,199 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::baseline::models::{
+    BaselineTask, TaskVariance, VarianceStatus, VarianceSummary,
+};
+use crate::modules::business::project::task::models::Task;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use chrono::NaiveDate;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// 两个日期之间相差的工作日数：current 晚于 baseline 为正，早于为负
+pub fn working_day_variance(
+    calendar: &WorkingCalendar,
+    baseline: NaiveDate,
+    current: NaiveDate,
+) -> AppResult<i64> {
+    // 区间不含较早的一天；earlier 为 NaiveDate::MAX 时两个日期相同，偏差为 0
+    let (earlier, later, sign) = if current > baseline {
+        (baseline, current, 1)
+    } else {
+        (current, baseline, -1)
+    };
+    match earlier.succ_opt() {
+        Some(first) => Ok(sign * calendar.count_working_days(first, later)?),
+        None => Ok(0),
+    }
+}
+
+/// 对比两组自定义属性，返回取值不同的属性名
+fn changed_attributes(baseline: &Value, current: &Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let baseline = baseline.as_object().unwrap_or(&empty);
+    let current = current.as_object().unwrap_or(&empty);
+    baseline
+        .keys()
+        .chain(current.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| baseline.get(*key) != current.get(*key))
+        .cloned()
+        .collect()
+}
+
+/// 计算当前计划相对基线的偏差；当前任务按传入顺序在前，已删除的任务在后
+pub fn compute_variance(
+    baseline_tasks: &[BaselineTask],
+    tasks: &[Task],
+    calendar: &WorkingCalendar,
+) -> AppResult<(VarianceSummary, Vec<TaskVariance>)> {
+    let snapshot: HashMap<i64, &BaselineTask> =
+        baseline_tasks.iter().map(|t| (t.task_id.0, t)).collect();
+    let mut summary = VarianceSummary::default();
+    let mut items = Vec::with_capacity(tasks.len());
+
+    for task in tasks {
+        let Some(base) = snapshot.get(&task.id.0) else {
+            summary.added_count += 1;
+            items.push(TaskVariance {
+                task_id: task.id,
+                task_name: task.task_name.clone(),
+                status: VarianceStatus::Added,
+                baseline_start_date_time: None,
+                baseline_end_date_time: None,
+                current_start_date_time: Some(task.start_date_time),
+                current_end_date_time: Some(task.end_date_time),
+                start_variance_days: None,
+                finish_variance_days: None,
+                changed_attributes: Vec::new(),
+            });
+            continue;
+        };
+
+        let start = working_day_variance(
+            calendar,
+            base.start_date_time.date(),
+            task.start_date_time.date(),
+        )?;
+        let finish = working_day_variance(
+            calendar,
+            base.end_date_time.date(),
+            task.end_date_time.date(),
+        )?;
+        summary.matched_count += 1;
+        summary.delayed_count += (finish > 0) as i64;
+        summary.ahead_count += (finish < 0) as i64;
+        summary.max_finish_variance_days = summary.max_finish_variance_days.max(finish);
+        items.push(TaskVariance {
+            task_id: task.id,
+            task_name: task.task_name.clone(),
+            status: VarianceStatus::Matched,
+            baseline_start_date_time: Some(base.start_date_time),
+            baseline_end_date_time: Some(base.end_date_time),
+            current_start_date_time: Some(task.start_date_time),
+            current_end_date_time: Some(task.end_date_time),
+            start_variance_days: Some(start),
+            finish_variance_days: Some(finish),
+            changed_attributes: changed_attributes(&base.custom_attributes, &task.custom_attributes),
+        });
+    }
+
+    let current_ids: BTreeSet<i64> = tasks.iter().map(|t| t.id.0).collect();
+    for base in baseline_tasks {
+        if current_ids.contains(&base.task_id.0) {
+            continue;
+        }
+        summary.removed_count += 1;
+        items.push(TaskVariance {
+            task_id: base.task_id,
+            task_name: base.task_name.clone(),
+            status: VarianceStatus::Removed,
+            baseline_start_date_time: Some(base.start_date_time),
+            baseline_end_date_time: Some(base.end_date_time),
+            current_start_date_time: None,
+            current_end_date_time: None,
+            start_variance_days: None,
+            finish_variance_days: None,
+            changed_attributes: Vec::new(),
+        });
+    }
+
+    let baseline_finish = baseline_tasks.iter().map(|t| t.end_date_time.date()).max();
+    let current_finish = tasks.iter().map(|t| t.end_date_time.date()).max();
+    if let (Some(baseline), Some(current)) = (baseline_finish, current_finish) {
+        summary.project_finish_variance_days =
+            Some(working_day_variance(calendar, baseline, current)?);
+    }
+
+    Ok((summary, items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::holiday::models::HolidayType;
+    use serde_json::json;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    fn task(id: i64, start: u32, end: u32, attributes: Value) -> Task {
+        let time = |d| date(d).and_hms_opt(9, 0, 0).unwrap();
+        Task {
+            custom_attributes: attributes,
+            start_date_time: time(start),
+            end_date_time: time(end),
+            ..Task::test(id)
+        }
+    }
+
+    fn snapshot(task: &Task) -> BaselineTask {
+        BaselineTask {
+            task_id: task.id,
+            task_name: task.task_name.clone(),
+            parent_id: task.parent_id,
+            task_type: task.task_type,
+            start_date_time: task.start_date_time,
+            end_date_time: task.end_date_time,
+            custom_attributes: task.custom_attributes.clone(),
+        }
+    }
+
+    #[test]
+    fn test_variance_in_working_days() {
+        // 2026-01-09 为周五，01-12 为周一；01-13 为假期
+        let calendar = WorkingCalendar::new([(date(13), HolidayType::CompanyHoliday)]);
+        assert_eq!(working_day_variance(&calendar, date(9), date(14)).unwrap(), 2);
+        assert_eq!(working_day_variance(&calendar, date(14), date(9)).unwrap(), -2);
+        assert_eq!(working_day_variance(&calendar, date(10), date(11)).unwrap(), 0);
+
+        let baseline = vec![
+            snapshot(&task(1, 5, 9, json!({"owner": "a"}))),
+            snapshot(&task(2, 5, 6, json!({}))),
+        ];
+        let current = vec![
+            task(1, 6, 14, json!({"owner": "b"})),
+            task(3, 7, 8, json!({})),
+        ];
+        let (summary, items) = compute_variance(&baseline, &current, &calendar).unwrap();
+
+        assert_eq!(items[0].start_variance_days, Some(1));
+        assert_eq!(items[0].finish_variance_days, Some(2));
+        assert_eq!(items[0].changed_attributes, vec!["owner".to_string()]);
+        assert_eq!(items[1].status, VarianceStatus::Added);
+        assert_eq!(items[2].status, VarianceStatus::Removed);
+        assert_eq!(
+            summary,
+            VarianceSummary {
+                matched_count: 1,
+                added_count: 1,
+                removed_count: 1,
+                delayed_count: 1,
+                ahead_count: 0,
+                max_finish_variance_days: 2,
+                project_finish_variance_days: Some(2),
+            }
+        );
+    }
+}
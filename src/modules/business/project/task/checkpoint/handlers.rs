@@ -0,0 +1,152 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::checkpoint::models::{
+    CheckpointApproval, CheckpointDecision, CheckpointDecisionParams, CheckpointSignOff,
+    SignOffStatus, MAX_APPROVAL_COMMENT_LENGTH,
+};
+use crate::modules::business::project::task::checkpoint::repository::CheckpointRepository;
+use crate::modules::business::project::task::models::{Task, TaskType};
+use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::business::project::workflow::repository::WorkflowRepository;
+use crate::modules::business::project::workflow::models::StatusCategory;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+/// 查询任务并校验其为当前项目的检查点任务
+async fn get_checkpoint_task(state: &AppState, project_id: i64, task_id: Id) -> AppResult<Task> {
+    let task = TaskRepository::get_project_task(&state.pool, project_id, task_id.0).await?;
+    if TaskType::from_i32(task.task_type) != Some(TaskType::Checkpoint) {
+        return Err(AppError::BadRequest(format!(
+            "Task {} is not a checkpoint",
+            task_id
+        )));
+    }
+    Ok(task)
+}
+
+pub async fn get_checkpoint(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<CheckpointSignOff>>> {
+    perm.require(Permission::TaskView)?;
+    let task = get_checkpoint_task(&state, project_id.0, task_id).await?;
+    let approvals = CheckpointRepository::get_approvals(&state.pool, task.id.0).await?;
+    Ok(Json(ApiResponse::success(CheckpointSignOff {
+        task_id: task.id,
+        status: SignOffStatus::from_latest(approvals.first().map(|a| a.decision)),
+        approvals,
+    })))
+}
+
+/// 记录审批结论：已完成的检查点不可再审批，已签核的检查点不可重复签核
+async fn decide(
+    state: &AppState,
+    claims: &Claims,
+    perm: &ProjectPermission,
+    project_id: i64,
+    task_id: Id,
+    decision: CheckpointDecision,
+    comment: Option<String>,
+) -> AppResult<CheckpointApproval> {
+    perm.require(Permission::CheckpointApprove)?;
+    let task = get_checkpoint_task(state, project_id, task_id).await?;
+
+    let comment = comment
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+    if decision == CheckpointDecision::Rejected && comment.is_none() {
+        return Err(AppError::BadRequest(
+            "A comment is required when rejecting a checkpoint".to_string(),
+        ));
+    }
+    if comment
+        .as_ref()
+        .is_some_and(|c| c.chars().count() > MAX_APPROVAL_COMMENT_LENGTH)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Comment must not exceed {} characters",
+            MAX_APPROVAL_COMMENT_LENGTH
+        )));
+    }
+
+    let workflow = WorkflowRepository::get_workflow(&state.pool, project_id).await?;
+    let completed = workflow
+        .current_status_id(task.status_id.map(|id| id.0))
+        .and_then(|id| workflow.status_category(id))
+        == Some(StatusCategory::Done);
+    if completed {
+        return Err(AppError::Conflict(format!(
+            "Checkpoint {} is already completed",
+            task_id
+        )));
+    }
+    let mut conn = state.pool.acquire().await?;
+    let latest = CheckpointRepository::get_latest_decision(&mut conn, task.id.0).await?;
+    if decision == CheckpointDecision::Approved && latest == Some(CheckpointDecision::Approved) {
+        return Err(AppError::Conflict(format!(
+            "Checkpoint {} is already signed off",
+            task_id
+        )));
+    }
+
+    let approval_id = state.generate_id().map_err(|e| {
+        AppError::InternalError(format!("Failed to generate approval ID: {}", e))
+    })?;
+    CheckpointRepository::create_approval(
+        &state.pool,
+        approval_id,
+        task.id.0,
+        decision,
+        comment.as_deref(),
+        claims.sub,
+    )
+    .await
+}
+
+pub async fn approve_checkpoint(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<CheckpointDecisionParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<CheckpointApproval>>)> {
+    let approval = decide(
+        &state,
+        &claims,
+        &perm,
+        project_id.0,
+        task_id,
+        CheckpointDecision::Approved,
+        params.comment,
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(approval))))
+}
+
+pub async fn reject_checkpoint(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<CheckpointDecisionParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<CheckpointApproval>>)> {
+    let approval = decide(
+        &state,
+        &claims,
+        &perm,
+        project_id.0,
+        task_id,
+        CheckpointDecision::Rejected,
+        params.comment,
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(approval))))
+}
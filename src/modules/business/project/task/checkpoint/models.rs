@@ -0,0 +1,99 @@
+use crate::common::id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+/// 审批意见最大长度（字符）
+pub const MAX_APPROVAL_COMMENT_LENGTH: usize = 2000;
+
+/// 审批结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointDecision {
+    Approved = 1,
+    Rejected = 2,
+}
+
+impl CheckpointDecision {
+    pub fn from_i16(v: i16) -> Option<Self> {
+        match v {
+            1 => Some(CheckpointDecision::Approved),
+            2 => Some(CheckpointDecision::Rejected),
+            _ => None,
+        }
+    }
+
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+}
+
+/// 检查点签核状态，由最新一条审批记录决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignOffStatus {
+    /// 尚无审批记录
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl SignOffStatus {
+    pub fn from_latest(latest: Option<CheckpointDecision>) -> Self {
+        match latest {
+            None => SignOffStatus::Pending,
+            Some(CheckpointDecision::Approved) => SignOffStatus::Approved,
+            Some(CheckpointDecision::Rejected) => SignOffStatus::Rejected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointApproval {
+    pub id: Id,
+    pub project_id: Id,
+    pub task_id: Id,
+    pub decision: CheckpointDecision,
+    pub comment: Option<String>,
+    pub approver_id: Id,
+    pub create_date_time: chrono::NaiveDateTime,
+    // JOIN 字段
+    pub username: Option<String>,
+    pub full_name: Option<String>,
+}
+
+impl FromRow<'_, PgRow> for CheckpointApproval {
+    fn from_row(row: &PgRow) -> sqlx::Result<Self> {
+        let decision: i16 = row.try_get("decision")?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            project_id: row.try_get("project_id")?,
+            task_id: row.try_get("task_id")?,
+            decision: CheckpointDecision::from_i16(decision).ok_or_else(|| {
+                sqlx::Error::Decode(format!("Invalid checkpoint decision: {}", decision).into())
+            })?,
+            comment: row.try_get("comment")?,
+            approver_id: row.try_get("approver_id")?,
+            create_date_time: row.try_get("create_date_time")?,
+            username: row.try_get("username")?,
+            full_name: row.try_get("full_name")?,
+        })
+    }
+}
+
+/// 检查点签核状态及审批历史（按时间倒序）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointSignOff {
+    pub task_id: Id,
+    pub status: SignOffStatus,
+    pub approvals: Vec<CheckpointApproval>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointDecisionParams {
+    /// 审批意见，驳回时必填
+    pub comment: Option<String>,
+}
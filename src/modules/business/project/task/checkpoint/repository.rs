@@ -0,0 +1,93 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::checkpoint::models::{
+    CheckpointApproval, CheckpointDecision,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct CheckpointRepository;
+
+/// project_task_checkpoint_approvals 表 SELECT 列（含审批人 JOIN 字段）
+const APPROVAL_COLUMNS: &str = r#"a.id, a.project_id, a.task_id, a.decision, a.comment,
+    a.approver_id, a.create_date_time, u.username, u.full_name"#;
+
+impl CheckpointRepository {
+    /// 查询任务的审批记录（按时间倒序）
+    pub async fn get_approvals(pool: &PgPool, task_id: i64) -> AppResult<Vec<CheckpointApproval>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_checkpoint_approvals a
+               LEFT JOIN users u ON u.id = a.approver_id
+               WHERE a.task_id = $1
+               ORDER BY a.create_date_time DESC, a.id DESC"#,
+            APPROVAL_COLUMNS,
+        );
+        let approvals = sqlx::query_as::<_, CheckpointApproval>(&sql)
+            .bind(task_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(approvals)
+    }
+
+    async fn get_approval_by_id(pool: &PgPool, approval_id: i64) -> AppResult<Option<CheckpointApproval>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_checkpoint_approvals a
+               LEFT JOIN users u ON u.id = a.approver_id
+               WHERE a.id = $1"#,
+            APPROVAL_COLUMNS,
+        );
+        let approval = sqlx::query_as::<_, CheckpointApproval>(&sql)
+            .bind(approval_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(approval)
+    }
+
+    /// 任务最新一条审批结论
+    pub async fn get_latest_decision(
+        conn: &mut PgConnection,
+        task_id: i64,
+    ) -> AppResult<Option<CheckpointDecision>> {
+        let decision: Option<(i16,)> = sqlx::query_as(
+            r#"SELECT decision FROM project_task_checkpoint_approvals
+               WHERE task_id = $1
+               ORDER BY create_date_time DESC, id DESC
+               LIMIT 1"#,
+        )
+        .bind(task_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(decision.and_then(|(d,)| CheckpointDecision::from_i16(d)))
+    }
+
+    pub async fn create_approval(
+        pool: &PgPool,
+        approval_id: i64,
+        task_id: i64,
+        decision: CheckpointDecision,
+        comment: Option<&str>,
+        approver_id: i64,
+    ) -> AppResult<CheckpointApproval> {
+        sqlx::query(
+            r#"INSERT INTO project_task_checkpoint_approvals
+                   (id, project_id, task_id, decision, comment, approver_id)
+               SELECT $1, t.project_id, t.id, $3, $4, $5
+               FROM project_tasks t
+               WHERE t.id = $2"#,
+        )
+        .bind(approval_id)
+        .bind(task_id)
+        .bind(decision.as_i16())
+        .bind(comment)
+        .bind(approver_id)
+        .execute(pool)
+        .await?;
+
+        Self::get_approval_by_id(pool, approval_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task not found: {}", task_id)))
+    }
+}
@@ -0,0 +1,201 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::{ApiResponse, PaginatedResponse};
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::permission::repository::ProjectPermissionResolver;
+use crate::modules::business::project::task::comment::mention::parse_mentions;
+use crate::modules::business::project::task::comment::models::{
+    CreateTaskCommentParams, TaskComment, TaskCommentQueryParams, TaskCommentThread,
+    UpdateTaskCommentParams, MAX_COMMENT_LENGTH,
+};
+use crate::modules::business::project::task::comment::repository::TaskCommentRepository;
+use crate::modules::business::project::task::repository::TaskRepository;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// 查询评论并校验其属于当前项目的指定任务
+async fn get_task_comment(
+    pool: &PgPool,
+    project_id: i64,
+    task_id: Id,
+    comment_id: Id,
+) -> AppResult<TaskComment> {
+    TaskCommentRepository::get_comment_by_id(pool, comment_id.0)
+        .await?
+        .filter(|c| c.project_id.0 == project_id && c.task_id == task_id)
+        .ok_or_else(|| AppError::NotFound(format!("Comment not found: {}", comment_id)))
+}
+
+fn validate_content(content: &str) -> AppResult<&str> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(AppError::BadRequest(
+            "Comment content cannot be empty".to_string(),
+        ));
+    }
+    if content.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Comment content must not exceed {} characters",
+            MAX_COMMENT_LENGTH
+        )));
+    }
+    Ok(content)
+}
+
+/// 将 @username 解析为用户 ID（按提及顺序），忽略不存在、已停用或不是项目成员的用户
+async fn resolve_mentions(pool: &PgPool, project_id: i64, content: &str) -> AppResult<Vec<i64>> {
+    let usernames = parse_mentions(content);
+    if usernames.is_empty() {
+        return Ok(vec![]);
+    }
+    let members: HashMap<String, i64> =
+        ProjectPermissionResolver::get_active_members_by_usernames(pool, project_id, &usernames)
+            .await?
+            .into_iter()
+            .collect();
+    let mut user_ids = Vec::new();
+    for username in &usernames {
+        if let Some(&user_id) = members.get(username) {
+            if !user_ids.contains(&user_id) {
+                user_ids.push(user_id);
+            }
+        }
+    }
+    Ok(user_ids)
+}
+
+pub async fn get_comments(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Query(params): Query<TaskCommentQueryParams>,
+) -> AppResult<Json<PaginatedResponse<TaskCommentThread>>> {
+    perm.require(Permission::TaskView)?;
+    TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+
+    let page = params.page.unwrap_or(1);
+    let per_page = params.per_page.unwrap_or(20);
+    let (roots, total) =
+        TaskCommentRepository::get_root_comments(&state.pool, task_id.0, page, per_page).await?;
+    let root_ids: Vec<i64> = roots.iter().map(|c| c.id.0).collect();
+    let mut replies: HashMap<Id, Vec<TaskComment>> = HashMap::new();
+    for reply in TaskCommentRepository::get_replies(&state.pool, &root_ids).await? {
+        if let Some(root_id) = reply.root_id {
+            replies.entry(root_id).or_default().push(reply);
+        }
+    }
+
+    let threads = roots
+        .into_iter()
+        .map(|comment| TaskCommentThread {
+            replies: replies.remove(&comment.id).unwrap_or_default(),
+            comment,
+        })
+        .collect();
+
+    Ok(Json(PaginatedResponse::new(
+        threads,
+        total,
+        page,
+        per_page,
+        &format!("/api/v1/projects/{}/tasks/{}/comments", project_id, task_id),
+    )))
+}
+
+pub async fn create_comment(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<CreateTaskCommentParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<TaskComment>>)> {
+    perm.require(Permission::TaskComment)?;
+    let content = validate_content(&params.content)?;
+    TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    let parent = match params.parent_id {
+        Some(parent_id) => {
+            Some(get_task_comment(&state.pool, project_id.0, task_id, parent_id).await?)
+        }
+        None => None,
+    };
+
+    let mention_user_ids = resolve_mentions(&state.pool, project_id.0, content).await?;
+    let comment_id = state.generate_id().map_err(|e| {
+        AppError::InternalError(format!("Failed to generate comment ID: {}", e))
+    })?;
+    let comment = TaskCommentRepository::create_comment(
+        &state.pool,
+        comment_id,
+        task_id.0,
+        parent.as_ref(),
+        content,
+        &mention_user_ids,
+        claims.sub,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(comment))))
+}
+
+/// 仅作者可编辑评论
+pub async fn update_comment(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id, comment_id)): Path<(Id, Id, Id)>,
+    Json(params): Json<UpdateTaskCommentParams>,
+) -> AppResult<Json<ApiResponse<TaskComment>>> {
+    perm.require(Permission::TaskComment)?;
+    let content = validate_content(&params.content)?;
+    let comment = get_task_comment(&state.pool, project_id.0, task_id, comment_id).await?;
+    if comment.creator_id.0 != claims.sub {
+        return Err(AppError::Forbidden(
+            "Only the author can edit this comment".to_string(),
+        ));
+    }
+
+    let mention_user_ids = resolve_mentions(&state.pool, project_id.0, content).await?;
+    let comment = TaskCommentRepository::update_comment(
+        &state.pool,
+        comment_id.0,
+        content,
+        &mention_user_ids,
+        claims.sub,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Comment not found: {}", comment_id)))?;
+
+    Ok(Json(ApiResponse::success(comment)))
+}
+
+/// 作者可删除自己的评论，拥有 TaskCommentManage 权限可删除任意评论
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id, comment_id)): Path<(Id, Id, Id)>,
+) -> AppResult<StatusCode> {
+    let comment = get_task_comment(&state.pool, project_id.0, task_id, comment_id).await?;
+    let is_author = comment.creator_id.0 == claims.sub;
+    if !perm.can_operate(Permission::TaskCommentManage, Permission::TaskComment, is_author) {
+        return Err(AppError::Forbidden(
+            "You don't have permission to delete this comment".to_string(),
+        ));
+    }
+
+    let deleted = TaskCommentRepository::delete_comment(&state.pool, comment_id.0).await?;
+    if !deleted {
+        return Err(AppError::NotFound(format!(
+            "Comment not found: {}",
+            comment_id
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,51 @@
+/// 单条评论最多解析的提及数量
+pub const MAX_MENTIONS: usize = 50;
+
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+/// 解析评论中的 `@username`（去重，保持出现顺序）
+///
+/// `@` 前紧邻字母或数字时不视为提及（如邮箱地址），用户名末尾的 `.` / `-` 视为标点。
+pub fn parse_mentions(content: &str) -> Vec<String> {
+    let mut usernames: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '@' && !prev.is_some_and(|p| p.is_ascii_alphanumeric()) {
+            let start = i + 1;
+            let mut end = start;
+            while let Some(&(j, next)) = chars.peek() {
+                if !is_username_char(next) {
+                    break;
+                }
+                end = j + next.len_utf8();
+                chars.next();
+            }
+            let username = content[start..end].trim_end_matches(['.', '-']);
+            if !username.is_empty() && !usernames.iter().any(|u| u == username) {
+                usernames.push(username.to_string());
+            }
+            prev = content[..end].chars().next_back();
+            continue;
+        }
+        prev = Some(c);
+    }
+    usernames.truncate(MAX_MENTIONS);
+    usernames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mentions() {
+        assert_eq!(
+            parse_mentions("@alice 请看一下，cc @bob.li. 邮件发 carol@example.com，再@alice"),
+            vec!["alice".to_string(), "bob.li".to_string()]
+        );
+        assert!(parse_mentions("@ 空 @@").is_empty());
+    }
+}
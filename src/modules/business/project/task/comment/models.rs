@@ -0,0 +1,57 @@
+use crate::common::id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 评论内容最大长度（字符）
+pub const MAX_COMMENT_LENGTH: usize = 10000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskComment {
+    pub id: Id,
+    pub project_id: Id,
+    pub task_id: Id,
+    /// 回复的评论，顶层评论为空
+    pub parent_id: Option<Id>,
+    /// 所在讨论串的顶层评论，顶层评论为空
+    pub root_id: Option<Id>,
+    pub content: String,
+    pub mention_user_ids: Vec<Id>,
+    pub creator_id: Id,
+    pub updater_id: Option<Id>,
+    pub create_date_time: chrono::NaiveDateTime,
+    pub update_date_time: Option<chrono::NaiveDateTime>,
+    // JOIN 字段
+    pub username: Option<String>,
+    pub full_name: Option<String>,
+}
+
+/// 讨论串：顶层评论及其全部回复（按时间正序）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCommentThread {
+    #[serde(flatten)]
+    pub comment: TaskComment,
+    pub replies: Vec<TaskComment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTaskCommentParams {
+    pub content: String,
+    /// 回复的评论
+    pub parent_id: Option<Id>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTaskCommentParams {
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCommentQueryParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
@@ -0,0 +1,153 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::comment::models::TaskComment;
+use sqlx::PgPool;
+
+pub struct TaskCommentRepository;
+
+/// project_task_comments 表 SELECT 列（含作者 JOIN 字段）
+const COMMENT_COLUMNS: &str = r#"c.id, c.project_id, c.task_id, c.parent_id, c.root_id, c.content,
+    c.mention_user_ids, c.creator_id, c.updater_id, c.create_date_time, c.update_date_time,
+    u.username, u.full_name"#;
+
+impl TaskCommentRepository {
+    /// 分页查询任务的顶层评论（按时间正序）
+    pub async fn get_root_comments(
+        pool: &PgPool,
+        task_id: i64,
+        page: i64,
+        per_page: i64,
+    ) -> AppResult<(Vec<TaskComment>, i64)> {
+        let offset = (page - 1) * per_page;
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_comments c
+               LEFT JOIN users u ON u.id = c.creator_id
+               WHERE c.task_id = $1 AND c.root_id IS NULL
+               ORDER BY c.create_date_time ASC, c.id ASC
+               LIMIT $2 OFFSET $3"#,
+            COMMENT_COLUMNS,
+        );
+        let comments = sqlx::query_as::<_, TaskComment>(&sql)
+            .bind(task_id)
+            .bind(per_page)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        let total: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM project_task_comments WHERE task_id = $1 AND root_id IS NULL",
+        )
+        .bind(task_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok((comments, total.0))
+    }
+
+    /// 查询讨论串下的全部回复
+    pub async fn get_replies(pool: &PgPool, root_ids: &[i64]) -> AppResult<Vec<TaskComment>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_comments c
+               LEFT JOIN users u ON u.id = c.creator_id
+               WHERE c.root_id = ANY($1)
+               ORDER BY c.create_date_time ASC, c.id ASC"#,
+            COMMENT_COLUMNS,
+        );
+        let replies = sqlx::query_as::<_, TaskComment>(&sql)
+            .bind(root_ids)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(replies)
+    }
+
+    pub async fn get_comment_by_id(
+        pool: &PgPool,
+        comment_id: i64,
+    ) -> AppResult<Option<TaskComment>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_comments c
+               LEFT JOIN users u ON u.id = c.creator_id
+               WHERE c.id = $1"#,
+            COMMENT_COLUMNS,
+        );
+        let comment = sqlx::query_as::<_, TaskComment>(&sql)
+            .bind(comment_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(comment)
+    }
+
+    /// 创建评论；回复时 root_id 取被回复评论所在的讨论串
+    pub async fn create_comment(
+        pool: &PgPool,
+        comment_id: i64,
+        task_id: i64,
+        parent: Option<&TaskComment>,
+        content: &str,
+        mention_user_ids: &[i64],
+        creator_id: i64,
+    ) -> AppResult<TaskComment> {
+        let parent_id = parent.map(|p| p.id.0);
+        let root_id = parent.map(|p| p.root_id.unwrap_or(p.id).0);
+        sqlx::query(
+            r#"INSERT INTO project_task_comments
+                   (id, project_id, task_id, parent_id, root_id, content, mention_user_ids, creator_id)
+               SELECT $1, t.project_id, t.id, $3, $4, $5, $6, $7
+               FROM project_tasks t
+               WHERE t.id = $2"#,
+        )
+        .bind(comment_id)
+        .bind(task_id)
+        .bind(parent_id)
+        .bind(root_id)
+        .bind(content)
+        .bind(mention_user_ids)
+        .bind(creator_id)
+        .execute(pool)
+        .await?;
+
+        Self::get_comment_by_id(pool, comment_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Task not found: {}", task_id)))
+    }
+
+    pub async fn update_comment(
+        pool: &PgPool,
+        comment_id: i64,
+        content: &str,
+        mention_user_ids: &[i64],
+        updater_id: i64,
+    ) -> AppResult<Option<TaskComment>> {
+        let result = sqlx::query(
+            r#"UPDATE project_task_comments
+               SET content = $2, mention_user_ids = $3, updater_id = $4,
+                   update_date_time = CURRENT_TIMESTAMP
+               WHERE id = $1"#,
+        )
+        .bind(comment_id)
+        .bind(content)
+        .bind(mention_user_ids)
+        .bind(updater_id)
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        Self::get_comment_by_id(pool, comment_id).await
+    }
+
+    /// 删除评论（级联删除其下的回复）
+    pub async fn delete_comment(pool: &PgPool, comment_id: i64) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM project_task_comments WHERE id = $1")
+            .bind(comment_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
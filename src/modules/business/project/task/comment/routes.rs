@@ -0,0 +1,40 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::comment::handlers;
+use axum::{
+    middleware,
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn task_comment_routes(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/comments",
+            get(handlers::get_comments),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/comments",
+            post(handlers::create_comment),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/comments/{comment_id}",
+            put(handlers::update_comment),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/comments/{comment_id}",
+            delete(handlers::delete_comment),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,304 @@
+use crate::common::error::{field_error, AppError, AppResult};
+use crate::common::id::Id;
+use crate::modules::business::project::task::dependency::models::{DependencyType, TaskDependency};
+use crate::modules::business::project::task::models::Task;
+use chrono::{Duration, NaiveDateTime};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskScheduleInfo {
+    pub task_id: Id,
+    pub task_name: String,
+    pub early_start: NaiveDateTime,
+    pub early_finish: NaiveDateTime,
+    pub late_start: NaiveDateTime,
+    pub late_finish: NaiveDateTime,
+    /// 总浮动时间（天）
+    pub total_float_days: f64,
+    /// 自由浮动时间（天）
+    pub free_float_days: f64,
+    pub is_critical: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathResponse {
+    pub project_start: Option<NaiveDateTime>,
+    pub project_finish: Option<NaiveDateTime>,
+    pub tasks: Vec<TaskScheduleInfo>,
+    /// 关键任务 ID，按最早开始时间排序
+    pub critical_task_ids: Vec<Id>,
+}
+
+/// 依赖图中的一条边（以任务下标表示）
+struct Edge {
+    from: usize,
+    to: usize,
+    dependency_type: DependencyType,
+    lag: Duration,
+}
+
+fn to_days(d: Duration) -> f64 {
+    d.num_seconds() as f64 / 86400.0
+}
+
+fn out_of_range() -> AppError {
+    let message = "Task dates and dependency lags exceed the supported date range".to_string();
+    AppError::ValidationError(
+        message.clone(),
+        vec![field_error("lagDays", "out_of_range", message)],
+    )
+}
+
+/// time + delta，溢出时返回 ValidationError
+fn add(time: NaiveDateTime, delta: Duration) -> AppResult<NaiveDateTime> {
+    time.checked_add_signed(delta).ok_or_else(out_of_range)
+}
+
+/// time - delta，溢出时返回 ValidationError
+fn sub(time: NaiveDateTime, delta: Duration) -> AppResult<NaiveDateTime> {
+    time.checked_sub_signed(delta).ok_or_else(out_of_range)
+}
+
+/// 对任务做拓扑排序，返回任务下标顺序；存在环时返回错误
+pub fn topological_order(task_ids: &[i64], dependencies: &[TaskDependency]) -> AppResult<Vec<usize>> {
+    let index: HashMap<i64, usize> = task_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut in_degree = vec![0usize; task_ids.len()];
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); task_ids.len()];
+
+    for dep in dependencies {
+        if let (Some(&from), Some(&to)) = (
+            index.get(&dep.predecessor_id.0),
+            index.get(&dep.successor_id.0),
+        ) {
+            successors[from].push(to);
+            in_degree[to] += 1;
+        }
+    }
+
+    let mut queue: VecDeque<usize> = (0..task_ids.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(task_ids.len());
+    while let Some(i) = queue.pop_front() {
+        order.push(i);
+        for &j in &successors[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                queue.push_back(j);
+            }
+        }
+    }
+
+    if order.len() != task_ids.len() {
+        return Err(AppError::BadRequest(
+            "Task dependencies contain a cycle".to_string(),
+        ));
+    }
+
+    Ok(order)
+}
+
+/// 关键路径（CPM）计算：基于任务依赖做正推 / 逆推，得出最早 / 最迟时间及浮动时间
+///
+/// - 没有前置依赖的任务以其计划开始时间作为最早开始时间，其余任务由依赖约束推出（尽早开始）
+/// - 最迟完成时间不晚于项目最晚完成时间
+/// - lag_days 按自然日计算
+pub fn compute_critical_path(
+    tasks: &[Task],
+    dependencies: &[TaskDependency],
+) -> AppResult<CriticalPathResponse> {
+    let task_ids: Vec<i64> = tasks.iter().map(|t| t.id.0).collect();
+    let order = topological_order(&task_ids, dependencies)?;
+    let index: HashMap<i64, usize> = task_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let mut incoming: Vec<Vec<Edge>> = (0..tasks.len()).map(|_| Vec::new()).collect();
+    let mut outgoing: Vec<Vec<Edge>> = (0..tasks.len()).map(|_| Vec::new()).collect();
+    for dep in dependencies {
+        let (Some(&from), Some(&to)) = (
+            index.get(&dep.predecessor_id.0),
+            index.get(&dep.successor_id.0),
+        ) else {
+            continue;
+        };
+        let dependency_type =
+            DependencyType::from_i32(dep.dependency_type).unwrap_or(DependencyType::FinishToStart);
+        let lag = Duration::days(dep.lag_days as i64);
+        incoming[to].push(Edge { from, to, dependency_type, lag });
+        outgoing[from].push(Edge { from, to, dependency_type, lag });
+    }
+
+    let durations: Vec<Duration> = tasks
+        .iter()
+        .map(|t| t.end_date_time - t.start_date_time)
+        .collect();
+
+    // 正推：最早开始 / 最早完成
+    let mut early_start: Vec<NaiveDateTime> = tasks.iter().map(|t| t.start_date_time).collect();
+    let mut early_finish: Vec<NaiveDateTime> = vec![NaiveDateTime::MIN; tasks.len()];
+    for &i in &order {
+        let mut required: Option<NaiveDateTime> = None;
+        for e in &incoming[i] {
+            let es = match e.dependency_type {
+                DependencyType::FinishToStart => add(early_finish[e.from], e.lag)?,
+                DependencyType::StartToStart => add(early_start[e.from], e.lag)?,
+                DependencyType::FinishToFinish => {
+                    sub(add(early_finish[e.from], e.lag)?, durations[i])?
+                }
+                DependencyType::StartToFinish => {
+                    sub(add(early_start[e.from], e.lag)?, durations[i])?
+                }
+            };
+            required = required.max(Some(es));
+        }
+        if let Some(es) = required {
+            early_start[i] = es;
+        }
+        early_finish[i] = add(early_start[i], durations[i])?;
+    }
+
+    let project_start = early_start.iter().min().copied();
+    let project_finish = early_finish.iter().max().copied();
+
+    // 逆推：最迟完成 / 最迟开始
+    let mut late_finish: Vec<NaiveDateTime> = vec![NaiveDateTime::MAX; tasks.len()];
+    let mut late_start: Vec<NaiveDateTime> = vec![NaiveDateTime::MAX; tasks.len()];
+    for &i in order.iter().rev() {
+        let mut finish = project_finish;
+        for e in &outgoing[i] {
+            let lf = match e.dependency_type {
+                DependencyType::FinishToStart => sub(late_start[e.to], e.lag)?,
+                DependencyType::StartToStart => add(sub(late_start[e.to], e.lag)?, durations[i])?,
+                DependencyType::FinishToFinish => sub(late_finish[e.to], e.lag)?,
+                DependencyType::StartToFinish => add(sub(late_finish[e.to], e.lag)?, durations[i])?,
+            };
+            finish = Some(finish.map_or(lf, |f| f.min(lf)));
+        }
+        late_finish[i] = finish.unwrap_or(early_finish[i]);
+        late_start[i] = sub(late_finish[i], durations[i])?;
+    }
+
+    let mut infos: Vec<TaskScheduleInfo> = Vec::with_capacity(tasks.len());
+    for (i, task) in tasks.iter().enumerate() {
+        let total_float = late_start[i] - early_start[i];
+        // 自由浮动：不推迟任何后续任务最早时间的前提下可延迟的时长
+        let mut free_float: Option<Duration> = None;
+        for e in &outgoing[i] {
+            let float = match e.dependency_type {
+                DependencyType::FinishToStart => early_start[e.to] - add(early_finish[i], e.lag)?,
+                DependencyType::StartToStart => early_start[e.to] - add(early_start[i], e.lag)?,
+                DependencyType::FinishToFinish => early_finish[e.to] - add(early_finish[i], e.lag)?,
+                DependencyType::StartToFinish => early_finish[e.to] - add(early_start[i], e.lag)?,
+            };
+            free_float = Some(free_float.map_or(float, |f| f.min(float)));
+        }
+        let free_float = free_float
+            .unwrap_or_else(|| project_finish.unwrap_or(early_finish[i]) - early_finish[i]);
+
+        infos.push(TaskScheduleInfo {
+            task_id: task.id,
+            task_name: task.task_name.clone(),
+            early_start: early_start[i],
+            early_finish: early_finish[i],
+            late_start: late_start[i],
+            late_finish: late_finish[i],
+            total_float_days: to_days(total_float),
+            free_float_days: to_days(free_float),
+            is_critical: total_float <= Duration::zero(),
+        });
+    }
+
+    let mut critical: Vec<usize> = order.iter().copied().filter(|&i| infos[i].is_critical).collect();
+    critical.sort_by_key(|&i| early_start[i]);
+    let critical_task_ids = critical.into_iter().map(|i| infos[i].task_id).collect();
+
+    Ok(CriticalPathResponse {
+        project_start,
+        project_finish,
+        tasks: infos,
+        critical_task_ids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn task(id: i64, start_day: u32, days: u32) -> Task {
+        Task {
+            start_date_time: at(start_day),
+            end_date_time: at(start_day + days),
+            ..Task::test(id)
+        }
+    }
+
+    #[test]
+    fn test_finish_to_start_chain() {
+        // 1(3d) -> 2(2d) -> 4(1d); 1 -> 3(1d) -> 4
+        let tasks = vec![task(1, 1, 3), task(2, 1, 2), task(3, 1, 1), task(4, 1, 1)];
+        let deps = vec![
+            TaskDependency::test(1, 2, DependencyType::FinishToStart, 0),
+            TaskDependency::test(2, 4, DependencyType::FinishToStart, 0),
+            TaskDependency::test(1, 3, DependencyType::FinishToStart, 0),
+            TaskDependency::test(3, 4, DependencyType::FinishToStart, 0),
+        ];
+        let result = compute_critical_path(&tasks, &deps).unwrap();
+
+        assert_eq!(result.project_finish, Some(at(7)));
+        assert_eq!(result.critical_task_ids, vec![Id(1), Id(2), Id(4)]);
+        let t3 = &result.tasks[2];
+        assert_eq!(t3.early_start, at(4));
+        assert_eq!(t3.total_float_days, 1.0);
+        assert_eq!(t3.free_float_days, 1.0);
+    }
+
+    #[test]
+    fn test_lag_and_start_to_start() {
+        let tasks = vec![task(1, 1, 4), task(2, 1, 2)];
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::StartToStart, 1)];
+        let result = compute_critical_path(&tasks, &deps).unwrap();
+
+        assert_eq!(result.tasks[1].early_start, at(2));
+        assert_eq!(result.tasks[1].total_float_days, 1.0);
+        assert_eq!(result.critical_task_ids, vec![Id(1)]);
+    }
+
+    #[test]
+    fn test_finish_to_finish() {
+        let tasks = vec![task(1, 1, 4), task(2, 1, 2)];
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToFinish, 0)];
+        let result = compute_critical_path(&tasks, &deps).unwrap();
+
+        assert_eq!(result.tasks[1].early_start, at(3));
+        assert_eq!(result.tasks[1].early_finish, at(5));
+        assert_eq!(result.critical_task_ids, vec![Id(1), Id(2)]);
+    }
+
+    #[test]
+    fn test_cycle_is_rejected() {
+        let tasks = vec![task(1, 1, 1), task(2, 1, 1)];
+        let deps = vec![
+            TaskDependency::test(1, 2, DependencyType::FinishToStart, 0),
+            TaskDependency::test(2, 1, DependencyType::FinishToStart, 0),
+        ];
+        assert!(compute_critical_path(&tasks, &deps).is_err());
+    }
+
+    #[test]
+    fn test_lag_overflow_is_rejected() {
+        let tasks = vec![task(1, 1, 1), task(2, 1, 1)];
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToStart, i32::MAX)];
+        assert!(matches!(
+            compute_critical_path(&tasks, &deps),
+            Err(AppError::ValidationError(..))
+        ));
+    }
+}
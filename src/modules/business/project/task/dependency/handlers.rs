@@ -0,0 +1,191 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::dependency::models::{
+    CreateTaskDependencyParams, DependencyType, TaskDependency, TaskDependencyQueryParams,
+    UpdateTaskDependencyParams, MAX_LAG_DAYS,
+};
+use crate::modules::business::project::task::dependency::repository::TaskDependencyRepository;
+use crate::modules::business::project::task::handlers::require_task_permission;
+use crate::modules::business::project::task::models::Task;
+use crate::modules::business::project::task::repository::TaskRepository;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use sqlx::PgPool;
+
+fn validate_dependency_type(dependency_type: i32) -> AppResult<()> {
+    if DependencyType::from_i32(dependency_type).is_none() {
+        return Err(AppError::BadRequest(format!(
+            "Invalid dependency type: {}. Valid: 0-3",
+            dependency_type
+        )));
+    }
+    Ok(())
+}
+
+fn validate_lag_days(lag_days: i32) -> AppResult<()> {
+    if !(-MAX_LAG_DAYS..=MAX_LAG_DAYS).contains(&lag_days) {
+        return Err(AppError::BadRequest(format!(
+            "Lag days must be between -{} and {}",
+            MAX_LAG_DAYS, MAX_LAG_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// 依赖约束的是后续任务的排期，因此按后续任务检查编辑权限
+async fn require_edit_successor(
+    pool: &PgPool,
+    perm: &ProjectPermission,
+    successor: &Task,
+) -> AppResult<()> {
+    require_task_permission(
+        pool,
+        perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[successor.id.0],
+        "edit",
+    )
+    .await
+}
+
+async fn get_project_dependency(
+    pool: &PgPool,
+    project_id: i64,
+    dependency_id: Id,
+) -> AppResult<TaskDependency> {
+    TaskDependencyRepository::get_dependency_by_id(pool, dependency_id.0)
+        .await?
+        .filter(|d| d.project_id.0 == project_id)
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Task dependency not found: {}", dependency_id))
+        })
+}
+
+pub async fn get_dependencies(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Query(params): Query<TaskDependencyQueryParams>,
+) -> AppResult<Json<ApiResponse<Vec<TaskDependency>>>> {
+    perm.require(Permission::TaskView)?;
+    let dependencies = TaskDependencyRepository::get_dependencies_by_project(
+        &state.pool,
+        project_id.0,
+        params.task_id.map(|id| id.0),
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(dependencies)))
+}
+
+pub async fn get_dependency_by_id(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, dependency_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<TaskDependency>>> {
+    perm.require(Permission::TaskView)?;
+    let dependency = get_project_dependency(&state.pool, project_id.0, dependency_id).await?;
+    Ok(Json(ApiResponse::success(dependency)))
+}
+
+pub async fn create_dependency(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<CreateTaskDependencyParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<TaskDependency>>)> {
+    if params.predecessor_id == params.successor_id {
+        return Err(AppError::BadRequest(
+            "A task cannot depend on itself".to_string(),
+        ));
+    }
+    if let Some(dependency_type) = params.dependency_type {
+        validate_dependency_type(dependency_type)?;
+    }
+    if let Some(lag_days) = params.lag_days {
+        validate_lag_days(lag_days)?;
+    }
+
+    TaskRepository::get_project_task(&state.pool, project_id.0, params.predecessor_id.0).await?;
+    let successor =
+        TaskRepository::get_project_task(&state.pool, project_id.0, params.successor_id.0).await?;
+    require_edit_successor(&state.pool, &perm, &successor).await?;
+
+    let creator_id = claims.sub;
+    let dependency_id = state.generate_id().map_err(|e| {
+        AppError::InternalError(format!("Failed to generate task dependency ID: {}", e))
+    })?;
+
+    let dependency = TaskDependencyRepository::create_dependency(
+        &state.pool,
+        dependency_id,
+        project_id.0,
+        params,
+        creator_id,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(dependency))))
+}
+
+pub async fn update_dependency(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, dependency_id)): Path<(Id, Id)>,
+    Json(params): Json<UpdateTaskDependencyParams>,
+) -> AppResult<Json<ApiResponse<TaskDependency>>> {
+    if let Some(dependency_type) = params.dependency_type {
+        validate_dependency_type(dependency_type)?;
+    }
+    if let Some(lag_days) = params.lag_days {
+        validate_lag_days(lag_days)?;
+    }
+
+    let dependency = get_project_dependency(&state.pool, project_id.0, dependency_id).await?;
+    let successor =
+        TaskRepository::get_project_task(&state.pool, project_id.0, dependency.successor_id.0)
+            .await?;
+    require_edit_successor(&state.pool, &perm, &successor).await?;
+
+    let updater_id = claims.sub;
+    let dependency = TaskDependencyRepository::update_dependency(
+        &state.pool,
+        dependency_id.0,
+        params,
+        updater_id,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Task dependency not found: {}", dependency_id)))?;
+
+    Ok(Json(ApiResponse::success(dependency)))
+}
+
+pub async fn delete_dependency(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, dependency_id)): Path<(Id, Id)>,
+) -> AppResult<StatusCode> {
+    let dependency = get_project_dependency(&state.pool, project_id.0, dependency_id).await?;
+    let successor =
+        TaskRepository::get_project_task(&state.pool, project_id.0, dependency.successor_id.0)
+            .await?;
+    require_edit_successor(&state.pool, &perm, &successor).await?;
+
+    let deleted = TaskDependencyRepository::delete_dependency(&state.pool, dependency_id.0).await?;
+    if !deleted {
+        return Err(AppError::NotFound(format!(
+            "Task dependency not found: {}",
+            dependency_id
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
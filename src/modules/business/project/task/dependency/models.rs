@@ -0,0 +1,103 @@
+use crate::common::id::Id;
+use crate::modules::holiday::calendar::MAX_WORKING_DAYS;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// lag_days 允许的最大绝对值（工作日）
+pub const MAX_LAG_DAYS: i32 = MAX_WORKING_DAYS as i32;
+
+/// 任务依赖类型
+/// FS: 前置完成后才能开始；SS: 前置开始后才能开始；
+/// FF: 前置完成后才能完成；SF: 前置开始后才能完成
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum DependencyType {
+    FinishToStart = 0,
+    StartToStart = 1,
+    FinishToFinish = 2,
+    StartToFinish = 3,
+}
+
+impl DependencyType {
+    pub fn from_i32(v: i32) -> Option<Self> {
+        match v {
+            0 => Some(DependencyType::FinishToStart),
+            1 => Some(DependencyType::StartToStart),
+            2 => Some(DependencyType::FinishToFinish),
+            3 => Some(DependencyType::StartToFinish),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        self as i32
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependency {
+    pub id: Id,
+    pub project_id: Id,
+    pub predecessor_id: Id,
+    pub successor_id: Id,
+    /// 0=finish_to_start, 1=start_to_start, 2=finish_to_finish, 3=start_to_finish
+    pub dependency_type: i32,
+    /// 滞后天数，负数表示提前量
+    pub lag_days: i32,
+    pub creator_id: Id,
+    pub updater_id: Option<Id>,
+    pub create_date_time: chrono::NaiveDateTime,
+    pub update_date_time: Option<chrono::NaiveDateTime>,
+}
+
+#[cfg(test)]
+impl TaskDependency {
+    /// 测试用依赖：项目 1 下 predecessor_id -> successor_id
+    pub fn test(
+        predecessor_id: i64,
+        successor_id: i64,
+        dependency_type: DependencyType,
+        lag_days: i32,
+    ) -> Self {
+        TaskDependency {
+            id: Id(predecessor_id * 100 + successor_id),
+            project_id: Id(1),
+            predecessor_id: Id(predecessor_id),
+            successor_id: Id(successor_id),
+            dependency_type: dependency_type.as_i32(),
+            lag_days,
+            creator_id: Id(1),
+            updater_id: None,
+            create_date_time: chrono::NaiveDateTime::default(),
+            update_date_time: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTaskDependencyParams {
+    pub predecessor_id: Id,
+    pub successor_id: Id,
+    /// 不传默认为 finish_to_start
+    pub dependency_type: Option<i32>,
+    /// 不传默认为 0
+    pub lag_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTaskDependencyParams {
+    /// NOT NULL 字段
+    pub dependency_type: Option<i32>,
+    /// NOT NULL 字段
+    pub lag_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependencyQueryParams {
+    /// 仅返回与该任务相关（作为前置或后续）的依赖
+    pub task_id: Option<Id>,
+}
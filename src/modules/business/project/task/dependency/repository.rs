@@ -0,0 +1,222 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::dependency::models::{
+    CreateTaskDependencyParams, DependencyType, TaskDependency, UpdateTaskDependencyParams,
+};
+use sqlx::{PgConnection, PgPool};
+use sqlx::QueryBuilder;
+
+pub struct TaskDependencyRepository;
+
+/// project_task_dependencies 表 SELECT 列
+const DEPENDENCY_COLUMNS: &str = "id, project_id, predecessor_id, successor_id, dependency_type, \
+    lag_days, creator_id, updater_id, create_date_time, update_date_time";
+
+/// project_task_dependencies 表 RETURNING 列
+const DEPENDENCY_RETURNING: &str = " RETURNING id, project_id, predecessor_id, successor_id, dependency_type, \
+    lag_days, creator_id, updater_id, create_date_time, update_date_time";
+
+impl TaskDependencyRepository {
+    /// 项目中的全部依赖（FOR SHARE），排期写入期间依赖不会被并发修改；需在事务中调用
+    pub async fn lock_dependencies_by_project(
+        conn: &mut PgConnection,
+        project_id: i64,
+    ) -> AppResult<Vec<TaskDependency>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_dependencies
+               WHERE project_id = $1
+               ORDER BY create_date_time ASC
+               FOR SHARE"#,
+            DEPENDENCY_COLUMNS,
+        );
+        let dependencies = sqlx::query_as::<_, TaskDependency>(&sql)
+            .bind(project_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(dependencies)
+    }
+
+    pub async fn get_dependencies_by_project(
+        pool: &PgPool,
+        project_id: i64,
+        task_id: Option<i64>,
+    ) -> AppResult<Vec<TaskDependency>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_dependencies
+               WHERE project_id = $1
+                 AND ($2::BIGINT IS NULL OR predecessor_id = $2 OR successor_id = $2)
+               ORDER BY create_date_time ASC"#,
+            DEPENDENCY_COLUMNS,
+        );
+        let dependencies = sqlx::query_as::<_, TaskDependency>(&sql)
+            .bind(project_id)
+            .bind(task_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(dependencies)
+    }
+
+    pub async fn get_dependency_by_id(
+        pool: &PgPool,
+        dependency_id: i64,
+    ) -> AppResult<Option<TaskDependency>> {
+        let sql = format!(
+            "SELECT {} FROM project_task_dependencies WHERE id = $1",
+            DEPENDENCY_COLUMNS,
+        );
+        let dependency = sqlx::query_as::<_, TaskDependency>(&sql)
+            .bind(dependency_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(dependency)
+    }
+
+    async fn exists_dependency(
+        conn: &mut PgConnection,
+        predecessor_id: i64,
+        successor_id: i64,
+    ) -> AppResult<bool> {
+        let row: (bool,) = sqlx::query_as(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM project_task_dependencies
+                   WHERE predecessor_id = $1 AND successor_id = $2
+               )"#,
+        )
+        .bind(predecessor_id)
+        .bind(successor_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// 检查新增 predecessor -> successor 是否会形成环：
+    /// 即从 successor 出发沿已有依赖能否到达 predecessor
+    async fn would_create_cycle(
+        conn: &mut PgConnection,
+        predecessor_id: i64,
+        successor_id: i64,
+    ) -> AppResult<bool> {
+        let row: (bool,) = sqlx::query_as(
+            r#"
+            WITH RECURSIVE reachable AS (
+                SELECT successor_id AS id
+                FROM project_task_dependencies
+                WHERE predecessor_id = $2
+                UNION
+                SELECT d.successor_id
+                FROM project_task_dependencies d
+                INNER JOIN reachable r ON d.predecessor_id = r.id
+            )
+            SELECT EXISTS(SELECT 1 FROM reachable WHERE id = $1)
+            "#,
+        )
+        .bind(predecessor_id)
+        .bind(successor_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// 创建依赖：锁定项目行后检查重复与成环并插入（同一事务），避免并发请求共同形成环
+    pub async fn create_dependency(
+        pool: &PgPool,
+        id: i64,
+        project_id: i64,
+        params: CreateTaskDependencyParams,
+        creator_id: i64,
+    ) -> AppResult<TaskDependency> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("SELECT id FROM projects WHERE id = $1 FOR UPDATE")
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+        if Self::exists_dependency(&mut tx, params.predecessor_id.0, params.successor_id.0).await? {
+            return Err(AppError::Conflict(
+                "Task dependency already exists".to_string(),
+            ));
+        }
+        if Self::would_create_cycle(&mut tx, params.predecessor_id.0, params.successor_id.0).await? {
+            return Err(AppError::BadRequest(
+                "Task dependency would create a cycle".to_string(),
+            ));
+        }
+
+        let dependency_type = params
+            .dependency_type
+            .unwrap_or(DependencyType::FinishToStart.as_i32());
+        let sql = format!(
+            r#"INSERT INTO project_task_dependencies
+               (id, project_id, predecessor_id, successor_id, dependency_type, lag_days,
+                creator_id, create_date_time)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP){}"#,
+            DEPENDENCY_RETURNING,
+        );
+        let dependency = sqlx::query_as::<_, TaskDependency>(&sql)
+            .bind(id)
+            .bind(project_id)
+            .bind(params.predecessor_id)
+            .bind(params.successor_id)
+            .bind(dependency_type)
+            .bind(params.lag_days.unwrap_or(0))
+            .bind(creator_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(dependency)
+    }
+
+    pub async fn update_dependency(
+        pool: &PgPool,
+        dependency_id: i64,
+        params: UpdateTaskDependencyParams,
+        updater_id: i64,
+    ) -> AppResult<Option<TaskDependency>> {
+        // 动态构建 SET 子句
+        let mut qb: QueryBuilder<sqlx::Postgres> =
+            QueryBuilder::new("UPDATE project_task_dependencies SET ");
+        let mut has_set = false;
+
+        if let Some(dt) = params.dependency_type {
+            qb.push("dependency_type = ");
+            qb.push_bind(dt);
+            has_set = true;
+        }
+
+        if let Some(lag) = params.lag_days {
+            if has_set { qb.push(", "); }
+            qb.push("lag_days = ");
+            qb.push_bind(lag);
+            has_set = true;
+        }
+
+        if has_set { qb.push(", "); }
+        qb.push("updater_id = ");
+        qb.push_bind(updater_id);
+        qb.push(", update_date_time = CURRENT_TIMESTAMP WHERE id = ");
+        qb.push_bind(dependency_id);
+        qb.push(DEPENDENCY_RETURNING);
+
+        let dependency = qb
+            .build_query_as::<TaskDependency>()
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(dependency)
+    }
+
+    pub async fn delete_dependency(pool: &PgPool, dependency_id: i64) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM project_task_dependencies WHERE id = $1")
+            .bind(dependency_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
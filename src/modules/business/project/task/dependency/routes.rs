@@ -0,0 +1,44 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::dependency::handlers;
+use axum::{
+    middleware,
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn task_dependency_routes(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/projects/{project_id}/task-dependencies",
+            get(handlers::get_dependencies),
+        )
+        .route(
+            "/projects/{project_id}/task-dependencies",
+            post(handlers::create_dependency),
+        )
+        .route(
+            "/projects/{project_id}/task-dependencies/{dependency_id}",
+            get(handlers::get_dependency_by_id),
+        )
+        .route(
+            "/projects/{project_id}/task-dependencies/{dependency_id}",
+            put(handlers::update_dependency),
+        )
+        .route(
+            "/projects/{project_id}/task-dependencies/{dependency_id}",
+            delete(handlers::delete_dependency),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,270 @@
+use crate::common::error::AppResult;
+use crate::common::id::Id;
+use crate::modules::business::project::task::baseline::models::BaselineTask;
+use crate::modules::business::project::task::models::{Task, TaskType};
+use crate::modules::business::project::task::scheduler::working_days_between;
+use crate::modules::business::project::task::tree::group_by_parent;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 单个任务的挣值指标（父任务为子树汇总）
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEarnedValue {
+    pub task_id: Id,
+    pub task_name: String,
+    pub parent_id: Option<Id>,
+    pub has_children: bool,
+    /// 是否纳入计算：指定基线时，不在基线中的叶子任务不计入
+    pub included: bool,
+    /// 计划工期（工作日），即完工预算
+    pub budget_days: f64,
+    pub planned_value: f64,
+    pub earned_value: f64,
+    pub schedule_variance: f64,
+    /// 截至状态日期的计划完成百分比
+    pub planned_percent: f64,
+    /// 按工期加权的实际完成百分比
+    pub percent_complete: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EarnedValueResponse {
+    pub status_date: NaiveDate,
+    pub baseline_id: Option<Id>,
+    /// 完工预算（工作日）
+    pub budget_at_completion: f64,
+    pub planned_value: f64,
+    pub earned_value: f64,
+    /// 进度偏差 SV = EV - PV（工作日）
+    pub schedule_variance: f64,
+    /// 进度绩效指数 SPI = EV / PV，PV 为 0 时为空
+    pub schedule_performance_index: Option<f64>,
+    pub planned_percent: f64,
+    pub percent_complete: f64,
+    pub tasks: Vec<TaskEarnedValue>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct Totals {
+    budget: f64,
+    planned: f64,
+    earned: f64,
+    included: bool,
+}
+
+impl Totals {
+    fn add(&mut self, other: Totals) {
+        self.budget += other.budget;
+        self.planned += other.planned;
+        self.earned += other.earned;
+        self.included |= other.included;
+    }
+}
+
+fn percent(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total * 100.0
+    } else {
+        0.0
+    }
+}
+
+struct Context<'a> {
+    children: HashMap<i64, Vec<&'a Task>>,
+    /// 基线快照中的计划起止日期与任务类型；为空时使用任务当前值
+    planned_dates: Option<HashMap<i64, (NaiveDate, NaiveDate, i32)>>,
+    calendar: &'a WorkingCalendar,
+    status_date: NaiveDate,
+}
+
+impl Context<'_> {
+    fn leaf_totals(&self, task: &Task) -> AppResult<Totals> {
+        let dates = match &self.planned_dates {
+            Some(planned) => planned.get(&task.id.0).copied(),
+            None => Some((
+                task.start_date_time.date(),
+                task.end_date_time.date(),
+                task.task_type,
+            )),
+        };
+        let Some((start, end, task_type)) = dates else {
+            return Ok(Totals::default());
+        };
+        if TaskType::from_i32(task_type).is_some_and(TaskType::is_zero_duration) {
+            return Ok(Totals {
+                included: true,
+                ..Totals::default()
+            });
+        }
+
+        let budget = working_days_between(self.calendar, start, end)? as f64;
+        let planned_fraction = if self.status_date < start {
+            0.0
+        } else if self.status_date >= end {
+            1.0
+        } else {
+            (self.calendar.count_working_days(start, self.status_date)? as f64 / budget).min(1.0)
+        };
+        Ok(Totals {
+            budget,
+            planned: budget * planned_fraction,
+            earned: budget * task.percent_complete.clamp(0.0, 100.0) / 100.0,
+            included: true,
+        })
+    }
+
+    /// 先序输出任务，后序汇总子树
+    fn visit(&self, task: &Task, items: &mut Vec<Option<TaskEarnedValue>>) -> AppResult<Totals> {
+        let index = items.len();
+        items.push(None);
+
+        let children = self.children.get(&task.id.0);
+        let totals = match children {
+            Some(children) => {
+                let mut totals = Totals::default();
+                for child in children {
+                    totals.add(self.visit(child, items)?);
+                }
+                totals
+            }
+            None => self.leaf_totals(task)?,
+        };
+
+        items[index] = Some(TaskEarnedValue {
+            task_id: task.id,
+            task_name: task.task_name.clone(),
+            parent_id: task.parent_id,
+            has_children: children.is_some(),
+            included: totals.included,
+            budget_days: totals.budget,
+            planned_value: totals.planned,
+            earned_value: totals.earned,
+            schedule_variance: totals.earned - totals.planned,
+            planned_percent: percent(totals.planned, totals.budget),
+            percent_complete: percent(totals.earned, totals.budget),
+        });
+        Ok(totals)
+    }
+}
+
+/// 计算项目在状态日期的挣值指标。
+///
+/// 以叶子任务的工期（工作日，扣除节假日）为预算，里程碑与检查点预算为 0：
+/// PV 按状态日期前已过的计划工作日占比计，EV 按完成百分比计。
+/// 传入基线时使用基线快照中的计划日期，不在基线中的任务不计入。
+pub fn compute_earned_value(
+    tasks: &[Task],
+    baseline: Option<&[BaselineTask]>,
+    calendar: &WorkingCalendar,
+    status_date: NaiveDate,
+) -> AppResult<EarnedValueResponse> {
+    let (roots, children) = group_by_parent(tasks.iter().collect());
+
+    let context = Context {
+        children,
+        planned_dates: baseline.map(|snapshot| {
+            snapshot
+                .iter()
+                .map(|b| {
+                    (
+                        b.task_id.0,
+                        (b.start_date_time.date(), b.end_date_time.date(), b.task_type),
+                    )
+                })
+                .collect()
+        }),
+        calendar,
+        status_date,
+    };
+
+    let mut items = Vec::with_capacity(tasks.len());
+    let mut totals = Totals::default();
+    for root in roots {
+        totals.add(context.visit(root, &mut items)?);
+    }
+
+    Ok(EarnedValueResponse {
+        status_date,
+        baseline_id: None,
+        budget_at_completion: totals.budget,
+        planned_value: totals.planned,
+        earned_value: totals.earned,
+        schedule_variance: totals.earned - totals.planned,
+        schedule_performance_index: (totals.planned > 0.0)
+            .then(|| totals.earned / totals.planned),
+        planned_percent: percent(totals.planned, totals.budget),
+        percent_complete: percent(totals.earned, totals.budget),
+        tasks: items.into_iter().flatten().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::holiday::models::HolidayType;
+    use serde_json::json;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    fn task(id: i64, parent_id: Option<i64>, start_day: u32, end_day: u32, percent: f64) -> Task {
+        Task {
+            parent_id: parent_id.map(Id),
+            start_date_time: date(start_day).and_hms_opt(9, 0, 0).unwrap(),
+            end_date_time: date(end_day).and_hms_opt(18, 0, 0).unwrap(),
+            percent_complete: percent,
+            ..Task::test(id)
+        }
+    }
+
+    #[test]
+    fn test_earned_value() {
+        // 2026-01-05 为周一，01-07 为假期
+        let calendar = WorkingCalendar::new([(date(7), HolidayType::CompanyHoliday)]);
+        let tasks = vec![
+            task(1, None, 5, 16, 0.0),
+            // 工期 4 个工作日（5、6、8、9），截至 01-08 计划完成 3 天，实际完成 50%
+            task(2, Some(1), 5, 9, 50.0),
+            // 工期 5 个工作日，尚未开始
+            task(3, Some(1), 12, 16, 20.0),
+            // 里程碑不占预算
+            Task {
+                task_type: 2,
+                ..task(4, Some(1), 9, 9, 100.0)
+            },
+        ];
+
+        let result = compute_earned_value(&tasks, None, &calendar, date(8)).unwrap();
+        assert_eq!(result.budget_at_completion, 9.0);
+        assert_eq!(result.planned_value, 3.0);
+        assert_eq!(result.earned_value, 3.0);
+        assert_eq!(result.schedule_variance, 0.0);
+        assert_eq!(result.schedule_performance_index, Some(1.0));
+        assert_eq!(result.tasks.len(), 4);
+        assert!(result.tasks[0].has_children);
+        assert_eq!(result.tasks[3].budget_days, 0.0);
+        assert!((result.tasks[0].percent_complete - 100.0 / 3.0).abs() < 1e-9);
+
+        // 基线中任务 2 计划 01-05 ~ 01-06 完成，任务 3 不在基线中
+        let baseline = vec![BaselineTask {
+            task_id: Id(2),
+            task_name: "task-2".to_string(),
+            parent_id: Some(Id(1)),
+            task_type: 1,
+            start_date_time: date(5).and_hms_opt(9, 0, 0).unwrap(),
+            end_date_time: date(6).and_hms_opt(18, 0, 0).unwrap(),
+            custom_attributes: json!({}),
+        }];
+        let result = compute_earned_value(&tasks, Some(&baseline), &calendar, date(8)).unwrap();
+        assert_eq!(result.budget_at_completion, 2.0);
+        assert_eq!(result.planned_value, 2.0);
+        assert_eq!(result.earned_value, 1.0);
+        assert_eq!(result.schedule_performance_index, Some(0.5));
+        assert!(!result.tasks[2].included);
+    }
+}
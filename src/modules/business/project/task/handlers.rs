@@ -1,20 +1,45 @@
 use crate::common::app_state::AppState;
-use crate::common::error::{AppError, AppResult};
+use crate::common::error::{field_error, AppError, AppResult};
 use crate::common::id::Id;
 use crate::common::jwt::Claims;
 use crate::common::response::{ApiResponse, PaginatedResponse};
 use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::repository::ProjectRepository;
+use crate::modules::business::project::task::attachment::handlers::cleanup_task_files;
+use crate::modules::business::project::task::attributes::validate_custom_attributes;
+use crate::modules::business::project::task::critical_path::{
+    compute_critical_path, CriticalPathResponse,
+};
+use crate::modules::business::project::task::baseline::handlers::get_project_baseline;
+use crate::modules::business::project::task::baseline::repository::BaselineRepository;
+use crate::modules::business::project::task::dependency::models::TaskDependency;
+use crate::modules::business::project::task::dependency::repository::TaskDependencyRepository;
+use crate::modules::business::project::task::checkpoint::models::CheckpointDecision;
+use crate::modules::business::project::task::checkpoint::repository::CheckpointRepository;
+use crate::modules::business::project::task::earned_value::{
+    compute_earned_value, EarnedValueResponse,
+};
+use crate::modules::business::project::task::history::handlers::record_history;
 use crate::modules::business::project::task::models::{
     BatchCreateTasksParams, BatchDeleteTaskAttributeConfigsParams, BatchDeleteTasksParams,
-    CreateTaskAttributeConfigParams, CreateTaskParams, Task, TaskAttributeConfig, TaskQueryParams,
+    BatchUpdateTasksParams,
+    CreateTaskAttributeConfigParams, CreateTaskParams, EarnedValueQuery, MoveTaskParams,
+    ScheduleTaskParams, Task,
+    TaskAttributeConfig, TaskQueryParams, TaskTreeNode, TaskTreeQuery, TaskType,
     UpdateTaskAttributeConfigParams, UpdateTaskParams,
 };
+use crate::modules::business::project::task::ordering::{plan_move, would_create_cycle};
+use crate::modules::business::project::task::query::TaskListQuery;
 use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::business::project::task::scheduler::{reschedule, ScheduleChange};
+use crate::modules::business::project::task::tree::{build_task_tree, parent_date_changes};
+use crate::modules::holiday::calendar::WorkingCalendar;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     Extension, Json,
 };
+use sqlx::{PgConnection, PgPool};
 
 pub async fn get_attribute_configs(
     State(state): State<AppState>,
@@ -59,14 +84,17 @@ pub async fn create_attribute_config(
         ))
     })?;
 
+    let mut tx = state.pool.begin().await?;
     let config = TaskRepository::create_attribute_config(
-        &state.pool,
+        &mut tx,
         config_id,
         project_id.0,
         params,
         creator_id,
     )
     .await?;
+    record_history(&state, &mut tx, &[], std::slice::from_ref(&config), creator_id).await?;
+    tx.commit().await?;
 
     Ok((StatusCode::CREATED, Json(ApiResponse::success(config))))
 }
@@ -81,9 +109,12 @@ pub async fn update_attribute_config(
     perm.require(Permission::AttributeConfigEdit)?;
     let updater_id = claims.sub;
 
+    let before = TaskRepository::get_attribute_configs_by_ids(&state.pool, &[config_id.0]).await?;
+    let mut tx = state.pool.begin().await?;
     let config =
-        TaskRepository::update_attribute_config(&state.pool, config_id.0, params, updater_id)
-            .await?;
+        TaskRepository::update_attribute_config(&mut tx, config_id.0, params, updater_id).await?;
+    record_history(&state, &mut tx, &before, std::slice::from_ref(&config), updater_id).await?;
+    tx.commit().await?;
 
     Ok(Json(ApiResponse::success(config)))
 }
@@ -96,7 +127,13 @@ pub async fn delete_attribute_config(
 ) -> AppResult<StatusCode> {
     perm.require(Permission::AttributeConfigArchive)?;
     let updater_id = claims.sub;
-    TaskRepository::archive_attribute_config(&state.pool, config_id.0, updater_id).await?;
+    let before = TaskRepository::get_attribute_configs_by_ids(&state.pool, &[config_id.0]).await?;
+    let mut tx = state.pool.begin().await?;
+    let after =
+        TaskRepository::set_attribute_configs_archived(&mut tx, &[config_id.0], true, updater_id)
+            .await?;
+    record_history(&state, &mut tx, &before, &after, updater_id).await?;
+    tx.commit().await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -110,7 +147,12 @@ pub async fn batch_delete_attribute_configs(
     perm.require(Permission::AttributeConfigArchive)?;
     let updater_id = claims.sub;
     let ids: Vec<i64> = params.ids.into_iter().map(|id| id.0).collect();
-    TaskRepository::batch_archive_attribute_configs(&state.pool, ids, updater_id).await?;
+    let before = TaskRepository::get_attribute_configs_by_ids(&state.pool, &ids).await?;
+    let mut tx = state.pool.begin().await?;
+    let after =
+        TaskRepository::set_attribute_configs_archived(&mut tx, &ids, true, updater_id).await?;
+    record_history(&state, &mut tx, &before, &after, updater_id).await?;
+    tx.commit().await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -122,32 +164,87 @@ pub async fn restore_attribute_config(
 ) -> AppResult<StatusCode> {
     perm.require(Permission::AttributeConfigArchive)?;
     let updater_id = claims.sub;
-    TaskRepository::restore_attribute_config(&state.pool, config_id.0, updater_id).await?;
+    let before = TaskRepository::get_attribute_configs_by_ids(&state.pool, &[config_id.0]).await?;
+    let mut tx = state.pool.begin().await?;
+    let after =
+        TaskRepository::set_attribute_configs_archived(&mut tx, &[config_id.0], false, updater_id)
+            .await?;
+    record_history(&state, &mut tx, &before, &after, updater_id).await?;
+    tx.commit().await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn hard_delete_attribute_config(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Extension(perm): Extension<ProjectPermission>,
     Path((_project_id, config_id)): Path<(Id, Id)>,
 ) -> AppResult<StatusCode> {
     perm.require(Permission::AttributeConfigArchive)?;
-    TaskRepository::delete_attribute_config(&state.pool, config_id.0).await?;
+    let before = TaskRepository::get_attribute_configs_by_ids(&state.pool, &[config_id.0]).await?;
+    let mut tx = state.pool.begin().await?;
+    TaskRepository::delete_attribute_config(&mut tx, config_id.0).await?;
+    record_history(&state, &mut tx, &before, &[], claims.sub).await?;
+    tx.commit().await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn batch_hard_delete_attribute_configs(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Extension(perm): Extension<ProjectPermission>,
     Path(_project_id): Path<Id>,
     Json(params): Json<BatchDeleteTaskAttributeConfigsParams>,
 ) -> AppResult<StatusCode> {
     perm.require(Permission::AttributeConfigArchive)?;
     let ids: Vec<i64> = params.ids.into_iter().map(|id| id.0).collect();
-    TaskRepository::batch_delete_attribute_configs(&state.pool, ids).await?;
+    let before = TaskRepository::get_attribute_configs_by_ids(&state.pool, &ids).await?;
+    let mut tx = state.pool.begin().await?;
+    TaskRepository::batch_delete_attribute_configs(&mut tx, ids).await?;
+    record_history(&state, &mut tx, &before, &[], claims.sub).await?;
+    tx.commit().await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// 检查对一组任务的操作权限：拥有 all 权限直接通过；仅有 own 权限时，
+/// 当前用户必须是每个任务的负责人（创建者或被指派人）
+pub async fn require_task_permission(
+    pool: &PgPool,
+    perm: &ProjectPermission,
+    all: Permission,
+    own: Permission,
+    task_ids: &[i64],
+    action: &str,
+) -> AppResult<()> {
+    let owned = if !perm.has_permission(all) && perm.has_permission(own) {
+        TaskRepository::get_owned_task_ids(pool, perm.user_id, task_ids).await?
+    } else {
+        vec![]
+    };
+    for task_id in task_ids {
+        if !perm.can_operate(all, own, owned.contains(task_id)) {
+            return Err(AppError::Forbidden(format!(
+                "You don't have permission to {} task {}",
+                action, task_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 解析自定义属性过滤与排序；未传 filter / sort 时不查询属性配置
+async fn parse_task_list_query(
+    state: &AppState,
+    project_id: i64,
+    params: &TaskQueryParams,
+) -> AppResult<TaskListQuery> {
+    if params.filter.is_none() && params.sort.is_none() {
+        return Ok(TaskListQuery::default());
+    }
+    let configs = TaskRepository::get_attribute_configs_by_project(&state.pool, project_id).await?;
+    TaskListQuery::parse(params.filter.as_deref(), params.sort.as_deref(), &configs)
+}
+
 pub async fn get_task_list(
     State(state): State<AppState>,
     Extension(perm): Extension<ProjectPermission>,
@@ -157,7 +254,9 @@ pub async fn get_task_list(
     perm.require(Permission::TaskView)?;
     let page = params.page.unwrap_or(1);
     let per_page = params.per_page.unwrap_or(20);
-    let (tasks, total) = TaskRepository::get_task_list(&state.pool, project_id.0, params).await?;
+    let query = parse_task_list_query(&state, project_id.0, &params).await?;
+    let (tasks, total) =
+        TaskRepository::get_task_list(&state.pool, project_id.0, params, &query).await?;
 
     Ok(Json(PaginatedResponse::new(
         tasks,
@@ -175,10 +274,218 @@ pub async fn get_all_tasks(
     Query(params): Query<TaskQueryParams>,
 ) -> AppResult<Json<ApiResponse<Vec<Task>>>> {
     perm.require(Permission::TaskView)?;
-    let tasks = TaskRepository::get_all_tasks(&state.pool, project_id.0, params).await?;
+    let query = parse_task_list_query(&state, project_id.0, &params).await?;
+    let tasks = TaskRepository::get_all_tasks(&state.pool, project_id.0, params, &query).await?;
     Ok(Json(ApiResponse::success(tasks)))
 }
 
+pub async fn get_task_tree(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Query(params): Query<TaskTreeQuery>,
+) -> AppResult<Json<ApiResponse<Vec<TaskTreeNode>>>> {
+    perm.require(Permission::TaskView)?;
+    let root_id = params.root_id.map(|id| id.0);
+    let tasks = TaskRepository::get_task_tree(&state.pool, project_id.0, root_id).await?;
+    if root_id.is_some() && tasks.is_empty() {
+        return Err(AppError::NotFound("Task not found".to_string()));
+    }
+
+    let numeric_attributes: Vec<String> =
+        TaskRepository::get_attribute_configs_by_project(&state.pool, project_id.0)
+            .await?
+            .into_iter()
+            .filter(|c| !c.is_archived && c.attribute_type == "number")
+            .map(|c| c.attribute_name)
+            .collect();
+
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+
+    Ok(Json(ApiResponse::success(build_task_tree(
+        tasks,
+        &numeric_attributes,
+        &calendar,
+    )?)))
+}
+
+/// 任务及其父任务的 id，作为 [`sync_parent_dates`] 的起点
+pub fn with_parent_ids<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> Vec<i64> {
+    tasks
+        .into_iter()
+        .flat_map(|t| std::iter::once(t.id.0).chain(t.parent_id.map(|p| p.0)))
+        .collect()
+}
+
+/// 项目开启 sync_parent_dates 时，将 `task_ids` 及其祖先任务的起止时间同步为子任务的最早开始 / 最晚结束
+///
+/// `task_ids` 应包含变更的任务及其变更前后的父任务（见 [`with_parent_ids`]）；`conn` 为执行变更的事务，
+/// 祖先任务在其中加锁，避免并发写入交错。返回被更新的父任务。
+pub async fn sync_parent_dates(
+    state: &AppState,
+    conn: &mut PgConnection,
+    project_id: i64,
+    task_ids: &[i64],
+    updater_id: i64,
+) -> AppResult<Vec<Task>> {
+    let enabled = ProjectRepository::is_sync_parent_dates_enabled(conn, project_id).await?;
+    if !enabled || task_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let ancestor_ids = TaskRepository::lock_task_ancestors(conn, project_id, task_ids).await?;
+    if ancestor_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    // 祖先任务的子树之外的父任务不受影响；仅需起止时间汇总，工作日历不影响结果
+    let tasks = TaskRepository::get_subtrees(conn, project_id, &ancestor_ids).await?;
+    let tree = build_task_tree(tasks.clone(), &[], &WorkingCalendar::default())?;
+    let changes = parent_date_changes(&tree)
+        .into_iter()
+        .filter(|(id, _, _)| ancestor_ids.contains(id))
+        .collect();
+    let updated = TaskRepository::batch_update_task_dates(conn, changes, updater_id).await?;
+    let before: Vec<Task> = tasks
+        .into_iter()
+        .filter(|t| updated.iter().any(|u| u.id == t.id))
+        .collect();
+    record_history(state, conn, &before, &updated, updater_id).await?;
+    Ok(updated)
+}
+
+pub async fn get_critical_path(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+) -> AppResult<Json<ApiResponse<CriticalPathResponse>>> {
+    perm.require(Permission::TaskView)?;
+    let tasks = TaskRepository::get_all_tasks(
+        &state.pool,
+        project_id.0,
+        TaskQueryParams::default(),
+        &TaskListQuery::default(),
+    )
+    .await?;
+    let dependencies =
+        TaskDependencyRepository::get_dependencies_by_project(&state.pool, project_id.0, None)
+            .await?;
+    let result = compute_critical_path(&tasks, &dependencies)?;
+    Ok(Json(ApiResponse::success(result)))
+}
+
+pub async fn get_earned_value(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Query(query): Query<EarnedValueQuery>,
+) -> AppResult<Json<ApiResponse<EarnedValueResponse>>> {
+    perm.require(Permission::TaskView)?;
+    let baseline = match query.baseline_id {
+        Some(baseline_id) => {
+            get_project_baseline(&state.pool, project_id.0, baseline_id).await?;
+            Some(BaselineRepository::get_baseline_tasks(&state.pool, baseline_id.0).await?)
+        }
+        None => None,
+    };
+    let tasks = TaskRepository::get_all_tasks(
+        &state.pool,
+        project_id.0,
+        TaskQueryParams::default(),
+        &TaskListQuery::default(),
+    )
+    .await?;
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+    let status_date = query
+        .status_date
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+
+    let mut result = compute_earned_value(&tasks, baseline.as_deref(), &calendar, status_date)?;
+    result.baseline_id = query.baseline_id;
+    Ok(Json(ApiResponse::success(result)))
+}
+
+/// 按依赖关系与工作日历计算排期变更（不落库）
+fn compute_schedule(
+    tasks: &[Task],
+    dependencies: &[TaskDependency],
+    calendar: &WorkingCalendar,
+    params: &ScheduleTaskParams,
+) -> AppResult<Vec<ScheduleChange>> {
+    reschedule(
+        tasks,
+        dependencies,
+        calendar,
+        params.task_id.0,
+        params.start_date_time,
+        params.duration_days,
+    )
+}
+
+pub async fn preview_schedule(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<ScheduleTaskParams>,
+) -> AppResult<Json<ApiResponse<Vec<ScheduleChange>>>> {
+    perm.require(Permission::TaskView)?;
+    let tasks = TaskRepository::get_all_tasks(
+        &state.pool,
+        project_id.0,
+        TaskQueryParams::default(),
+        &TaskListQuery::default(),
+    )
+    .await?;
+    let dependencies =
+        TaskDependencyRepository::get_dependencies_by_project(&state.pool, project_id.0, None)
+            .await?;
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+    let changes = compute_schedule(&tasks, &dependencies, &calendar, &params)?;
+    Ok(Json(ApiResponse::success(changes)))
+}
+
+pub async fn apply_schedule(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<ScheduleTaskParams>,
+) -> AppResult<Json<ApiResponse<Vec<ScheduleChange>>>> {
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+    // 在事务内锁定任务与依赖后再计算，避免覆盖计算与写入之间的并发修改
+    let mut tx = state.pool.begin().await?;
+    let tasks = TaskRepository::lock_tasks(&mut tx, project_id.0, None).await?;
+    let dependencies =
+        TaskDependencyRepository::lock_dependencies_by_project(&mut tx, project_id.0).await?;
+    let changes = compute_schedule(&tasks, &dependencies, &calendar, &params)?;
+
+    // 每个被调整的任务都需要编辑权限：edit_all 或 edit_own
+    let task_ids: Vec<i64> = changes.iter().map(|c| c.task_id.0).collect();
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &task_ids,
+        "edit",
+    )
+    .await?;
+
+    let dates = changes
+        .iter()
+        .map(|c| (c.task_id.0, c.new_start_date_time, c.new_end_date_time))
+        .collect();
+    let before: Vec<Task> = tasks
+        .into_iter()
+        .filter(|t| task_ids.contains(&t.id.0))
+        .collect();
+    let updated = TaskRepository::batch_update_task_dates(&mut tx, dates, claims.sub).await?;
+    record_history(&state, &mut tx, &before, &updated, claims.sub).await?;
+    sync_parent_dates(&state, &mut tx, project_id.0, &with_parent_ids(&updated), claims.sub)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(ApiResponse::success(changes)))
+}
+
 pub async fn get_task_by_id(
     State(state): State<AppState>,
     Extension(perm): Extension<ProjectPermission>,
@@ -192,21 +499,137 @@ pub async fn get_task_by_id(
     Ok(Json(ApiResponse::success(task)))
 }
 
+/// 校验完成百分比在 0-100 之间
+fn check_percent_complete(value: Option<f64>, field: &str) -> AppResult<()> {
+    match value {
+        Some(v) if !(0.0..=100.0).contains(&v) => Err(AppError::ValidationError(
+            "Invalid percent complete".to_string(),
+            vec![field_error(
+                field,
+                "out_of_range",
+                "Percent complete must be between 0 and 100".to_string(),
+            )],
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// 校验任务类型取值，不允许新建或更新为 Unknown
+fn check_task_type(task_type: i32, field: &str) -> AppResult<()> {
+    match TaskType::from_i32(task_type) {
+        Some(TaskType::Unknown) | None => Err(AppError::ValidationError(
+            "Invalid task type".to_string(),
+            vec![field_error(
+                field,
+                "invalid_value",
+                format!(
+                    "Task type must be one of {} (default), {} (milestone), {} (checkpoint)",
+                    TaskType::Default.as_i32(),
+                    TaskType::Milestone.as_i32(),
+                    TaskType::Checkpoint.as_i32()
+                ),
+            )],
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// 里程碑与检查点为时间点：开始与结束时间必须相同
+fn check_zero_duration_dates(
+    task_type: i32,
+    start_date_time: chrono::NaiveDateTime,
+    end_date_time: chrono::NaiveDateTime,
+    field: &str,
+) -> AppResult<()> {
+    if TaskType::from_i32(task_type).is_some_and(TaskType::is_zero_duration)
+        && start_date_time != end_date_time
+    {
+        return Err(AppError::ValidationError(
+            "Invalid milestone or checkpoint".to_string(),
+            vec![field_error(
+                field,
+                "zero_duration",
+                "Milestones and checkpoints must have zero duration (end equals start)"
+                    .to_string(),
+            )],
+        ));
+    }
+    Ok(())
+}
+
+/// 未签核的检查点不可改为其他类型或将进度设为 100%，避免绕过完成前的签核；`conn` 为执行更新的事务
+async fn check_checkpoint_sign_off(
+    conn: &mut PgConnection,
+    task: &Task,
+    params: &UpdateTaskParams,
+) -> AppResult<()> {
+    let is_checkpoint = |t: i32| TaskType::from_i32(t) == Some(TaskType::Checkpoint);
+    let leaves_checkpoint =
+        is_checkpoint(task.task_type) && params.task_type.is_some_and(|t| !is_checkpoint(t));
+    let completes_checkpoint = is_checkpoint(params.task_type.unwrap_or(task.task_type))
+        && params.percent_complete.is_some_and(|p| p >= 100.0);
+    if !leaves_checkpoint && !completes_checkpoint {
+        return Ok(());
+    }
+    if CheckpointRepository::get_latest_decision(conn, task.id.0).await?
+        != Some(CheckpointDecision::Approved)
+    {
+        let action = if leaves_checkpoint {
+            "its type can be changed"
+        } else {
+            "it can be completed"
+        };
+        return Err(AppError::Conflict(format!(
+            "Checkpoint {} must be signed off before {}",
+            task.id, action
+        )));
+    }
+    Ok(())
+}
+
 pub async fn create_task(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Extension(perm): Extension<ProjectPermission>,
     Path(project_id): Path<Id>,
-    Json(params): Json<CreateTaskParams>,
+    Json(mut params): Json<CreateTaskParams>,
 ) -> AppResult<(StatusCode, Json<ApiResponse<Task>>)> {
     perm.require(Permission::TaskCreate)?;
+    check_percent_complete(params.percent_complete, "percentComplete")?;
+    check_task_type(params.task_type, "taskType")?;
+    check_zero_duration_dates(
+        params.task_type,
+        params.start_date_time,
+        params.end_date_time,
+        "endDateTime",
+    )?;
     let creator_id = claims.sub;
+
+    let configs = TaskRepository::get_attribute_configs_by_project(&state.pool, project_id.0).await?;
+    let mut errors = Vec::new();
+    params.custom_attributes = Some(validate_custom_attributes(
+        &configs,
+        params.custom_attributes.as_ref(),
+        "customAttributes",
+        &mut errors,
+    ));
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            "Invalid custom attributes".to_string(),
+            errors,
+        ));
+    }
     let task_id = state
         .generate_id()
         .map_err(|e| AppError::InternalError(format!("Failed to generate task ID: {}", e)))?;
 
+    let mut tx = state.pool.begin().await?;
     let task =
-        TaskRepository::create_task(&state.pool, task_id, project_id.0, params, creator_id).await?;
+        TaskRepository::create_task(&mut tx, task_id, project_id.0, params, creator_id).await?;
+    record_history(&state, &mut tx, &[], std::slice::from_ref(&task), creator_id).await?;
+    sync_parent_dates(&state, &mut tx, project_id.0, &with_parent_ids([&task]), creator_id)
+        .await?;
+    tx.commit().await?;
 
     Ok((StatusCode::CREATED, Json(ApiResponse::success(task))))
 }
@@ -227,21 +650,50 @@ pub async fn batch_create_tasks(
         ));
     }
 
-    let mut tasks_with_ids = Vec::with_capacity(params.tasks.len());
-    for task_param in params.tasks {
+    let configs = TaskRepository::get_attribute_configs_by_project(&state.pool, project_id.0).await?;
+    let mut errors = Vec::new();
+    let mut tasks = params.tasks;
+    for (i, task_param) in tasks.iter_mut().enumerate() {
+        check_percent_complete(
+            task_param.percent_complete,
+            &format!("tasks[{}].percentComplete", i),
+        )?;
+        check_task_type(task_param.task_type, &format!("tasks[{}].taskType", i))?;
+        check_zero_duration_dates(
+            task_param.task_type,
+            task_param.start_date_time,
+            task_param.end_date_time,
+            &format!("tasks[{}].endDateTime", i),
+        )?;
+        task_param.custom_attributes = Some(validate_custom_attributes(
+            &configs,
+            task_param.custom_attributes.as_ref(),
+            &format!("tasks[{}].customAttributes", i),
+            &mut errors,
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            "Invalid custom attributes".to_string(),
+            errors,
+        ));
+    }
+
+    let mut tasks_with_ids = Vec::with_capacity(tasks.len());
+    for task_param in tasks {
         let task_id = state
             .generate_id()
             .map_err(|e| AppError::InternalError(format!("Failed to generate task ID: {}", e)))?;
         tasks_with_ids.push((task_id, task_param));
     }
 
-    let tasks = TaskRepository::batch_create_tasks(
-        &state.pool,
-        tasks_with_ids,
-        project_id.0,
-        creator_id,
-    )
-    .await?;
+    let mut tx = state.pool.begin().await?;
+    let tasks =
+        TaskRepository::insert_tasks(&mut tx, tasks_with_ids, project_id.0, creator_id).await?;
+    record_history(&state, &mut tx, &[], &tasks, creator_id).await?;
+    sync_parent_dates(&state, &mut tx, project_id.0, &with_parent_ids(&tasks), creator_id)
+        .await?;
+    tx.commit().await?;
 
     Ok((StatusCode::CREATED, Json(ApiResponse::success(tasks))))
 }
@@ -250,27 +702,280 @@ pub async fn update_task(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Extension(perm): Extension<ProjectPermission>,
-    Path((_project_id, task_id)): Path<(Id, Id)>,
-    Json(params): Json<UpdateTaskParams>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(mut params): Json<UpdateTaskParams>,
 ) -> AppResult<Json<ApiResponse<Task>>> {
-    // 检查编辑权限：edit_all �?edit_own（需查询任务创建者）
-    let task = TaskRepository::get_task_by_id(&state.pool, task_id.0)
+    // 在事务内锁定并读取任务，校验与变更前快照均基于同一版本
+    let mut tx = state.pool.begin().await?;
+    let task = TaskRepository::lock_tasks(&mut tx, project_id.0, Some(&[task_id.0]))
         .await?
+        .pop()
         .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
-    if !perm.can_operate(Permission::TaskEditAll, Permission::TaskEditOwn, task.creator_id.0) {
-        return Err(AppError::Forbidden(
-            "You don't have permission to edit this task".to_string(),
-        ));
+    // 检查编辑权限：edit_all �?edit_own（需查询任务创建者）
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[task.id.0],
+        "edit",
+    )
+    .await?;
+    check_percent_complete(params.percent_complete, "percentComplete")?;
+    if let Some(task_type) = params.task_type {
+        check_task_type(task_type, "taskType")?;
     }
+    // 仅在类型或日期变化时校验，避免仅改名等操作被既有数据阻断
+    if params.task_type.is_some()
+        || params.start_date_time.is_some()
+        || params.end_date_time.is_some()
+    {
+        check_zero_duration_dates(
+            params.task_type.unwrap_or(task.task_type),
+            params.start_date_time.unwrap_or(task.start_date_time),
+            params.end_date_time.unwrap_or(task.end_date_time),
+            "endDateTime",
+        )?;
+    }
+    check_checkpoint_sign_off(&mut tx, &task, &params).await?;
     let updater_id = claims.sub;
 
-    let task = TaskRepository::update_task(&state.pool, task_id.0, params, updater_id).await?;
+    if let Some(Some(parent_id)) = params.parent_id {
+        let tasks = TaskRepository::get_all_tasks(
+            &state.pool,
+            task.project_id.0,
+            TaskQueryParams::default(),
+            &TaskListQuery::default(),
+        )
+        .await?;
+        if !tasks.iter().any(|t| t.id == parent_id) {
+            return Err(AppError::BadRequest(format!(
+                "Parent task {} does not exist in this project",
+                parent_id
+            )));
+        }
+        if would_create_cycle(&tasks, task.id.0, parent_id.0) {
+            return Err(AppError::BadRequest(
+                "Cannot move a task under itself or its descendants".to_string(),
+            ));
+        }
+    }
+
+    // 仅在提交了 custom_attributes 时校验
+    if let Some(custom_attributes) = params.custom_attributes.take() {
+        let configs =
+            TaskRepository::get_attribute_configs_by_project(&state.pool, task.project_id.0).await?;
+        let mut errors = Vec::new();
+        let normalized = validate_custom_attributes(
+            &configs,
+            Some(&custom_attributes),
+            "customAttributes",
+            &mut errors,
+        );
+        if !errors.is_empty() {
+            return Err(AppError::ValidationError(
+                "Invalid custom attributes".to_string(),
+                errors,
+            ));
+        }
+        params.custom_attributes = Some(normalized);
+    }
+
+    let before = task;
+    let task = TaskRepository::update_task(&mut tx, task_id.0, params, updater_id).await?;
+    record_history(
+        &state,
+        &mut tx,
+        std::slice::from_ref(&before),
+        std::slice::from_ref(&task),
+        updater_id,
+    )
+    .await?;
+    // 若被更新的任务本身是父任务，返回同步后的版本
+    let task = sync_parent_dates(
+        &state,
+        &mut tx,
+        task.project_id.0,
+        &with_parent_ids([&before, &task]),
+        updater_id,
+    )
+    .await?
+    .into_iter()
+    .find(|t| t.id == task.id)
+    .unwrap_or(task);
+    tx.commit().await?;
+
+    Ok(Json(ApiResponse::success(task)))
+}
+
+pub async fn batch_update_tasks(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<BatchUpdateTasksParams>,
+) -> AppResult<Json<ApiResponse<Vec<Task>>>> {
+    perm.require(Permission::TaskBatchOperate)?;
+    if params.tasks.is_empty() {
+        return Err(AppError::BadRequest(
+            "Tasks list cannot be empty".to_string(),
+        ));
+    }
+
+    let configs = TaskRepository::get_attribute_configs_by_project(&state.pool, project_id.0).await?;
+    // 在事务内锁定并读取任务：校验、环检查与变更前快照均基于同一版本
+    let mut tx = state.pool.begin().await?;
+    let mut tasks = TaskRepository::lock_tasks(&mut tx, project_id.0, None).await?;
+    let original = tasks.clone();
+
+    let mut seen = std::collections::HashSet::new();
+    let mut errors = Vec::new();
+    let mut updates = Vec::with_capacity(params.tasks.len());
+    for (i, mut item) in params.tasks.into_iter().enumerate() {
+        if !seen.insert(item.id) {
+            return Err(AppError::BadRequest(format!(
+                "Task {} appears more than once",
+                item.id
+            )));
+        }
+        let Some(task) = tasks.iter_mut().find(|t| t.id == item.id) else {
+            return Err(AppError::NotFound(format!("Task not found: {}", item.id)));
+        };
+        check_percent_complete(
+            item.params.percent_complete,
+            &format!("tasks[{}].percentComplete", i),
+        )?;
+        if let Some(task_type) = item.params.task_type {
+            check_task_type(task_type, &format!("tasks[{}].taskType", i))?;
+        }
+        if item.params.task_type.is_some()
+            || item.params.start_date_time.is_some()
+            || item.params.end_date_time.is_some()
+        {
+            check_zero_duration_dates(
+                item.params.task_type.unwrap_or(task.task_type),
+                item.params.start_date_time.unwrap_or(task.start_date_time),
+                item.params.end_date_time.unwrap_or(task.end_date_time),
+                &format!("tasks[{}].endDateTime", i),
+            )?;
+        }
+        check_checkpoint_sign_off(&mut tx, task, &item.params).await?;
+        if let Some(custom_attributes) = item.params.custom_attributes.take() {
+            item.params.custom_attributes = Some(validate_custom_attributes(
+                &configs,
+                Some(&custom_attributes),
+                &format!("tasks[{}].customAttributes", i),
+                &mut errors,
+            ));
+        }
+        // 先应用所有父任务变更，再统一检查环
+        if let Some(parent_id) = item.params.parent_id {
+            task.parent_id = parent_id;
+        }
+        updates.push((item.id.0, item.params));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            "Invalid custom attributes".to_string(),
+            errors,
+        ));
+    }
+    for (task_id, update) in &updates {
+        let Some(Some(parent_id)) = update.parent_id else {
+            continue;
+        };
+        if !tasks.iter().any(|t| t.id == parent_id) {
+            return Err(AppError::BadRequest(format!(
+                "Parent task {} does not exist in this project",
+                parent_id
+            )));
+        }
+        if would_create_cycle(&tasks, *task_id, parent_id.0) {
+            return Err(AppError::BadRequest(format!(
+                "Task {} cannot be moved under itself or its descendants",
+                task_id
+            )));
+        }
+    }
+
+    let mut updated = TaskRepository::batch_update_tasks(&mut tx, updates, claims.sub).await?;
+    let before: Vec<Task> = original
+        .into_iter()
+        .filter(|t| seen.contains(&t.id))
+        .collect();
+    record_history(&state, &mut tx, &before, &updated, claims.sub).await?;
+    let task_ids = with_parent_ids(before.iter().chain(&updated));
+    for synced in sync_parent_dates(&state, &mut tx, project_id.0, &task_ids, claims.sub).await? {
+        if let Some(task) = updated.iter_mut().find(|t| t.id == synced.id) {
+            *task = synced;
+        }
+    }
+    tx.commit().await?;
+
+    Ok(Json(ApiResponse::success(updated)))
+}
+
+pub async fn move_task(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<MoveTaskParams>,
+) -> AppResult<Json<ApiResponse<Task>>> {
+    let tasks = TaskRepository::get_all_tasks(
+        &state.pool,
+        project_id.0,
+        TaskQueryParams::default(),
+        &TaskListQuery::default(),
+    )
+    .await?;
+    let task = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[task.id.0],
+        "edit",
+    )
+    .await?;
+
+    let plan = plan_move(
+        &tasks,
+        task_id.0,
+        params.parent_id.map(|id| id.0),
+        params.before_id.map(|id| id.0),
+        params.after_id.map(|id| id.0),
+    )?;
+    let mut tx = state.pool.begin().await?;
+    // 重新编号时兄弟任务的 order 也会变化
+    let after =
+        TaskRepository::move_task(&mut tx, task_id.0, plan.parent_id, plan.orders, claims.sub)
+            .await?;
+    let before: Vec<Task> = tasks
+        .iter()
+        .filter(|t| after.iter().any(|a| a.id == t.id))
+        .cloned()
+        .collect();
+    record_history(&state, &mut tx, &before, &after, claims.sub).await?;
+    let task_ids = with_parent_ids(before.iter().chain(&after));
+    let synced = sync_parent_dates(&state, &mut tx, project_id.0, &task_ids, claims.sub).await?;
+    tx.commit().await?;
+    let task = synced
+        .into_iter()
+        .chain(after)
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
 
     Ok(Json(ApiResponse::success(task)))
 }
 
 pub async fn delete_task(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Extension(perm): Extension<ProjectPermission>,
     Path((_project_id, task_id)): Path<(Id, Id)>,
 ) -> AppResult<StatusCode> {
@@ -278,23 +983,50 @@ pub async fn delete_task(
     let task = TaskRepository::get_task_by_id(&state.pool, task_id.0)
         .await?
         .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
-    if !perm.can_operate(Permission::TaskDeleteAll, Permission::TaskDeleteOwn, task.creator_id.0) {
-        return Err(AppError::Forbidden(
-            "You don't have permission to delete this task".to_string(),
-        ));
-    }
-    TaskRepository::delete_task(&state.pool, task_id.0).await?;
+    // 删除会级联删除子任务：仅有 delete_own 权限时，整棵子树都必须由当前用户负责
+    let subtree_ids: Vec<i64> = if perm.has_permission(Permission::TaskDeleteAll) {
+        vec![task.id.0]
+    } else {
+        TaskRepository::get_task_tree(&state.pool, task.project_id.0, Some(task.id.0))
+            .await?
+            .into_iter()
+            .map(|t| t.id.0)
+            .collect()
+    };
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskDeleteAll,
+        Permission::TaskDeleteOwn,
+        &subtree_ids,
+        "delete",
+    )
+    .await?;
+    let mut tx = state.pool.begin().await?;
+    let deleted = TaskRepository::delete_task(&mut tx, task_id.0).await?;
+    record_history(&state, &mut tx, &deleted, &[], claims.sub).await?;
+    let task_ids = with_parent_ids(&deleted);
+    sync_parent_dates(&state, &mut tx, task.project_id.0, &task_ids, claims.sub).await?;
+    tx.commit().await?;
+    cleanup_task_files(&state, &deleted).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn batch_delete_tasks(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Extension(perm): Extension<ProjectPermission>,
-    Path(_project_id): Path<Id>,
+    Path(project_id): Path<Id>,
     Json(params): Json<BatchDeleteTasksParams>,
 ) -> AppResult<StatusCode> {
     perm.require(Permission::TaskBatchOperate)?;
     let ids: Vec<i64> = params.ids.into_iter().map(|id| id.0).collect();
-    TaskRepository::batch_delete_tasks(&state.pool, ids).await?;
+    let mut tx = state.pool.begin().await?;
+    let deleted = TaskRepository::batch_delete_tasks(&mut tx, ids).await?;
+    record_history(&state, &mut tx, &deleted, &[], claims.sub).await?;
+    let task_ids = with_parent_ids(&deleted);
+    sync_parent_dates(&state, &mut tx, project_id.0, &task_ids, claims.sub).await?;
+    tx.commit().await?;
+    cleanup_task_files(&state, &deleted).await;
     Ok(StatusCode::NO_CONTENT)
 }
@@ -0,0 +1,157 @@
+use crate::modules::business::project::task::history::models::{
+    Audited, FieldChange, HistoryAction, NewTaskHistory,
+};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// 不记录的字段：标识与审计元数据（操作人与时间由历史记录本身保存）
+const IGNORED_FIELDS: &[&str] = &[
+    "id",
+    "projectId",
+    "creatorId",
+    "updaterId",
+    "createDateTime",
+    "updateDateTime",
+];
+
+/// 按键逐项比较的 JSON 对象字段
+const EXPANDED_FIELDS: &[&str] = &["customAttributes"];
+
+/// 将实体的序列化结果展开为 字段 -> 值；customAttributes 展开为 customAttributes.<key>
+fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    let Some(object) = value.as_object() else {
+        return fields;
+    };
+    for (key, value) in object {
+        if IGNORED_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        match value.as_object() {
+            Some(nested) if EXPANDED_FIELDS.contains(&key.as_str()) => {
+                for (nested_key, nested_value) in nested {
+                    fields.insert(format!("{}.{}", key, nested_key), nested_value.clone());
+                }
+            }
+            _ => {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    fields
+}
+
+/// 比较两个序列化后的实体，返回发生变化的字段（缺失视为 null）
+pub fn diff_fields(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let before = flatten(before);
+    let after = flatten(after);
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let old = before.get(key).cloned().unwrap_or(Value::Null);
+            let new = after.get(key).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| FieldChange {
+                field: key.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
+
+/// 对比操作前后的实体列表生成变更记录：仅在 after 中为新增，仅在 before 中为删除，
+/// 两者都有且字段不同为更新
+pub fn diff_entities<T: Audited>(before: &[T], after: &[T]) -> Vec<NewTaskHistory> {
+    let to_value = |entity: &T| serde_json::to_value(entity).unwrap_or(Value::Null);
+    let old: HashMap<i64, &T> = before.iter().map(|e| (e.audit_id(), e)).collect();
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    for entity in after {
+        if !seen.insert(entity.audit_id()) {
+            continue;
+        }
+        let (action, changes) = match old.get(&entity.audit_id()) {
+            Some(previous) => (
+                HistoryAction::Update,
+                diff_fields(&to_value(previous), &to_value(entity)),
+            ),
+            None => (
+                HistoryAction::Create,
+                diff_fields(&Value::Null, &to_value(entity)),
+            ),
+        };
+        if action == HistoryAction::Update && changes.is_empty() {
+            continue;
+        }
+        entries.push(NewTaskHistory {
+            project_id: entity.audit_project_id(),
+            entity_type: T::ENTITY_TYPE,
+            entity_id: entity.audit_id(),
+            action,
+            changes,
+        });
+    }
+
+    for entity in before {
+        if !seen.insert(entity.audit_id()) {
+            continue;
+        }
+        entries.push(NewTaskHistory {
+            project_id: entity.audit_project_id(),
+            entity_type: T::ENTITY_TYPE,
+            entity_id: entity.audit_id(),
+            action: HistoryAction::Delete,
+            changes: diff_fields(&to_value(entity), &Value::Null),
+        });
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_expands_custom_attributes() {
+        let before = json!({
+            "id": "1",
+            "taskName": "Design",
+            "startDateTime": "2026-01-05T09:00:00",
+            "customAttributes": {"owner": "alice", "points": 3},
+            "updateDateTime": null,
+        });
+        let after = json!({
+            "id": "1",
+            "taskName": "Design",
+            "startDateTime": "2026-01-06T09:00:00",
+            "customAttributes": {"owner": "bob", "priority": "high"},
+            "updateDateTime": "2026-01-02T10:00:00",
+        });
+
+        let change = |field: &str, before: Value, after: Value| FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        };
+        assert_eq!(
+            diff_fields(&before, &after),
+            vec![
+                change("customAttributes.owner", json!("alice"), json!("bob")),
+                change("customAttributes.points", json!(3), Value::Null),
+                change("customAttributes.priority", Value::Null, json!("high")),
+                change(
+                    "startDateTime",
+                    json!("2026-01-05T09:00:00"),
+                    json!("2026-01-06T09:00:00")
+                ),
+            ]
+        );
+
+        let created = diff_fields(&Value::Null, &after);
+        assert!(created.iter().all(|c| c.before.is_null()));
+        assert!(!created.iter().any(|c| c.field == "id" || c.field == "updateDateTime"));
+    }
+}
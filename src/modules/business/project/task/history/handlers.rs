@@ -0,0 +1,108 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::response::PaginatedResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::history::diff::diff_entities;
+use crate::modules::business::project::task::history::models::{
+    Audited, HistoryEntityType, TaskHistory, TaskHistoryQueryParams,
+};
+use crate::modules::business::project::task::history::repository::TaskHistoryRepository;
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use sqlx::PgConnection;
+
+/// 对比操作前后的实体并写入变更历史；conn 应为执行变更的事务，使变更与记录一同提交
+pub async fn record_history<T: Audited>(
+    state: &AppState,
+    conn: &mut PgConnection,
+    before: &[T],
+    after: &[T],
+    actor_id: i64,
+) -> AppResult<()> {
+    let entries = diff_entities(before, after)
+        .into_iter()
+        .map(|entry| {
+            let id = state.generate_id().map_err(|e| {
+                AppError::InternalError(format!("Failed to generate task history ID: {}", e))
+            })?;
+            Ok((id, entry))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    TaskHistoryRepository::create_histories(conn, entries, actor_id).await
+}
+
+async fn get_histories(
+    state: &AppState,
+    project_id: Id,
+    entity_type: HistoryEntityType,
+    entity_id: Id,
+    params: TaskHistoryQueryParams,
+    base_path: &str,
+) -> AppResult<Json<PaginatedResponse<TaskHistory>>> {
+    let page = params.page.unwrap_or(1);
+    let per_page = params.per_page.unwrap_or(20);
+    let (histories, total) = TaskHistoryRepository::get_histories(
+        &state.pool,
+        project_id.0,
+        entity_type,
+        entity_id.0,
+        page,
+        per_page,
+    )
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        histories,
+        total,
+        page,
+        per_page,
+        base_path,
+    )))
+}
+
+pub async fn get_task_history(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Query(params): Query<TaskHistoryQueryParams>,
+) -> AppResult<Json<PaginatedResponse<TaskHistory>>> {
+    perm.require(Permission::TaskView)?;
+    let base_path = format!(
+        "/api/v1/projects/{}/tasks/{}/history",
+        project_id, task_id
+    );
+    get_histories(
+        &state,
+        project_id,
+        HistoryEntityType::Task,
+        task_id,
+        params,
+        &base_path,
+    )
+    .await
+}
+
+pub async fn get_attribute_config_history(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, config_id)): Path<(Id, Id)>,
+    Query(params): Query<TaskHistoryQueryParams>,
+) -> AppResult<Json<PaginatedResponse<TaskHistory>>> {
+    perm.require(Permission::AttributeConfigView)?;
+    let base_path = format!(
+        "/api/v1/projects/{}/task-attribute-configs/{}/history",
+        project_id, config_id
+    );
+    get_histories(
+        &state,
+        project_id,
+        HistoryEntityType::AttributeConfig,
+        config_id,
+        params,
+        &base_path,
+    )
+    .await
+}
@@ -0,0 +1,114 @@
+use crate::common::id::Id;
+use crate::modules::business::project::task::models::{Task, TaskAttributeConfig};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryEntityType {
+    Task,
+    AttributeConfig,
+}
+
+impl HistoryEntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryEntityType::Task => "task",
+            HistoryEntityType::AttributeConfig => "attribute_config",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl HistoryAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryAction::Create => "create",
+            HistoryAction::Update => "update",
+            HistoryAction::Delete => "delete",
+        }
+    }
+}
+
+/// 单个字段的变更，新增时 before 为 null，删除时 after 为 null
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHistory {
+    pub id: Id,
+    pub project_id: Id,
+    /// task, attribute_config
+    pub entity_type: String,
+    pub entity_id: Id,
+    /// create, update, delete
+    pub action: String,
+    #[sqlx(json)]
+    pub changes: Vec<FieldChange>,
+    pub actor_id: Id,
+    pub create_date_time: chrono::NaiveDateTime,
+    // JOIN 字段
+    pub actor_username: Option<String>,
+    pub actor_full_name: Option<String>,
+}
+
+/// 待写入的变更记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTaskHistory {
+    pub project_id: i64,
+    pub entity_type: HistoryEntityType,
+    pub entity_id: i64,
+    pub action: HistoryAction,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHistoryQueryParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+/// 记录变更历史的实体
+pub trait Audited: Serialize {
+    const ENTITY_TYPE: HistoryEntityType;
+
+    fn audit_id(&self) -> i64;
+
+    fn audit_project_id(&self) -> i64;
+}
+
+impl Audited for Task {
+    const ENTITY_TYPE: HistoryEntityType = HistoryEntityType::Task;
+
+    fn audit_id(&self) -> i64 {
+        self.id.0
+    }
+
+    fn audit_project_id(&self) -> i64 {
+        self.project_id.0
+    }
+}
+
+impl Audited for TaskAttributeConfig {
+    const ENTITY_TYPE: HistoryEntityType = HistoryEntityType::AttributeConfig;
+
+    fn audit_id(&self) -> i64 {
+        self.id.0
+    }
+
+    fn audit_project_id(&self) -> i64 {
+        self.project_id.0
+    }
+}
@@ -0,0 +1,96 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::history::models::{
+    HistoryEntityType, NewTaskHistory, TaskHistory,
+};
+use sqlx::{PgConnection, PgPool};
+
+pub struct TaskHistoryRepository;
+
+impl TaskHistoryRepository {
+    /// 批量写入变更记录，entries 为 (id, 记录)
+    pub async fn create_histories(
+        conn: &mut PgConnection,
+        entries: Vec<(i64, NewTaskHistory)>,
+        actor_id: i64,
+    ) -> AppResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut ids = Vec::with_capacity(entries.len());
+        let mut project_ids = Vec::with_capacity(entries.len());
+        let mut entity_types = Vec::with_capacity(entries.len());
+        let mut entity_ids = Vec::with_capacity(entries.len());
+        let mut actions = Vec::with_capacity(entries.len());
+        let mut changes = Vec::with_capacity(entries.len());
+        for (id, entry) in entries {
+            ids.push(id);
+            project_ids.push(entry.project_id);
+            entity_types.push(entry.entity_type.as_str());
+            entity_ids.push(entry.entity_id);
+            actions.push(entry.action.as_str());
+            changes.push(serde_json::to_value(entry.changes).unwrap_or_default());
+        }
+
+        sqlx::query(
+            r#"INSERT INTO project_task_histories
+                   (id, project_id, entity_type, entity_id, action, changes, actor_id)
+               SELECT data.id, data.project_id, data.entity_type, data.entity_id,
+                      data.action, data.changes, $7
+               FROM unnest($1::bigint[], $2::bigint[], $3::varchar[], $4::bigint[],
+                           $5::varchar[], $6::jsonb[])
+                   AS data(id, project_id, entity_type, entity_id, action, changes)"#,
+        )
+        .bind(&ids)
+        .bind(&project_ids)
+        .bind(&entity_types)
+        .bind(&entity_ids)
+        .bind(&actions)
+        .bind(&changes)
+        .bind(actor_id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 查询实体的变更历史（按时间倒序），实体删除后仍可查询
+    pub async fn get_histories(
+        pool: &PgPool,
+        project_id: i64,
+        entity_type: HistoryEntityType,
+        entity_id: i64,
+        page: i64,
+        per_page: i64,
+    ) -> AppResult<(Vec<TaskHistory>, i64)> {
+        let offset = (page - 1) * per_page;
+        let histories = sqlx::query_as::<_, TaskHistory>(
+            r#"SELECT h.id, h.project_id, h.entity_type, h.entity_id, h.action, h.changes,
+                   h.actor_id, h.create_date_time,
+                   u.username AS actor_username, u.full_name AS actor_full_name
+               FROM project_task_histories h
+               LEFT JOIN users u ON u.id = h.actor_id
+               WHERE h.project_id = $1 AND h.entity_type = $2 AND h.entity_id = $3
+               ORDER BY h.create_date_time DESC, h.id DESC
+               LIMIT $4 OFFSET $5"#,
+        )
+        .bind(project_id)
+        .bind(entity_type.as_str())
+        .bind(entity_id)
+        .bind(per_page)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        let total: (i64,) = sqlx::query_as(
+            r#"SELECT COUNT(*) FROM project_task_histories
+               WHERE project_id = $1 AND entity_type = $2 AND entity_id = $3"#,
+        )
+        .bind(project_id)
+        .bind(entity_type.as_str())
+        .bind(entity_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok((histories, total.0))
+    }
+}
@@ -0,0 +1,28 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::history::handlers;
+use axum::{middleware, routing::get, Router};
+
+pub fn task_history_routes(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/history",
+            get(handlers::get_task_history),
+        )
+        .route(
+            "/projects/{project_id}/task-attribute-configs/{config_id}/history",
+            get(handlers::get_attribute_config_history),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
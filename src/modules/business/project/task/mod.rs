@@ -1,6 +1,22 @@
+pub mod assignee;
+pub mod attachment;
+pub mod attributes;
+pub mod baseline;
+pub mod checkpoint;
+pub mod comment;
+pub mod critical_path;
+pub mod dependency;
+pub mod earned_value;
 pub mod handlers;
+pub mod history;
 pub mod models;
+pub mod ordering;
+pub mod query;
+pub mod recurrence;
 pub mod repository;
 pub mod routes;
+pub mod scheduler;
+pub mod tree;
+pub mod worklog;
 
 pub use routes::*;
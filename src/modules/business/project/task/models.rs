@@ -2,9 +2,8 @@ use crate::common::id::Id;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
-#[allow(unused)]
 pub enum AttributeType {
     Text,
     Number,
@@ -12,6 +11,7 @@ pub enum AttributeType {
     Date,
     DateTime,
     Select,
+    User,
 }
 
 impl AttributeType {
@@ -24,10 +24,10 @@ impl AttributeType {
             AttributeType::Date => "date",
             AttributeType::DateTime => "datetime",
             AttributeType::Select => "select",
+            AttributeType::User => "user",
         }
     }
 
-    #[allow(unused)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "text" => Some(AttributeType::Text),
@@ -36,6 +36,7 @@ impl AttributeType {
             "date" => Some(AttributeType::Date),
             "datetime" => Some(AttributeType::DateTime),
             "select" => Some(AttributeType::Select),
+            "user" => Some(AttributeType::User),
             _ => None,
         }
     }
@@ -43,26 +44,35 @@ impl AttributeType {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
-#[allow(unused)]
 pub enum TaskType {
+    /// 历史数据遗留，不允许新建或更新为该类型
     Unknown = 0,
     Default = 1,
+    /// 里程碑：时间点，开始与结束时间相同
     Milestone = 2,
+    /// 检查点：需经审批签核后才能完成
     Checkpoint = 3,
 }
 
 impl TaskType {
-    #[allow(unused)]
-    pub fn from_i32(v: i32) -> Self {
+    pub fn from_i32(v: i32) -> Option<Self> {
         match v {
-            _ => TaskType::Unknown,
+            0 => Some(TaskType::Unknown),
+            1 => Some(TaskType::Default),
+            2 => Some(TaskType::Milestone),
+            3 => Some(TaskType::Checkpoint),
+            _ => None,
         }
     }
 
-    #[allow(unused)]
     pub fn as_i32(self) -> i32 {
         self as i32
     }
+
+    /// 里程碑与检查点为时间点，不占工期
+    pub fn is_zero_duration(self) -> bool {
+        matches!(self, TaskType::Milestone | TaskType::Checkpoint)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -87,6 +97,30 @@ pub struct TaskAttributeConfig {
     pub update_date_time: Option<chrono::NaiveDateTime>,
 }
 
+#[cfg(test)]
+impl TaskAttributeConfig {
+    /// 测试用属性配置：项目 1 下的非必填属性，其余字段按需覆盖
+    pub fn test(name: &str, attribute_type: &str) -> Self {
+        TaskAttributeConfig {
+            id: Id(1),
+            project_id: Id(1),
+            attribute_name: name.to_string(),
+            attribute_label: name.to_string(),
+            attribute_type: attribute_type.to_string(),
+            is_required: false,
+            default_value: None,
+            options: None,
+            value_color_map: None,
+            order: None,
+            is_archived: false,
+            creator_id: Id(1),
+            updater_id: None,
+            create_date_time: chrono::NaiveDateTime::default(),
+            update_date_time: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
@@ -100,12 +134,44 @@ pub struct Task {
     pub start_date_time: chrono::NaiveDateTime,
     pub end_date_time: chrono::NaiveDateTime,
     pub task_type: i32,
+    /// 完成百分比（0-100）
+    pub percent_complete: f64,
+    /// 工作流状态，NULL 表示尚未进入工作流（视为初始状态）
+    pub status_id: Option<Id>,
     pub creator_id: Id,
     pub updater_id: Option<Id>,
     pub create_date_time: chrono::NaiveDateTime,
     pub update_date_time: Option<chrono::NaiveDateTime>,
 }
 
+#[cfg(test)]
+impl Task {
+    /// 测试用任务：项目 1 下的普通任务，起止时间均为 2026-01-01 00:00，其余字段按需覆盖
+    pub fn test(id: i64) -> Self {
+        let time = chrono::NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        Task {
+            id: Id(id),
+            task_name: format!("task-{}", id),
+            parent_id: None,
+            project_id: Id(1),
+            order: id as f64,
+            custom_attributes: serde_json::json!({}),
+            start_date_time: time,
+            end_date_time: time,
+            task_type: TaskType::Default.as_i32(),
+            percent_complete: 0.0,
+            status_id: None,
+            creator_id: Id(1),
+            updater_id: None,
+            create_date_time: time,
+            update_date_time: None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTaskAttributeConfigParams {
@@ -149,10 +215,12 @@ pub struct CreateTaskParams {
     pub start_date_time: chrono::NaiveDateTime,
     pub end_date_time: chrono::NaiveDateTime,
     pub task_type: i32,
+    /// 完成百分比（0-100），默认 0
+    pub percent_complete: Option<f64>,
     pub custom_attributes: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTaskParams {
     /// NOT NULL 字段
@@ -168,17 +236,87 @@ pub struct UpdateTaskParams {
     pub end_date_time: Option<chrono::NaiveDateTime>,
     /// NOT NULL 字段
     pub task_type: Option<i32>,
+    /// NOT NULL 字段，0-100
+    pub percent_complete: Option<f64>,
     /// NOT NULL 字段 (custom_attributes 默认 '{}'::jsonb)
     pub custom_attributes: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskQueryParams {
     pub page: Option<i64>,
     pub per_page: Option<i64>,
     pub task_name: Option<String>,
     pub parent_id: Option<Id>,
+    pub task_type: Option<i32>,
+    pub status_id: Option<Id>,
+    /// 开始日期范围（含两端）
+    pub start_date_from: Option<chrono::NaiveDate>,
+    pub start_date_to: Option<chrono::NaiveDate>,
+    /// 结束日期范围（含两端）
+    pub end_date_from: Option<chrono::NaiveDate>,
+    pub end_date_to: Option<chrono::NaiveDate>,
+    /// 自定义属性过滤，JSON 数组：`[{"attribute":"priority","op":"gte","value":3}]`
+    pub filter: Option<String>,
+    /// 排序，逗号分隔，`-` 前缀表示降序：`-priority,startDateTime`
+    pub sort: Option<String>,
+}
+
+/// 任务树查询参数
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTreeQuery {
+    /// 仅返回以该任务为根的子树
+    pub root_id: Option<Id>,
+}
+
+/// 挣值分析查询参数
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EarnedValueQuery {
+    /// 状态日期，默认当天
+    pub status_date: Option<chrono::NaiveDate>,
+    /// 以该基线的计划日期为准，默认使用任务当前日期
+    pub baseline_id: Option<Id>,
+}
+
+/// 子树汇总（叶子任务即自身的值）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRollup {
+    /// 子树最早开始时间
+    pub start_date_time: chrono::NaiveDateTime,
+    /// 子树最晚结束时间
+    pub end_date_time: chrono::NaiveDateTime,
+    pub child_count: i64,
+    pub descendant_count: i64,
+    /// number 类型自定义属性在叶子任务上的合计
+    pub attributes: std::collections::BTreeMap<String, f64>,
+    /// 叶子任务工期（工作日）合计
+    pub work_days: i64,
+    /// 完成百分比（0-100）：叶子任务为自身取值，父任务按子任务工期加权
+    pub progress: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTreeNode {
+    #[serde(flatten)]
+    pub task: Task,
+    pub rollup: TaskRollup,
+    pub children: Vec<TaskTreeNode>,
+}
+
+/// 自动排期参数：移动任务和/或调整工期（工作日），并顺延下游任务
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleTaskParams {
+    pub task_id: Id,
+    /// 新的开始时间，不传则保持原开始时间
+    pub start_date_time: Option<chrono::NaiveDateTime>,
+    /// 新的工期（工作日），不传则保持原工期
+    pub duration_days: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -187,6 +325,33 @@ pub struct BatchCreateTasksParams {
     pub tasks: Vec<CreateTaskParams>,
 }
 
+/// 批量更新中的单个任务补丁，字段语义同 UpdateTaskParams
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUpdateTaskItem {
+    pub id: Id,
+    #[serde(flatten)]
+    pub params: UpdateTaskParams,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUpdateTasksParams {
+    pub tasks: Vec<BatchUpdateTaskItem>,
+}
+
+/// 移动任务：指定新的父任务及相邻的兄弟任务；before / after 均不传时追加到末尾
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveTaskParams {
+    /// 目标父任务，null 表示移动到顶层
+    pub parent_id: Option<Id>,
+    /// 放到该兄弟任务之前
+    pub before_id: Option<Id>,
+    /// 放到该兄弟任务之后
+    pub after_id: Option<Id>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BatchDeleteTasksParams {
     pub ids: Vec<Id>,
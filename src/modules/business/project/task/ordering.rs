@@ -0,0 +1,181 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::models::Task;
+use std::collections::{HashMap, HashSet};
+
+/// 重新编号时相邻兄弟任务的 order 间隔
+pub const ORDER_STEP: f64 = 1024.0;
+
+/// 相邻 order 的最小间隔，小于该值时重新编号
+pub const MIN_ORDER_GAP: f64 = 1e-6;
+
+/// 移动计划：新的父任务与需要更新的 (task_id, order)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovePlan {
+    pub parent_id: Option<i64>,
+    pub orders: Vec<(i64, f64)>,
+    /// 是否重新编号了兄弟任务
+    pub rebalanced: bool,
+}
+
+/// `parent_id` 是否为 `task_id` 自身或其后代（即移动后会形成环）
+pub fn would_create_cycle(tasks: &[Task], task_id: i64, parent_id: i64) -> bool {
+    let parents: HashMap<i64, Option<i64>> = tasks
+        .iter()
+        .map(|t| (t.id.0, t.parent_id.map(|p| p.0)))
+        .collect();
+    let mut visited = HashSet::new();
+    let mut current = Some(parent_id);
+    while let Some(id) = current {
+        if id == task_id {
+            return true;
+        }
+        if !visited.insert(id) {
+            // 数据中已有环，视为非法
+            return true;
+        }
+        current = parents.get(&id).copied().flatten();
+    }
+    false
+}
+
+/// 计算移动后的 order：取前后兄弟任务的中点，间隔过小时按 ORDER_STEP 重新编号整个兄弟列表
+///
+/// `tasks` 为项目内全部任务；before_id / after_id 必须是目标父任务下的兄弟任务，同时传入时必须相邻。
+pub fn plan_move(
+    tasks: &[Task],
+    task_id: i64,
+    parent_id: Option<i64>,
+    before_id: Option<i64>,
+    after_id: Option<i64>,
+) -> AppResult<MovePlan> {
+    if let Some(parent_id) = parent_id {
+        if !tasks.iter().any(|t| t.id.0 == parent_id) {
+            return Err(AppError::BadRequest(format!(
+                "Parent task {} does not exist in this project",
+                parent_id
+            )));
+        }
+        if would_create_cycle(tasks, task_id, parent_id) {
+            return Err(AppError::BadRequest(
+                "Cannot move a task under itself or its descendants".to_string(),
+            ));
+        }
+    }
+
+    let mut siblings: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.id.0 != task_id && t.parent_id.map(|p| p.0) == parent_id)
+        .collect();
+    siblings.sort_by(|a, b| {
+        a.order
+            .total_cmp(&b.order)
+            .then(b.create_date_time.cmp(&a.create_date_time))
+    });
+
+    let position = |id: i64| {
+        siblings.iter().position(|t| t.id.0 == id).ok_or_else(|| {
+            AppError::BadRequest(format!("Task {} is not a sibling under the target parent", id))
+        })
+    };
+    // 插入位置：新任务位于 siblings[index] 之前
+    let index = match (before_id, after_id) {
+        (Some(before), Some(after)) => {
+            let (b, a) = (position(before)?, position(after)?);
+            if a + 1 != b {
+                return Err(AppError::BadRequest(
+                    "beforeId and afterId must be adjacent siblings".to_string(),
+                ));
+            }
+            b
+        }
+        (Some(before), None) => position(before)?,
+        (None, Some(after)) => position(after)? + 1,
+        (None, None) => siblings.len(),
+    };
+
+    let prev = index.checked_sub(1).map(|i| siblings[i].order);
+    let next = siblings.get(index).map(|t| t.order);
+    let order = match (prev, next) {
+        (Some(p), Some(n)) => (p + n) / 2.0,
+        (Some(p), None) => p + ORDER_STEP,
+        (None, Some(n)) => n - ORDER_STEP,
+        (None, None) => ORDER_STEP,
+    };
+
+    let gap_too_small = match (prev, next) {
+        (Some(p), Some(n)) => n - p < MIN_ORDER_GAP || order <= p || order >= n,
+        _ => !order.is_finite(),
+    };
+    if !gap_too_small {
+        return Ok(MovePlan {
+            parent_id,
+            orders: vec![(task_id, order)],
+            rebalanced: false,
+        });
+    }
+
+    let mut sequence: Vec<i64> = siblings.iter().map(|t| t.id.0).collect();
+    sequence.insert(index, task_id);
+    Ok(MovePlan {
+        parent_id,
+        orders: sequence
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| (id, (i + 1) as f64 * ORDER_STEP))
+            .collect(),
+        rebalanced: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::id::Id;
+    use chrono::NaiveDate;
+
+    fn task(id: i64, parent_id: Option<i64>, order: f64) -> Task {
+        let time = NaiveDate::from_ymd_opt(2026, 1, 5)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        Task {
+            parent_id: parent_id.map(Id),
+            order,
+            start_date_time: time,
+            end_date_time: time,
+            ..Task::test(id)
+        }
+    }
+
+    #[test]
+    fn test_move_between_siblings() {
+        let tasks = vec![task(1, None, 1.0), task(2, None, 2.0), task(3, None, 3.0)];
+        let plan = plan_move(&tasks, 3, None, Some(2), Some(1)).unwrap();
+        assert_eq!(plan.orders, vec![(3, 1.5)]);
+        assert!(!plan.rebalanced);
+
+        let plan = plan_move(&tasks, 1, None, None, None).unwrap();
+        assert_eq!(plan.orders, vec![(1, 3.0 + ORDER_STEP)]);
+
+        assert!(plan_move(&tasks, 3, None, Some(1), Some(2)).is_err());
+    }
+
+    #[test]
+    fn test_rebalances_when_gap_exhausted() {
+        let tasks = vec![task(1, None, 1.0), task(2, None, 1.0 + 1e-9), task(3, None, 5.0)];
+        let plan = plan_move(&tasks, 3, None, None, Some(1)).unwrap();
+        assert!(plan.rebalanced);
+        assert_eq!(
+            plan.orders,
+            vec![(1, ORDER_STEP), (3, 2.0 * ORDER_STEP), (2, 3.0 * ORDER_STEP)]
+        );
+    }
+
+    #[test]
+    fn test_rejects_cycles() {
+        let tasks = vec![task(1, None, 1.0), task(2, Some(1), 1.0), task(3, Some(2), 1.0)];
+        assert!(plan_move(&tasks, 1, Some(3), None, None).is_err());
+        assert!(plan_move(&tasks, 1, Some(1), None, None).is_err());
+        assert!(plan_move(&tasks, 3, Some(1), None, Some(2)).is_ok());
+    }
+}
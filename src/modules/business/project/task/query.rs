@@ -0,0 +1,313 @@
+use crate::common::error::{field_error, AppError, AppResult};
+use crate::modules::business::project::task::attributes::{
+    parse_date, parse_datetime, scalar_to_string,
+};
+use crate::modules::business::project::task::models::{AttributeType, TaskAttributeConfig};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// 过滤运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterOp {
+    Eq,
+    In,
+    Contains,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl FilterOp {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            FilterOp::Gt => " > ",
+            FilterOp::Gte => " >= ",
+            FilterOp::Lt => " < ",
+            FilterOp::Lte => " <= ",
+            _ => " = ",
+        }
+    }
+
+    /// 各属性类型支持的运算符
+    fn allowed_for(attribute_type: AttributeType) -> &'static [FilterOp] {
+        use FilterOp::*;
+        match attribute_type {
+            AttributeType::Text => &[Eq, Contains],
+            AttributeType::Select | AttributeType::User => &[Eq, In],
+            AttributeType::Boolean => &[Eq],
+            AttributeType::Number | AttributeType::Date | AttributeType::DateTime => {
+                &[Eq, Gt, Gte, Lt, Lte]
+            }
+        }
+    }
+}
+
+/// filter 参数中的单个条件
+#[derive(Debug, Deserialize)]
+pub struct AttributeFilterParam {
+    pub attribute: String,
+    pub op: FilterOp,
+    #[serde(default)]
+    pub value: Value,
+}
+
+/// 校验后的过滤值；日期 / 日期时间已规范化为可按字典序比较的字符串
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeFilter {
+    pub attribute_name: String,
+    pub attribute_type: AttributeType,
+    pub op: FilterOp,
+    pub value: FilterValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortField {
+    /// project_tasks 列
+    Column(&'static str),
+    Attribute {
+        name: String,
+        attribute_type: AttributeType,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+/// 任务列表的属性过滤与排序（已按项目属性配置校验）
+#[derive(Debug, Default)]
+pub struct TaskListQuery {
+    pub filters: Vec<AttributeFilter>,
+    pub sort: Vec<SortKey>,
+}
+
+/// 可排序的内置字段
+fn column_for(field: &str) -> Option<&'static str> {
+    match field {
+        "taskName" => Some("task_name"),
+        "order" => Some("\"order\""),
+        "startDateTime" => Some("start_date_time"),
+        "endDateTime" => Some("end_date_time"),
+        "taskType" => Some("task_type"),
+        "createDateTime" => Some("create_date_time"),
+        "updateDateTime" => Some("update_date_time"),
+        _ => None,
+    }
+}
+
+/// 按属性类型解析过滤值；日期时间属性接受纯日期，范围下界取当天 00:00:00，上界取 23:59:59
+fn parse_filter_value(attribute_type: AttributeType, op: FilterOp, value: &Value) -> Option<FilterValue> {
+    if op == FilterOp::In {
+        let items = value.as_array()?;
+        let list: Option<Vec<String>> = items.iter().map(scalar_to_string).collect();
+        return list.filter(|l| !l.is_empty()).map(FilterValue::List);
+    }
+    match attribute_type {
+        AttributeType::Text | AttributeType::Select | AttributeType::User => {
+            scalar_to_string(value).map(FilterValue::Text)
+        }
+        AttributeType::Number => match value {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse::<f64>().ok().filter(|n| n.is_finite()),
+            _ => None,
+        }
+        .map(FilterValue::Number),
+        AttributeType::Boolean => match value {
+            Value::Bool(b) => Some(*b),
+            Value::String(s) => s.trim().parse::<bool>().ok(),
+            _ => None,
+        }
+        .map(FilterValue::Bool),
+        AttributeType::Date => parse_date(value.as_str()?.trim())
+            .map(|d| FilterValue::Text(d.format("%Y-%m-%d").to_string())),
+        AttributeType::DateTime => {
+            let s = value.as_str()?.trim();
+            let dt = parse_datetime(s).or_else(|| {
+                let date = parse_date(s)?;
+                match op {
+                    FilterOp::Lte | FilterOp::Gt => date.and_hms_opt(23, 59, 59),
+                    _ => date.and_hms_opt(0, 0, 0),
+                }
+            })?;
+            Some(FilterValue::Text(dt.format("%Y-%m-%dT%H:%M:%S").to_string()))
+        }
+    }
+}
+
+fn find_config<'a>(configs: &'a [TaskAttributeConfig], name: &str) -> Option<(&'a TaskAttributeConfig, AttributeType)> {
+    configs
+        .iter()
+        .find(|c| c.attribute_name == name)
+        .map(|c| (c, AttributeType::from_str(&c.attribute_type).unwrap_or(AttributeType::Text)))
+}
+
+impl TaskListQuery {
+    /// 解析 filter（JSON 数组）与 sort（逗号分隔）参数，属性必须在项目属性配置中存在
+    pub fn parse(
+        filter: Option<&str>,
+        sort: Option<&str>,
+        configs: &[TaskAttributeConfig],
+    ) -> AppResult<Self> {
+        let params: Vec<AttributeFilterParam> = match filter.map(str::trim).filter(|f| !f.is_empty()) {
+            Some(f) => serde_json::from_str(f)
+                .map_err(|e| AppError::BadRequest(format!("Invalid filter: {}", e)))?,
+            None => Vec::new(),
+        };
+
+        let mut errors = Vec::new();
+        let mut query = TaskListQuery::default();
+
+        for (i, param) in params.into_iter().enumerate() {
+            let Some((config, attribute_type)) = find_config(configs, &param.attribute) else {
+                errors.push(field_error(
+                    format!("filter[{}].attribute", i),
+                    "unknown_attribute",
+                    format!("Unknown custom attribute: {}", param.attribute),
+                ));
+                continue;
+            };
+            if !FilterOp::allowed_for(attribute_type).contains(&param.op) {
+                errors.push(field_error(
+                    format!("filter[{}].op", i),
+                    "invalid_operator",
+                    format!(
+                        "Operator is not supported for {} attribute {}",
+                        attribute_type.as_str(),
+                        config.attribute_name
+                    ),
+                ));
+                continue;
+            }
+            match parse_filter_value(attribute_type, param.op, &param.value) {
+                Some(value) => query.filters.push(AttributeFilter {
+                    attribute_name: config.attribute_name.clone(),
+                    attribute_type,
+                    op: param.op,
+                    value,
+                }),
+                None => errors.push(field_error(
+                    format!("filter[{}].value", i),
+                    "invalid_value",
+                    format!(
+                        "Invalid value for {} attribute {}",
+                        attribute_type.as_str(),
+                        config.attribute_name
+                    ),
+                )),
+            }
+        }
+
+        for key in sort.unwrap_or("").split(',').map(str::trim).filter(|k| !k.is_empty()) {
+            let (descending, name) = match key.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, key.strip_prefix('+').unwrap_or(key)),
+            };
+            // 内置字段优先；`customAttributes.` 前缀强制按属性排序
+            let field = match name.strip_prefix("customAttributes.") {
+                Some(attr) => find_config(configs, attr),
+                None => match column_for(name) {
+                    Some(column) => {
+                        query.sort.push(SortKey {
+                            field: SortField::Column(column),
+                            descending,
+                        });
+                        continue;
+                    }
+                    None => find_config(configs, name),
+                },
+            };
+            match field {
+                Some((config, attribute_type)) => query.sort.push(SortKey {
+                    field: SortField::Attribute {
+                        name: config.attribute_name.clone(),
+                        attribute_type,
+                    },
+                    descending,
+                }),
+                None => errors.push(field_error(
+                    "sort".to_string(),
+                    "unknown_field",
+                    format!("Unknown sort field: {}", name),
+                )),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::ValidationError(
+                "Invalid task query".to_string(),
+                errors,
+            ));
+        }
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters_and_sort() {
+        let configs = vec![
+            TaskAttributeConfig::test("priority", "number"),
+            TaskAttributeConfig::test("status", "select"),
+            TaskAttributeConfig::test("deadline", "datetime"),
+        ];
+        let filter = r#"[
+            {"attribute":"priority","op":"gte","value":"2"},
+            {"attribute":"status","op":"in","value":[" open ","doing"]},
+            {"attribute":"deadline","op":"lte","value":"2026-01-31"}
+        ]"#;
+        let query =
+            TaskListQuery::parse(Some(filter), Some("-priority,startDateTime"), &configs).unwrap();
+
+        assert_eq!(query.filters[0].value, FilterValue::Number(2.0));
+        assert_eq!(
+            query.filters[1].value,
+            FilterValue::List(vec!["open".to_string(), "doing".to_string()])
+        );
+        assert_eq!(
+            query.filters[2].value,
+            FilterValue::Text("2026-01-31T23:59:59".to_string())
+        );
+        assert!(query.sort[0].descending);
+        assert_eq!(query.sort[1].field, SortField::Column("start_date_time"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_filters() {
+        let configs = vec![TaskAttributeConfig::test("priority", "number"), TaskAttributeConfig::test("note", "text")];
+        let filter = r#"[
+            {"attribute":"missing","op":"eq","value":1},
+            {"attribute":"note","op":"gt","value":"a"},
+            {"attribute":"priority","op":"eq","value":"high"}
+        ]"#;
+        let Err(AppError::ValidationError(_, errors)) =
+            TaskListQuery::parse(Some(filter), Some("unknown"), &configs)
+        else {
+            panic!("expected validation error");
+        };
+        let codes: Vec<&str> = errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            vec!["unknown_attribute", "invalid_operator", "invalid_value", "unknown_field"]
+        );
+
+        assert!(matches!(
+            TaskListQuery::parse(Some("not json"), None, &configs),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,429 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{field_error, AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::attachment::handlers::cleanup_task_files;
+use crate::modules::business::project::task::attributes::validate_custom_attributes;
+use crate::modules::business::project::task::handlers::{
+    require_task_permission, sync_parent_dates, with_parent_ids,
+};
+use crate::modules::business::project::task::history::handlers::record_history;
+use crate::modules::business::project::task::models::{
+    CreateTaskParams, Task, TaskType, UpdateTaskParams,
+};
+use crate::modules::business::project::task::ordering::ORDER_STEP;
+use crate::modules::business::project::task::recurrence::models::{
+    BulkEditOccurrencesParams, CreateRecurrenceParams, DeleteRecurrenceQuery,
+    GenerateOccurrencesParams, HolidayPolicy, NewRecurrence, RecurrenceDetail,
+    RecurrenceOccurrence, TaskRecurrence, UpdateRecurrenceParams, DEFAULT_HORIZON_DAYS,
+    MAX_HORIZON_DAYS, MAX_OCCURRENCES_PER_GENERATION,
+};
+use crate::modules::business::project::task::recurrence::repository::RecurrenceRepository;
+use crate::modules::business::project::task::recurrence::rule::{plan_occurrences, RecurrenceRule};
+use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::business::project::task::scheduler::working_duration;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::Duration;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+/// 解析并规范化 RRULE
+fn parse_rule(rrule: &str) -> AppResult<RecurrenceRule> {
+    RecurrenceRule::parse(rrule).map_err(|message| {
+        AppError::ValidationError(
+            "Invalid recurrence rule".to_string(),
+            vec![field_error("rrule", "invalid_rrule", message)],
+        )
+    })
+}
+
+/// 查询系列并校验其属于当前项目
+async fn get_project_recurrence(
+    pool: &PgPool,
+    project_id: i64,
+    recurrence_id: Id,
+) -> AppResult<TaskRecurrence> {
+    RecurrenceRepository::get_recurrence_by_id(pool, recurrence_id.0)
+        .await?
+        .filter(|r| r.project_id.0 == project_id)
+        .ok_or_else(|| AppError::NotFound(format!("Recurrence not found: {}", recurrence_id)))
+}
+
+/// 系列中的任务，按规则计算的日期正序
+async fn get_occurrences(pool: &PgPool, recurrence_id: i64) -> AppResult<Vec<RecurrenceOccurrence>> {
+    let links = RecurrenceRepository::get_occurrences(pool, recurrence_id).await?;
+    let task_ids: Vec<i64> = links.iter().map(|(task_id, _)| *task_id).collect();
+    let mut tasks: HashMap<i64, Task> = TaskRepository::get_tasks_by_ids(pool, &task_ids)
+        .await?
+        .into_iter()
+        .map(|t| (t.id.0, t))
+        .collect();
+    Ok(links
+        .into_iter()
+        .filter_map(|(task_id, occurrence_date)| {
+            tasks
+                .remove(&task_id)
+                .map(|task| RecurrenceOccurrence {
+                    occurrence_date,
+                    task,
+                })
+        })
+        .collect())
+}
+
+pub async fn get_recurrences(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+) -> AppResult<Json<ApiResponse<Vec<TaskRecurrence>>>> {
+    perm.require(Permission::TaskView)?;
+    let recurrences = RecurrenceRepository::get_recurrences(&state.pool, project_id.0).await?;
+    Ok(Json(ApiResponse::success(recurrences)))
+}
+
+pub async fn get_recurrence_by_id(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, recurrence_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<RecurrenceDetail>>> {
+    perm.require(Permission::TaskView)?;
+    let recurrence = get_project_recurrence(&state.pool, project_id.0, recurrence_id).await?;
+    let occurrences = get_occurrences(&state.pool, recurrence.id.0).await?;
+    Ok(Json(ApiResponse::success(RecurrenceDetail {
+        recurrence,
+        occurrences,
+    })))
+}
+
+pub async fn create_recurrence(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<CreateRecurrenceParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<TaskRecurrence>>)> {
+    perm.require(Permission::TaskCreate)?;
+    let task = TaskRepository::get_task_by_id(&state.pool, params.task_id.0)
+        .await?
+        .filter(|t| t.project_id == project_id)
+        .ok_or_else(|| AppError::NotFound(format!("Task not found: {}", params.task_id)))?;
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &[task.id.0],
+        "edit",
+    )
+    .await?;
+    if RecurrenceRepository::get_recurrence_id_by_task(&state.pool, task.id.0)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Conflict(format!(
+            "Task {} already belongs to a recurrence",
+            task.id
+        )));
+    }
+    let rule = parse_rule(&params.rrule)?;
+
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+    let recurrence_id = state
+        .generate_id()
+        .map_err(|e| AppError::InternalError(format!("Failed to generate recurrence ID: {}", e)))?;
+    let recurrence = NewRecurrence {
+        id: recurrence_id,
+        project_id: project_id.0,
+        rrule: rule.to_string(),
+        holiday_policy: params.holiday_policy.unwrap_or(HolidayPolicy::ShiftForward),
+        start_date: task.start_date_time.date(),
+        task_name: task.task_name.clone(),
+        parent_id: task.parent_id.map(|id| id.0),
+        task_type: task.task_type,
+        custom_attributes: task.custom_attributes.clone(),
+        start_time: task.start_date_time.time(),
+        end_time: task.end_date_time.time(),
+        duration_days: working_duration(&calendar, &task)? as i32,
+    };
+    let recurrence =
+        RecurrenceRepository::create_recurrence(&state.pool, recurrence, task.id.0, claims.sub)
+            .await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(recurrence))))
+}
+
+pub async fn update_recurrence(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, recurrence_id)): Path<(Id, Id)>,
+    Json(params): Json<UpdateRecurrenceParams>,
+) -> AppResult<Json<ApiResponse<TaskRecurrence>>> {
+    perm.require(Permission::TaskCreate)?;
+    let recurrence = get_project_recurrence(&state.pool, project_id.0, recurrence_id).await?;
+    let task_ids: Vec<i64> = RecurrenceRepository::get_occurrences(&state.pool, recurrence.id.0)
+        .await?
+        .into_iter()
+        .map(|(task_id, _)| task_id)
+        .collect();
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &task_ids,
+        "edit",
+    )
+    .await?;
+    let rrule = match params.rrule {
+        Some(rrule) => parse_rule(&rrule)?.to_string(),
+        None => recurrence.rrule,
+    };
+    let holiday_policy = params.holiday_policy.unwrap_or(recurrence.holiday_policy);
+    let recurrence = RecurrenceRepository::update_rule(
+        &state.pool,
+        recurrence.id.0,
+        &rrule,
+        holiday_policy,
+        claims.sub,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(recurrence)))
+}
+
+pub async fn delete_recurrence(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, recurrence_id)): Path<(Id, Id)>,
+    Query(query): Query<DeleteRecurrenceQuery>,
+) -> AppResult<StatusCode> {
+    perm.require(Permission::TaskCreate)?;
+    let recurrence = get_project_recurrence(&state.pool, project_id.0, recurrence_id).await?;
+    // 解除关联同样需要对系列中全部任务（含模板任务）有删除权限
+    let occurrences = RecurrenceRepository::get_occurrences(&state.pool, recurrence.id.0).await?;
+    let task_ids: Vec<i64> = occurrences.iter().map(|(task_id, _)| *task_id).collect();
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskDeleteAll,
+        Permission::TaskDeleteOwn,
+        &task_ids,
+        "delete",
+    )
+    .await?;
+
+    if let Some(delete_from) = query.delete_from {
+        let task_ids: Vec<i64> = occurrences
+            .into_iter()
+            .filter(|(_, occurrence_date)| *occurrence_date >= delete_from)
+            .map(|(task_id, _)| task_id)
+            .collect();
+        let mut tx = state.pool.begin().await?;
+        let deleted = TaskRepository::batch_delete_tasks(&mut tx, task_ids).await?;
+        record_history(&state, &mut tx, &deleted, &[], claims.sub).await?;
+        RecurrenceRepository::delete_recurrence(&mut tx, recurrence.id.0).await?;
+        let task_ids = with_parent_ids(&deleted);
+        sync_parent_dates(&state, &mut tx, project_id.0, &task_ids, claims.sub).await?;
+        tx.commit().await?;
+        cleanup_task_files(&state, &deleted).await;
+    } else {
+        let mut conn = state.pool.acquire().await?;
+        RecurrenceRepository::delete_recurrence(&mut conn, recurrence.id.0).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 按规则生成到指定日期为止的任务；已生成过的日期不会重复生成
+pub async fn generate_occurrences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, recurrence_id)): Path<(Id, Id)>,
+    Json(params): Json<GenerateOccurrencesParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<Vec<Task>>>)> {
+    perm.require(Permission::TaskCreate)?;
+    let recurrence = get_project_recurrence(&state.pool, project_id.0, recurrence_id).await?;
+
+    let today = chrono::Local::now().date_naive();
+    let until = params
+        .until
+        .unwrap_or(today + Duration::days(DEFAULT_HORIZON_DAYS));
+    if until > today + Duration::days(MAX_HORIZON_DAYS) {
+        return Err(AppError::BadRequest(format!(
+            "Occurrences can be generated at most {} days ahead",
+            MAX_HORIZON_DAYS
+        )));
+    }
+    if until <= recurrence.generated_until {
+        return Ok((StatusCode::CREATED, Json(ApiResponse::success(vec![]))));
+    }
+
+    let rule = parse_rule(&recurrence.rrule)?;
+    let calendar = WorkingCalendar::load_for_project(&state.pool, project_id.0).await?;
+    let taken: HashSet<_> = get_occurrences(&state.pool, recurrence.id.0)
+        .await?
+        .into_iter()
+        .map(|o| o.task.start_date_time.date())
+        .collect();
+    let (planned, generated_until) = plan_occurrences(
+        &rule,
+        recurrence.start_date,
+        (recurrence.generated_until, until),
+        &calendar,
+        recurrence.holiday_policy,
+        taken,
+        MAX_OCCURRENCES_PER_GENERATION,
+    )?;
+
+    let is_zero_duration =
+        TaskType::from_i32(recurrence.task_type).is_some_and(TaskType::is_zero_duration);
+
+    // 在事务内读取兄弟任务的最大 order，追加到同级任务末尾
+    let mut tx = state.pool.begin().await?;
+    let parent_id = recurrence.parent_id.map(|id| id.0);
+    let max_order = TaskRepository::get_max_child_order(&mut tx, project_id.0, parent_id)
+        .await?
+        .unwrap_or(0.0);
+    let mut occurrences = Vec::with_capacity(planned.len());
+    for (i, occurrence) in planned.into_iter().enumerate() {
+        let task_id = state
+            .generate_id()
+            .map_err(|e| AppError::InternalError(format!("Failed to generate task ID: {}", e)))?;
+        let start_date_time = occurrence.date.and_time(recurrence.start_time);
+        let end_date_time = if is_zero_duration {
+            start_date_time
+        } else {
+            calendar
+                .add_working_days(occurrence.date, recurrence.duration_days as i64 - 1)?
+                .and_time(recurrence.end_time)
+        };
+        let params = CreateTaskParams {
+            task_name: recurrence.task_name.clone(),
+            parent_id: recurrence.parent_id,
+            order: max_order + ORDER_STEP * (i + 1) as f64,
+            start_date_time,
+            end_date_time,
+            task_type: recurrence.task_type,
+            percent_complete: None,
+            custom_attributes: Some(recurrence.custom_attributes.clone()),
+        };
+        occurrences.push((task_id, params, occurrence.occurrence_date));
+    }
+
+    let created = RecurrenceRepository::materialize_occurrences(
+        &mut tx,
+        &recurrence,
+        generated_until,
+        occurrences,
+        claims.sub,
+    )
+    .await?;
+    record_history(&state, &mut tx, &[], &created, claims.sub).await?;
+    sync_parent_dates(&state, &mut tx, project_id.0, &with_parent_ids(&created), claims.sub)
+        .await?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(created))))
+}
+
+/// 批量修改系列中的任务，并同步更新模板
+pub async fn bulk_edit_occurrences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, recurrence_id)): Path<(Id, Id)>,
+    Json(params): Json<BulkEditOccurrencesParams>,
+) -> AppResult<Json<ApiResponse<Vec<Task>>>> {
+    perm.require(Permission::TaskCreate)?;
+    // 模板决定后续生成的全部任务，修改模板需要编辑全部任务的权限
+    perm.require(Permission::TaskEditAll)?;
+    let recurrence = get_project_recurrence(&state.pool, project_id.0, recurrence_id).await?;
+    if params.task_name.is_none() && params.custom_attributes.is_none() {
+        return Err(AppError::BadRequest("Nothing to update".to_string()));
+    }
+    if params
+        .task_name
+        .as_ref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err(AppError::BadRequest(
+            "Task name cannot be empty".to_string(),
+        ));
+    }
+
+    let custom_attributes = match params.custom_attributes {
+        Some(custom_attributes) => {
+            let configs =
+                TaskRepository::get_attribute_configs_by_project(&state.pool, project_id.0).await?;
+            let mut errors = Vec::new();
+            let normalized = validate_custom_attributes(
+                &configs,
+                Some(&custom_attributes),
+                "customAttributes",
+                &mut errors,
+            );
+            if !errors.is_empty() {
+                return Err(AppError::ValidationError(
+                    "Invalid custom attributes".to_string(),
+                    errors,
+                ));
+            }
+            Some(normalized)
+        }
+        None => None,
+    };
+
+    let occurrences: Vec<RecurrenceOccurrence> = get_occurrences(&state.pool, recurrence.id.0)
+        .await?
+        .into_iter()
+        .filter(|o| params.from_date.is_none_or(|from| o.occurrence_date >= from))
+        .collect();
+    let task_ids: Vec<i64> = occurrences.iter().map(|o| o.task.id.0).collect();
+    require_task_permission(
+        &state.pool,
+        &perm,
+        Permission::TaskEditAll,
+        Permission::TaskEditOwn,
+        &task_ids,
+        "edit",
+    )
+    .await?;
+
+    let updates = task_ids
+        .iter()
+        .map(|task_id| {
+            (
+                *task_id,
+                UpdateTaskParams {
+                    task_name: params.task_name.clone(),
+                    custom_attributes: custom_attributes.clone(),
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+    let mut tx = state.pool.begin().await?;
+    let updated = TaskRepository::batch_update_tasks(&mut tx, updates, claims.sub).await?;
+    let before: Vec<Task> = occurrences.into_iter().map(|o| o.task).collect();
+    record_history(&state, &mut tx, &before, &updated, claims.sub).await?;
+    RecurrenceRepository::update_template(
+        &mut tx,
+        recurrence.id.0,
+        params.task_name.as_deref(),
+        custom_attributes.as_ref(),
+        claims.sub,
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(Json(ApiResponse::success(updated)))
+}
@@ -0,0 +1,175 @@
+use crate::common::id::Id;
+use crate::modules::business::project::task::models::Task;
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+/// 生成任务的默认范围（天，自当天起）
+pub const DEFAULT_HORIZON_DAYS: i64 = 90;
+
+/// 生成任务的最大范围（天，自当天起）
+pub const MAX_HORIZON_DAYS: i64 = 366;
+
+/// 单次最多生成的任务数
+pub const MAX_OCCURRENCES_PER_GENERATION: usize = 500;
+
+/// 遇非工作日时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolidayPolicy {
+    /// 照常生成
+    Keep = 0,
+    Skip = 1,
+    /// 顺延到下一个工作日
+    ShiftForward = 2,
+    /// 提前到上一个工作日
+    ShiftBackward = 3,
+}
+
+impl HolidayPolicy {
+    pub fn from_i16(v: i16) -> Option<Self> {
+        match v {
+            0 => Some(HolidayPolicy::Keep),
+            1 => Some(HolidayPolicy::Skip),
+            2 => Some(HolidayPolicy::ShiftForward),
+            3 => Some(HolidayPolicy::ShiftBackward),
+            _ => None,
+        }
+    }
+
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+}
+
+/// 重复任务系列
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRecurrence {
+    pub id: Id,
+    pub project_id: Id,
+    pub rrule: String,
+    pub holiday_policy: HolidayPolicy,
+    /// 系列起始日期（即首个任务的日期）
+    pub start_date: NaiveDate,
+    // 任务模板
+    pub task_name: String,
+    pub parent_id: Option<Id>,
+    pub task_type: i32,
+    pub custom_attributes: serde_json::Value,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    /// 工期（工作日）
+    pub duration_days: i32,
+    /// 已生成到的日期（按规则计算的日期，含端点）
+    pub generated_until: NaiveDate,
+    pub creator_id: Id,
+    pub updater_id: Option<Id>,
+    pub create_date_time: chrono::NaiveDateTime,
+    pub update_date_time: Option<chrono::NaiveDateTime>,
+}
+
+impl FromRow<'_, PgRow> for TaskRecurrence {
+    fn from_row(row: &PgRow) -> sqlx::Result<Self> {
+        let holiday_policy: i16 = row.try_get("holiday_policy")?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            project_id: row.try_get("project_id")?,
+            rrule: row.try_get("rrule")?,
+            holiday_policy: HolidayPolicy::from_i16(holiday_policy).ok_or_else(|| {
+                sqlx::Error::Decode(format!("Invalid holiday policy: {}", holiday_policy).into())
+            })?,
+            start_date: row.try_get("start_date")?,
+            task_name: row.try_get("task_name")?,
+            parent_id: row.try_get("parent_id")?,
+            task_type: row.try_get("task_type")?,
+            custom_attributes: row.try_get("custom_attributes")?,
+            start_time: row.try_get("start_time")?,
+            end_time: row.try_get("end_time")?,
+            duration_days: row.try_get("duration_days")?,
+            generated_until: row.try_get("generated_until")?,
+            creator_id: row.try_get("creator_id")?,
+            updater_id: row.try_get("updater_id")?,
+            create_date_time: row.try_get("create_date_time")?,
+            update_date_time: row.try_get("update_date_time")?,
+        })
+    }
+}
+
+/// 新建系列（模板取自已有任务）
+#[derive(Debug, Clone)]
+pub struct NewRecurrence {
+    pub id: i64,
+    pub project_id: i64,
+    pub rrule: String,
+    pub holiday_policy: HolidayPolicy,
+    pub start_date: NaiveDate,
+    pub task_name: String,
+    pub parent_id: Option<i64>,
+    pub task_type: i32,
+    pub custom_attributes: serde_json::Value,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub duration_days: i32,
+}
+
+/// 系列中的任务及其按规则计算的日期
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceOccurrence {
+    pub occurrence_date: NaiveDate,
+    #[serde(flatten)]
+    pub task: Task,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceDetail {
+    #[serde(flatten)]
+    pub recurrence: TaskRecurrence,
+    /// 按日期正序
+    pub occurrences: Vec<RecurrenceOccurrence>,
+}
+
+/// 以已有任务为模板创建系列，该任务成为系列的首个任务
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRecurrenceParams {
+    pub task_id: Id,
+    pub rrule: String,
+    /// 默认顺延到下一个工作日
+    pub holiday_policy: Option<HolidayPolicy>,
+}
+
+/// 修改规则仅影响尚未生成的日期
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRecurrenceParams {
+    pub rrule: Option<String>,
+    pub holiday_policy: Option<HolidayPolicy>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateOccurrencesParams {
+    /// 生成到该日期（含），默认当天起 DEFAULT_HORIZON_DAYS 天
+    pub until: Option<NaiveDate>,
+}
+
+/// 批量修改系列中的任务，同时更新模板以作用于之后生成的任务
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkEditOccurrencesParams {
+    /// 仅修改按规则计算的日期不早于该日期的任务，默认全部
+    pub from_date: Option<NaiveDate>,
+    pub task_name: Option<String>,
+    pub custom_attributes: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteRecurrenceQuery {
+    /// 同时删除按规则计算的日期不早于该日期的任务；默认保留全部任务
+    pub delete_from: Option<NaiveDate>,
+}
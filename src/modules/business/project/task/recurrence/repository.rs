@@ -0,0 +1,245 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::models::{CreateTaskParams, Task};
+use crate::modules::business::project::task::recurrence::models::{
+    HolidayPolicy, NewRecurrence, TaskRecurrence,
+};
+use crate::modules::business::project::task::repository::TaskRepository;
+use chrono::NaiveDate;
+use sqlx::{PgConnection, PgPool, QueryBuilder};
+
+pub struct RecurrenceRepository;
+
+/// project_task_recurrences 表 SELECT 列
+const RECURRENCE_COLUMNS: &str = r#"id, project_id, rrule, holiday_policy, start_date, task_name,
+    parent_id, task_type, custom_attributes, start_time, end_time, duration_days, generated_until,
+    creator_id, updater_id, create_date_time, update_date_time"#;
+
+impl RecurrenceRepository {
+    pub async fn get_recurrences(pool: &PgPool, project_id: i64) -> AppResult<Vec<TaskRecurrence>> {
+        let sql = format!(
+            "SELECT {} FROM project_task_recurrences WHERE project_id = $1 ORDER BY create_date_time ASC, id ASC",
+            RECURRENCE_COLUMNS,
+        );
+        let recurrences = sqlx::query_as::<_, TaskRecurrence>(&sql)
+            .bind(project_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(recurrences)
+    }
+
+    pub async fn get_recurrence_by_id(
+        pool: &PgPool,
+        recurrence_id: i64,
+    ) -> AppResult<Option<TaskRecurrence>> {
+        let sql = format!(
+            "SELECT {} FROM project_task_recurrences WHERE id = $1",
+            RECURRENCE_COLUMNS,
+        );
+        let recurrence = sqlx::query_as::<_, TaskRecurrence>(&sql)
+            .bind(recurrence_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(recurrence)
+    }
+
+    /// 任务所属的系列
+    pub async fn get_recurrence_id_by_task(pool: &PgPool, task_id: i64) -> AppResult<Option<i64>> {
+        let row: Option<(i64,)> =
+            sqlx::query_as("SELECT recurrence_id FROM project_task_occurrences WHERE task_id = $1")
+                .bind(task_id)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    /// 系列中的任务：(task_id, 按规则计算的日期)，按日期正序
+    pub async fn get_occurrences(
+        pool: &PgPool,
+        recurrence_id: i64,
+    ) -> AppResult<Vec<(i64, NaiveDate)>> {
+        let rows = sqlx::query_as(
+            r#"SELECT task_id, occurrence_date FROM project_task_occurrences
+               WHERE recurrence_id = $1
+               ORDER BY occurrence_date ASC"#,
+        )
+        .bind(recurrence_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// 创建系列，模板任务作为系列的首个任务
+    pub async fn create_recurrence(
+        pool: &PgPool,
+        recurrence: NewRecurrence,
+        template_task_id: i64,
+        creator_id: i64,
+    ) -> AppResult<TaskRecurrence> {
+        let mut tx = pool.begin().await?;
+        let sql = format!(
+            r#"INSERT INTO project_task_recurrences
+                   (id, project_id, rrule, holiday_policy, start_date, task_name, parent_id,
+                    task_type, custom_attributes, start_time, end_time, duration_days,
+                    generated_until, creator_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $5, $13)
+               RETURNING {}"#,
+            RECURRENCE_COLUMNS,
+        );
+        let created = sqlx::query_as::<_, TaskRecurrence>(&sql)
+            .bind(recurrence.id)
+            .bind(recurrence.project_id)
+            .bind(&recurrence.rrule)
+            .bind(recurrence.holiday_policy.as_i16())
+            .bind(recurrence.start_date)
+            .bind(&recurrence.task_name)
+            .bind(recurrence.parent_id)
+            .bind(recurrence.task_type)
+            .bind(&recurrence.custom_attributes)
+            .bind(recurrence.start_time)
+            .bind(recurrence.end_time)
+            .bind(recurrence.duration_days)
+            .bind(creator_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"INSERT INTO project_task_occurrences (task_id, recurrence_id, occurrence_date)
+               VALUES ($1, $2, $3)"#,
+        )
+        .bind(template_task_id)
+        .bind(recurrence.id)
+        .bind(recurrence.start_date)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    pub async fn update_rule(
+        pool: &PgPool,
+        recurrence_id: i64,
+        rrule: &str,
+        holiday_policy: HolidayPolicy,
+        updater_id: i64,
+    ) -> AppResult<TaskRecurrence> {
+        let sql = format!(
+            r#"UPDATE project_task_recurrences
+               SET rrule = $2, holiday_policy = $3, updater_id = $4,
+                   update_date_time = CURRENT_TIMESTAMP
+               WHERE id = $1
+               RETURNING {}"#,
+            RECURRENCE_COLUMNS,
+        );
+        let recurrence = sqlx::query_as::<_, TaskRecurrence>(&sql)
+            .bind(recurrence_id)
+            .bind(rrule)
+            .bind(holiday_policy.as_i16())
+            .bind(updater_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(recurrence)
+    }
+
+    /// 更新任务模板，未传的字段保持不变
+    pub async fn update_template(
+        conn: &mut PgConnection,
+        recurrence_id: i64,
+        task_name: Option<&str>,
+        custom_attributes: Option<&serde_json::Value>,
+        updater_id: i64,
+    ) -> AppResult<TaskRecurrence> {
+        let mut qb: QueryBuilder<sqlx::Postgres> =
+            QueryBuilder::new("UPDATE project_task_recurrences SET updater_id = ");
+        qb.push_bind(updater_id);
+        qb.push(", update_date_time = CURRENT_TIMESTAMP");
+        if let Some(task_name) = task_name {
+            qb.push(", task_name = ").push_bind(task_name);
+        }
+        if let Some(custom_attributes) = custom_attributes {
+            qb.push(", custom_attributes = ").push_bind(custom_attributes);
+        }
+        qb.push(" WHERE id = ").push_bind(recurrence_id);
+        qb.push(format!(" RETURNING {}", RECURRENCE_COLUMNS));
+
+        let recurrence = qb
+            .build_query_as::<TaskRecurrence>()
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(recurrence)
+    }
+
+    /// 删除系列；系列中的任务保留，仅解除关联
+    pub async fn delete_recurrence(conn: &mut PgConnection, recurrence_id: i64) -> AppResult<()> {
+        sqlx::query("DELETE FROM project_task_recurrences WHERE id = $1")
+            .bind(recurrence_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 插入生成的任务及其关联，并推进 generated_until；需在事务中调用。
+    ///
+    /// 数据库中的 generated_until 已不同于 `recurrence` 读取时的值，说明有并发生成，返回 Conflict。
+    pub async fn materialize_occurrences(
+        conn: &mut PgConnection,
+        recurrence: &TaskRecurrence,
+        generated_until: NaiveDate,
+        occurrences: Vec<(i64, CreateTaskParams, NaiveDate)>,
+        creator_id: i64,
+    ) -> AppResult<Vec<Task>> {
+        let updated = sqlx::query(
+            r#"UPDATE project_task_recurrences
+               SET generated_until = $2
+               WHERE id = $1 AND generated_until = $3"#,
+        )
+        .bind(recurrence.id.0)
+        .bind(generated_until)
+        .bind(recurrence.generated_until)
+        .execute(&mut *conn)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Err(AppError::Conflict(
+                "Occurrences are being generated concurrently, please retry".to_string(),
+            ));
+        }
+        if occurrences.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let links: Vec<(i64, NaiveDate)> = occurrences
+            .iter()
+            .map(|(id, _, occurrence_date)| (*id, *occurrence_date))
+            .collect();
+        let tasks = TaskRepository::insert_tasks(
+            &mut *conn,
+            occurrences
+                .into_iter()
+                .map(|(id, params, _)| (id, params))
+                .collect(),
+            recurrence.project_id.0,
+            creator_id,
+        )
+        .await?;
+
+        let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
+            "INSERT INTO project_task_occurrences (task_id, recurrence_id, occurrence_date) ",
+        );
+        qb.push_values(links, |mut b, (task_id, occurrence_date)| {
+            b.push_bind(task_id)
+                .push_bind(recurrence.id.0)
+                .push_bind(occurrence_date);
+        });
+        qb.build().execute(&mut *conn).await?;
+
+        Ok(tasks)
+    }
+}
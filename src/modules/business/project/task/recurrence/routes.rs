@@ -0,0 +1,52 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::recurrence::handlers;
+use axum::{
+    middleware,
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn task_recurrence_routes(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/projects/{project_id}/recurrences",
+            get(handlers::get_recurrences),
+        )
+        .route(
+            "/projects/{project_id}/recurrences",
+            post(handlers::create_recurrence),
+        )
+        .route(
+            "/projects/{project_id}/recurrences/{recurrence_id}",
+            get(handlers::get_recurrence_by_id),
+        )
+        .route(
+            "/projects/{project_id}/recurrences/{recurrence_id}",
+            put(handlers::update_recurrence),
+        )
+        .route(
+            "/projects/{project_id}/recurrences/{recurrence_id}",
+            delete(handlers::delete_recurrence),
+        )
+        .route(
+            "/projects/{project_id}/recurrences/{recurrence_id}/generate",
+            post(handlers::generate_occurrences),
+        )
+        .route(
+            "/projects/{project_id}/recurrences/{recurrence_id}/occurrences",
+            put(handlers::bulk_edit_occurrences),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,463 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::recurrence::models::HolidayPolicy;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::collections::HashSet;
+use std::fmt;
+
+/// INTERVAL 上限
+pub const MAX_INTERVAL: u32 = 366;
+
+/// COUNT 上限
+pub const MAX_COUNT: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// BYDAY 中的一项；ordinal 仅用于 MONTHLY，如 1MO 为当月第一个周一，-1FR 为最后一个周五
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i8>,
+    pub weekday: Weekday,
+}
+
+/// RRULE 子集：FREQ（DAILY / WEEKLY / MONTHLY）、INTERVAL、BYDAY、UNTIL、COUNT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub until: Option<NaiveDate>,
+    pub count: Option<u32>,
+}
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+fn parse_by_day(token: &str) -> Result<ByDay, String> {
+    let invalid = || format!("Invalid BYDAY value: {}", token);
+    if token.len() < 2 || !token.is_char_boundary(token.len() - 2) {
+        return Err(invalid());
+    }
+    let (ordinal, day) = token.split_at(token.len() - 2);
+    let weekday = WEEKDAYS
+        .iter()
+        .find(|(name, _)| *name == day)
+        .map(|(_, weekday)| *weekday)
+        .ok_or_else(invalid)?;
+    let ordinal = match ordinal {
+        "" => None,
+        _ => {
+            let n: i8 = ordinal.parse().map_err(|_| invalid())?;
+            if n == 0 || !(-5..=5).contains(&n) {
+                return Err(invalid());
+            }
+            Some(n)
+        }
+    };
+    Ok(ByDay { ordinal, weekday })
+}
+
+fn parse_until(value: &str) -> Result<NaiveDate, String> {
+    // 接受 YYYYMMDD 或 YYYYMMDDTHHMMSS[Z]，仅取日期部分
+    value
+        .get(..8)
+        .filter(|_| value.len() == 8 || value[8..].starts_with('T'))
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("Invalid UNTIL value: {}", value))
+}
+
+impl RecurrenceRule {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let body = input
+            .strip_prefix("RRULE:")
+            .or_else(|| input.strip_prefix("rrule:"))
+            .unwrap_or(input);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut until = None;
+        let mut count = None;
+        let mut seen = HashSet::new();
+        for part in body.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid RRULE part: {}", part))?;
+            let key = key.trim().to_ascii_uppercase();
+            let value = value.trim().to_ascii_uppercase();
+            if !seen.insert(key.clone()) {
+                return Err(format!("Duplicate RRULE part: {}", key));
+            }
+            match key.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(format!("Unsupported FREQ: {}", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse()
+                        .ok()
+                        .filter(|n| (1..=MAX_INTERVAL).contains(n))
+                        .ok_or_else(|| {
+                            format!("INTERVAL must be between 1 and {}", MAX_INTERVAL)
+                        })?
+                }
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(|token| parse_by_day(token.trim()))
+                        .collect::<Result<_, _>>()?
+                }
+                "UNTIL" => until = Some(parse_until(&value)?),
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|n| (1..=MAX_COUNT).contains(n))
+                            .ok_or_else(|| format!("COUNT must be between 1 and {}", MAX_COUNT))?,
+                    )
+                }
+                _ => return Err(format!("Unsupported RRULE part: {}", key)),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| "FREQ is required".to_string())?;
+        if until.is_some() && count.is_some() {
+            return Err("UNTIL and COUNT cannot both be set".to_string());
+        }
+        if frequency != Frequency::Monthly && by_day.iter().any(|d| d.ordinal.is_some()) {
+            return Err("BYDAY ordinals are only supported with FREQ=MONTHLY".to_string());
+        }
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            until,
+            count,
+        })
+    }
+
+    /// 第 period 个周期的起始日期，以及该周期内（未经 dtstart / UNTIL 过滤）的候选日期
+    fn period_dates(&self, dtstart: NaiveDate, period: u32) -> (NaiveDate, Vec<NaiveDate>) {
+        let step = period * self.interval;
+        let matches_day = |d: NaiveDate| {
+            self.by_day.is_empty() || self.by_day.iter().any(|b| b.weekday == d.weekday())
+        };
+        match self.frequency {
+            Frequency::Daily => {
+                let date = dtstart + Duration::days(step as i64);
+                (date, [date].into_iter().filter(|d| matches_day(*d)).collect())
+            }
+            Frequency::Weekly => {
+                let monday = dtstart - Duration::days(dtstart.weekday().num_days_from_monday() as i64)
+                    + Duration::weeks(step as i64);
+                let mut dates: Vec<NaiveDate> = if self.by_day.is_empty() {
+                    vec![monday + Duration::days(dtstart.weekday().num_days_from_monday() as i64)]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|b| monday + Duration::days(b.weekday.num_days_from_monday() as i64))
+                        .collect()
+                };
+                dates.sort();
+                dates.dedup();
+                (monday, dates)
+            }
+            Frequency::Monthly => {
+                let first = dtstart
+                    .with_day(1)
+                    .and_then(|d| d.checked_add_months(Months::new(step)))
+                    .unwrap_or(NaiveDate::MAX);
+                if first == NaiveDate::MAX {
+                    return (first, vec![]);
+                }
+                let next_first = first
+                    .checked_add_months(Months::new(1))
+                    .unwrap_or(NaiveDate::MAX);
+                let month_days: Vec<NaiveDate> = first
+                    .iter_days()
+                    .take_while(|d| *d < next_first)
+                    .collect();
+
+                let mut dates: Vec<NaiveDate> = if self.by_day.is_empty() {
+                    // 当月没有该日（如 31 日）时跳过
+                    first.with_day(dtstart.day()).into_iter().collect()
+                } else {
+                    self.by_day
+                        .iter()
+                        .flat_map(|b| {
+                            let days: Vec<NaiveDate> = month_days
+                                .iter()
+                                .copied()
+                                .filter(|d| d.weekday() == b.weekday)
+                                .collect();
+                            match b.ordinal {
+                                None => days,
+                                Some(n) if n > 0 => days.get(n as usize - 1).copied().into_iter().collect(),
+                                Some(n) => days
+                                    .len()
+                                    .checked_sub(n.unsigned_abs() as usize)
+                                    .and_then(|i| days.get(i).copied())
+                                    .into_iter()
+                                    .collect(),
+                            }
+                        })
+                        .collect()
+                };
+                dates.sort();
+                dates.dedup();
+                (first, dates)
+            }
+        }
+    }
+
+    /// 包含 after 所在日期的周期序号（after 早于 dtstart 时为 0），此前的周期内日期均不晚于 after
+    fn period_containing(&self, dtstart: NaiveDate, after: NaiveDate) -> u32 {
+        let elapsed = match self.frequency {
+            Frequency::Daily => (after - dtstart).num_days(),
+            Frequency::Weekly => {
+                let monday = |d: NaiveDate| d - Duration::days(d.weekday().num_days_from_monday() as i64);
+                (monday(after) - monday(dtstart)).num_weeks()
+            }
+            Frequency::Monthly => {
+                (after.year() as i64 * 12 + after.month0() as i64)
+                    - (dtstart.year() as i64 * 12 + dtstart.month0() as i64)
+            }
+        };
+        (elapsed.max(0) / self.interval as i64) as u32
+    }
+
+    /// 系列中位于 (after, to] 区间内的日期（按规则计算，未做假期调整）。
+    ///
+    /// 与 RFC 5545 一致，dtstart 总是系列的第一个日期，并计入 COUNT。
+    /// 未设置 COUNT 时从 after 所在周期开始迭代；设置 COUNT 时需从头计数（最多 MAX_COUNT 个）。
+    pub fn dates_between(&self, dtstart: NaiveDate, after: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let end = self.until.map_or(to, |until| until.min(to));
+        let limit = self.count.unwrap_or(u32::MAX);
+        let mut dates = Vec::new();
+        if dtstart > end {
+            return dates;
+        }
+        if dtstart > after {
+            dates.push(dtstart);
+        }
+
+        let first_period = match self.count {
+            Some(_) => 0,
+            None => self.period_containing(dtstart, after),
+        };
+        let mut emitted = 1;
+        for period in first_period.. {
+            let (period_start, candidates) = self.period_dates(dtstart, period);
+            if period_start > end || emitted >= limit {
+                break;
+            }
+            for date in candidates.into_iter().filter(|d| *d > dtstart) {
+                if date > end || emitted >= limit {
+                    return dates;
+                }
+                emitted += 1;
+                if date > after {
+                    dates.push(date);
+                }
+            }
+        }
+        dates
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={};INTERVAL={}", frequency, self.interval)?;
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|b| {
+                    let name = WEEKDAYS
+                        .iter()
+                        .find(|(_, weekday)| *weekday == b.weekday)
+                        .map(|(name, _)| *name)
+                        .unwrap_or_default();
+                    match b.ordinal {
+                        Some(n) => format!("{}{}", n, name),
+                        None => name.to_string(),
+                    }
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        Ok(())
+    }
+}
+
+/// 按假期策略调整日期；跳过时返回 None
+pub fn apply_holiday_policy(
+    calendar: &WorkingCalendar,
+    date: NaiveDate,
+    policy: HolidayPolicy,
+) -> AppResult<Option<NaiveDate>> {
+    if policy == HolidayPolicy::Keep || calendar.is_working_day(date) {
+        return Ok(Some(date));
+    }
+    match policy {
+        HolidayPolicy::Skip => Ok(None),
+        HolidayPolicy::ShiftBackward => calendar.add_working_days(date, -1).map(Some),
+        _ => calendar.next_working_day(date).map(Some),
+    }
+}
+
+/// 待生成的任务：按规则计算的日期与调整后的实际日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedOccurrence {
+    pub occurrence_date: NaiveDate,
+    pub date: NaiveDate,
+}
+
+/// 规划 window = (after, to] 区间内待生成的任务，最多 limit 个。
+///
+/// `taken` 为已有任务的实际日期，调整后与之重复的日期不再生成。
+/// 返回待生成任务及本次已覆盖到的日期（达到 limit 时早于 to）。
+pub fn plan_occurrences(
+    rule: &RecurrenceRule,
+    dtstart: NaiveDate,
+    (after, to): (NaiveDate, NaiveDate),
+    calendar: &WorkingCalendar,
+    policy: HolidayPolicy,
+    mut taken: HashSet<NaiveDate>,
+    limit: usize,
+) -> AppResult<(Vec<PlannedOccurrence>, NaiveDate)> {
+    let mut planned = Vec::new();
+    let mut covered = after;
+    for occurrence_date in rule.dates_between(dtstart, after, to) {
+        if planned.len() >= limit {
+            return Ok((planned, covered));
+        }
+        covered = occurrence_date;
+        if let Some(date) = apply_holiday_policy(calendar, occurrence_date, policy)? {
+            if taken.insert(date) {
+                planned.push(PlannedOccurrence {
+                    occurrence_date,
+                    date,
+                });
+            }
+        }
+    }
+    Ok((planned, to.max(after)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::holiday::models::HolidayType;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_rules_and_holiday_policy() {
+        assert!(RecurrenceRule::parse("FREQ=YEARLY").is_err());
+        assert!(RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=1MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=3;UNTIL=20260201").is_err());
+
+        // 2026-01-05 为周一：每两周的周一、周三，共 5 次
+        let rule = RecurrenceRule::parse("rrule:freq=weekly;interval=2;byday=WE,MO;count=5").unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=WE,MO;COUNT=5");
+        assert_eq!(
+            rule.dates_between(date(1, 5), date(1, 4), date(12, 31)),
+            vec![date(1, 5), date(1, 7), date(1, 19), date(1, 21), date(2, 2)]
+        );
+        assert_eq!(
+            rule.dates_between(date(1, 5), date(1, 19), date(12, 31)),
+            vec![date(1, 21), date(2, 2)]
+        );
+
+        // 未设置 COUNT 时从 after 所在周期开始迭代
+        let rule = RecurrenceRule::parse("FREQ=DAILY;INTERVAL=2").unwrap();
+        assert_eq!(
+            rule.dates_between(date(1, 5), date(12, 1), date(12, 5)),
+            vec![date(12, 3), date(12, 5)]
+        );
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO,FR").unwrap();
+        assert_eq!(
+            rule.dates_between(date(1, 5), date(3, 2), date(3, 9)),
+            vec![date(3, 6), date(3, 9)]
+        );
+
+        // 每月最后一个周五，直到 4 月底
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20260430T000000Z").unwrap();
+        assert_eq!(
+            rule.dates_between(date(1, 30), date(1, 1), date(12, 31)),
+            vec![date(1, 30), date(2, 27), date(3, 27), date(4, 24)]
+        );
+
+        // 每月 31 日：没有 31 日的月份跳过
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY").unwrap();
+        assert_eq!(
+            rule.dates_between(date(1, 31), date(1, 31), date(5, 31)),
+            vec![date(3, 31), date(5, 31)]
+        );
+
+        // 每天：01-07 为假期，顺延后与 01-08 重复的不再生成
+        let calendar = WorkingCalendar::new([(date(1, 7), HolidayType::CompanyHoliday)]);
+        let rule = RecurrenceRule::parse("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR").unwrap();
+        let (planned, covered) = plan_occurrences(
+            &rule,
+            date(1, 5),
+            (date(1, 5), date(1, 9)),
+            &calendar,
+            HolidayPolicy::ShiftForward,
+            HashSet::from([date(1, 5)]),
+            10,
+        )
+        .unwrap();
+        let dates: Vec<NaiveDate> = planned.iter().map(|p| p.date).collect();
+        assert_eq!(dates, vec![date(1, 6), date(1, 8), date(1, 9)]);
+        assert_eq!(planned[1].occurrence_date, date(1, 7));
+        assert_eq!(covered, date(1, 9));
+
+        let (planned, covered) = plan_occurrences(
+            &rule,
+            date(1, 5),
+            (date(1, 5), date(1, 9)),
+            &calendar,
+            HolidayPolicy::Skip,
+            HashSet::new(),
+            2,
+        )
+        .unwrap();
+        let dates: Vec<NaiveDate> = planned.iter().map(|p| p.date).collect();
+        assert_eq!(dates, vec![date(1, 6), date(1, 8)]);
+        assert_eq!(covered, date(1, 8));
+    }
+}
@@ -1,9 +1,12 @@
-use crate::common::error::AppResult;
+use crate::common::error::{AppError, AppResult};
 use crate::modules::business::project::task::models::{
-    CreateTaskAttributeConfigParams, CreateTaskParams, Task, TaskAttributeConfig, TaskQueryParams,
+    AttributeType, CreateTaskAttributeConfigParams, CreateTaskParams, Task, TaskAttributeConfig, TaskQueryParams,
     UpdateTaskAttributeConfigParams, UpdateTaskParams,
 };
-use sqlx::PgPool;
+use crate::modules::business::project::task::query::{
+    AttributeFilter, FilterOp, FilterValue, SortField, TaskListQuery,
+};
+use sqlx::{PgConnection, PgPool};
 use sqlx::QueryBuilder;
 
 pub struct TaskRepository;
@@ -22,17 +25,222 @@ const CONFIG_RETURNING: &str = r#" RETURNING id, project_id, attribute_name, att
     COALESCE(value_color_map, 'null'::jsonb) AS value_color_map,
     "order", is_archived, creator_id, updater_id, create_date_time, update_date_time"#;
 
+/// project_tasks 表列清单，`$p` 为表别名前缀（如 "t."）
+macro_rules! task_columns {
+    ($p:literal) => {
+        concat!(
+            $p, "id, ", $p, "task_name, ", $p, "parent_id, ", $p, "project_id, ", $p, "\"order\",
+    ",
+            $p, "custom_attributes,
+    ",
+            $p, "start_date_time, ", $p, "end_date_time, ", $p, "task_type, ",
+            $p, "percent_complete, ", $p, "status_id,
+    ",
+            $p, "creator_id, ", $p, "updater_id, ", $p, "create_date_time, ", $p, "update_date_time"
+        )
+    };
+}
+
 /// project_tasks 表 SELECT 列
-const TASK_COLUMNS: &str = r#"id, task_name, parent_id, project_id, "order",
-    custom_attributes,
-    start_date_time, end_date_time, task_type,
-    creator_id, updater_id, create_date_time, update_date_time"#;
+const TASK_COLUMNS: &str = task_columns!("");
+
+/// project_tasks 表 SELECT 列（别名 t）
+const TASK_COLUMNS_T: &str = task_columns!("t.");
 
 /// project_tasks 表 RETURNING 列
-const TASK_RETURNING: &str = r#" RETURNING id, task_name, parent_id, project_id, "order",
-    custom_attributes,
-    start_date_time, end_date_time, task_type,
-    creator_id, updater_id, create_date_time, update_date_time"#;
+const TASK_RETURNING: &str = concat!(" RETURNING ", task_columns!(""));
+
+/// 追加任务列表的 WHERE 条件
+fn push_task_conditions(
+    qb: &mut QueryBuilder<sqlx::Postgres>,
+    project_id: i64,
+    params: &TaskQueryParams,
+    query: &TaskListQuery,
+) {
+    qb.push(" WHERE project_id = ");
+    qb.push_bind(project_id);
+    if let Some(ref name) = params.task_name {
+        qb.push(" AND task_name ILIKE ");
+        qb.push_bind(format!("%{}%", name));
+    }
+    if let Some(parent_id) = params.parent_id {
+        qb.push(" AND parent_id = ");
+        qb.push_bind(parent_id);
+    }
+    if let Some(task_type) = params.task_type {
+        qb.push(" AND task_type = ");
+        qb.push_bind(task_type);
+    }
+    if let Some(status_id) = params.status_id {
+        qb.push(" AND status_id = ");
+        qb.push_bind(status_id);
+    }
+    // 日期范围含两端：上界取次日 0 点（不含）
+    if let Some(from) = params.start_date_from {
+        qb.push(" AND start_date_time >= ");
+        qb.push_bind(from);
+    }
+    if let Some(to) = params.start_date_to {
+        qb.push(" AND start_date_time < ");
+        qb.push_bind(to);
+        qb.push("::date + 1");
+    }
+    if let Some(from) = params.end_date_from {
+        qb.push(" AND end_date_time >= ");
+        qb.push_bind(from);
+    }
+    if let Some(to) = params.end_date_to {
+        qb.push(" AND end_date_time < ");
+        qb.push_bind(to);
+        qb.push("::date + 1");
+    }
+    for filter in &query.filters {
+        push_attribute_filter(qb, filter);
+    }
+}
+
+/// 自定义属性取值表达式：数字属性仅在 JSON 类型为 number 时转换，避免历史脏数据导致类型转换失败
+fn push_attribute_expr(
+    qb: &mut QueryBuilder<sqlx::Postgres>,
+    name: &str,
+    attribute_type: AttributeType,
+) {
+    if attribute_type == AttributeType::Number {
+        qb.push("(CASE WHEN jsonb_typeof(custom_attributes -> ");
+        qb.push_bind(name.to_string());
+        qb.push(") = 'number' THEN (custom_attributes ->> ");
+        qb.push_bind(name.to_string());
+        qb.push(")::float8 END)");
+    } else {
+        qb.push("(custom_attributes ->> ");
+        qb.push_bind(name.to_string());
+        qb.push(")");
+    }
+}
+
+fn push_attribute_filter(qb: &mut QueryBuilder<sqlx::Postgres>, filter: &AttributeFilter) {
+    qb.push(" AND ");
+    push_attribute_expr(qb, &filter.attribute_name, filter.attribute_type);
+    match (&filter.value, filter.op) {
+        (FilterValue::List(values), _) => {
+            qb.push(" = ANY(");
+            qb.push_bind(values.clone());
+            qb.push(")");
+        }
+        (FilterValue::Text(text), FilterOp::Contains) => {
+            qb.push(" ILIKE ");
+            qb.push_bind(format!("%{}%", text));
+        }
+        (FilterValue::Text(text), op) => {
+            qb.push(op.as_sql());
+            qb.push_bind(text.clone());
+        }
+        (FilterValue::Number(number), op) => {
+            qb.push(op.as_sql());
+            qb.push_bind(*number);
+        }
+        (FilterValue::Bool(value), _) => {
+            qb.push(" = ");
+            qb.push_bind(value.to_string());
+        }
+    }
+}
+
+/// 追加 ORDER BY：先按请求的排序键，再按默认顺序
+fn push_task_order(qb: &mut QueryBuilder<sqlx::Postgres>, query: &TaskListQuery) {
+    qb.push(" ORDER BY ");
+    for key in &query.sort {
+        match &key.field {
+            SortField::Column(column) => {
+                qb.push(*column);
+            }
+            SortField::Attribute { name, attribute_type } => {
+                push_attribute_expr(qb, name, *attribute_type);
+            }
+        }
+        qb.push(if key.descending { " DESC NULLS LAST, " } else { " ASC NULLS LAST, " });
+    }
+    qb.push("\"order\" ASC NULLS LAST, create_date_time DESC");
+}
+
+/// 构建单个任务的 UPDATE ... RETURNING 语句
+fn build_update_task_query(
+    task_id: i64,
+    params: &UpdateTaskParams,
+    updater_id: i64,
+) -> QueryBuilder<'static, sqlx::Postgres> {
+    // 动态构建 SET 子句
+    let mut qb: QueryBuilder<sqlx::Postgres> =
+        QueryBuilder::new("UPDATE project_tasks SET ");
+    let mut has_set = false;
+
+    // NOT NULL 字段
+    if let Some(ref name) = params.task_name {
+        qb.push("task_name = ");
+        qb.push_bind(name.clone());
+        has_set = true;
+    }
+
+    // 可空字段：双层 Option
+    if let Some(ref pid_opt) = params.parent_id {
+        if has_set { qb.push(", "); }
+        qb.push("parent_id = ");
+        qb.push_bind(pid_opt.map(|id| id.0));
+        has_set = true;
+    }
+
+    // NOT NULL 字段
+    if let Some(ref ord) = params.order {
+        if has_set { qb.push(", "); }
+        qb.push("\"order\" = ");
+        qb.push_bind(*ord);
+        has_set = true;
+    }
+
+    if let Some(ref sdt) = params.start_date_time {
+        if has_set { qb.push(", "); }
+        qb.push("start_date_time = ");
+        qb.push_bind(*sdt);
+        has_set = true;
+    }
+
+    if let Some(ref edt) = params.end_date_time {
+        if has_set { qb.push(", "); }
+        qb.push("end_date_time = ");
+        qb.push_bind(*edt);
+        has_set = true;
+    }
+
+    if let Some(ref tt) = params.task_type {
+        if has_set { qb.push(", "); }
+        qb.push("task_type = ");
+        qb.push_bind(*tt);
+        has_set = true;
+    }
+
+    if let Some(pc) = params.percent_complete {
+        if has_set { qb.push(", "); }
+        qb.push("percent_complete = ");
+        qb.push_bind(pc);
+        has_set = true;
+    }
+
+    if let Some(ref ca) = params.custom_attributes {
+        if has_set { qb.push(", "); }
+        qb.push("custom_attributes = ");
+        qb.push_bind(ca.clone());
+        has_set = true;
+    }
+
+    if has_set { qb.push(", "); }
+    qb.push("updater_id = ");
+    qb.push_bind(updater_id);
+    qb.push(", update_date_time = CURRENT_TIMESTAMP WHERE id = ");
+    qb.push_bind(task_id);
+    qb.push(TASK_RETURNING);
+
+    qb
+}
 
 impl TaskRepository {
     pub async fn get_attribute_configs_by_project(
@@ -70,8 +278,24 @@ impl TaskRepository {
         Ok(config)
     }
 
-    pub async fn create_attribute_config(
+    pub async fn get_attribute_configs_by_ids(
         pool: &PgPool,
+        config_ids: &[i64],
+    ) -> AppResult<Vec<TaskAttributeConfig>> {
+        let sql = format!(
+            r#"SELECT {} FROM project_task_attribute_configs WHERE id = ANY($1)"#,
+            CONFIG_COLUMNS,
+        );
+        let configs = sqlx::query_as::<_, TaskAttributeConfig>(&sql)
+            .bind(config_ids)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(configs)
+    }
+
+    pub async fn create_attribute_config(
+        conn: &mut PgConnection,
         id: i64,
         project_id: i64,
         params: CreateTaskAttributeConfigParams,
@@ -96,14 +320,14 @@ impl TaskRepository {
         .bind(&params.value_color_map)
         .bind(params.order)
         .bind(creator_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(config)
     }
 
     pub async fn update_attribute_config(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         config_id: i64,
         params: UpdateTaskAttributeConfigParams,
         updater_id: i64,
@@ -165,110 +389,77 @@ impl TaskRepository {
 
         let config = qb
             .build_query_as::<TaskAttributeConfig>()
-            .fetch_one(pool)
+            .fetch_one(&mut *conn)
             .await?;
 
         Ok(config)
     }
 
-    pub async fn delete_attribute_config(pool: &PgPool, config_id: i64) -> AppResult<()> {
+    pub async fn delete_attribute_config(conn: &mut PgConnection, config_id: i64) -> AppResult<()> {
         sqlx::query("DELETE FROM project_task_attribute_configs WHERE id = $1")
             .bind(config_id)
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
 
         Ok(())
     }
 
-    pub async fn batch_delete_attribute_configs(pool: &PgPool, ids: Vec<i64>) -> AppResult<()> {
+    pub async fn batch_delete_attribute_configs(conn: &mut PgConnection, ids: Vec<i64>) -> AppResult<()> {
         sqlx::query("DELETE FROM project_task_attribute_configs WHERE id = ANY($1)")
             .bind(&ids)
-            .execute(pool)
-            .await?;
-
-        Ok(())
-    }
-
-    /// 软删除：将配置标记为已归档
-    pub async fn archive_attribute_config(pool: &PgPool, config_id: i64, updater_id: i64) -> AppResult<()> {
-        sqlx::query(
-            "UPDATE project_task_attribute_configs SET is_archived = true, updater_id = $2, update_date_time = CURRENT_TIMESTAMP WHERE id = $1"
-        )
-            .bind(config_id)
-            .bind(updater_id)
-            .execute(pool)
-            .await?;
-
-        Ok(())
-    }
-
-    /// 批量软删除
-    pub async fn batch_archive_attribute_configs(pool: &PgPool, ids: Vec<i64>, updater_id: i64) -> AppResult<()> {
-        sqlx::query(
-            "UPDATE project_task_attribute_configs SET is_archived = true, updater_id = $2, update_date_time = CURRENT_TIMESTAMP WHERE id = ANY($1)"
-        )
-            .bind(&ids)
-            .bind(updater_id)
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
 
         Ok(())
     }
 
-    /// 恢复已归档的配置
-    pub async fn restore_attribute_config(pool: &PgPool, config_id: i64, updater_id: i64) -> AppResult<()> {
-        sqlx::query(
-            "UPDATE project_task_attribute_configs SET is_archived = false, updater_id = $2, update_date_time = CURRENT_TIMESTAMP WHERE id = $1"
-        )
-            .bind(config_id)
+    /// 设置配置的归档状态（软删除 / 恢复），返回更新后的配置
+    pub async fn set_attribute_configs_archived(
+        conn: &mut PgConnection,
+        ids: &[i64],
+        is_archived: bool,
+        updater_id: i64,
+    ) -> AppResult<Vec<TaskAttributeConfig>> {
+        let sql = format!(
+            r#"UPDATE project_task_attribute_configs
+               SET is_archived = $2, updater_id = $3, update_date_time = CURRENT_TIMESTAMP
+               WHERE id = ANY($1){}"#,
+            CONFIG_RETURNING,
+        );
+        let configs = sqlx::query_as::<_, TaskAttributeConfig>(&sql)
+            .bind(ids)
+            .bind(is_archived)
             .bind(updater_id)
-            .execute(pool)
+            .fetch_all(&mut *conn)
             .await?;
 
-        Ok(())
+        Ok(configs)
     }
 
     pub async fn get_task_list(
         pool: &PgPool,
         project_id: i64,
         params: TaskQueryParams,
+        query: &TaskListQuery,
     ) -> AppResult<(Vec<Task>, i64)> {
         let page = params.page.unwrap_or(1);
         let page_size = params.per_page.unwrap_or(10);
         let offset = (page - 1) * page_size;
-        let task_name_pattern = params.task_name.as_ref().map(|t| format!("%{}%", t));
-        let tasks = sqlx::query_as::<_, Task>(
-            &format!(
-                r#"SELECT {}
-               FROM project_tasks
-               WHERE project_id = $1
-                 AND ($2::TEXT IS NULL OR task_name ILIKE $2)
-                 AND ($3::BIGINT IS NULL OR parent_id = $3)
-               ORDER BY "order" ASC NULLS LAST, create_date_time DESC
-               LIMIT $4 OFFSET $5"#,
-                TASK_COLUMNS,
-            ),
-        )
-        .bind(project_id)
-        .bind(&task_name_pattern)
-        .bind(params.parent_id)
-        .bind(page_size)
-        .bind(offset)
-        .fetch_all(pool)
-        .await?;
 
-        let total: (i64,) = sqlx::query_as(
-            r#"SELECT COUNT(*)
-               FROM project_tasks
-               WHERE project_id = $1
-                 AND ($2::TEXT IS NULL OR task_name ILIKE $2)
-                 AND ($3::BIGINT IS NULL OR parent_id = $3)"#,
-        )
-        .bind(project_id)
-        .bind(&task_name_pattern)
-        .bind(params.parent_id)
-        .fetch_one(pool)
-        .await?;
+        let mut qb: QueryBuilder<sqlx::Postgres> =
+            QueryBuilder::new(format!("SELECT {} FROM project_tasks", TASK_COLUMNS));
+        push_task_conditions(&mut qb, project_id, &params, query);
+        push_task_order(&mut qb, query);
+        qb.push(" LIMIT ");
+        qb.push_bind(page_size);
+        qb.push(" OFFSET ");
+        qb.push_bind(offset);
+        let tasks = qb.build_query_as::<Task>().fetch_all(pool).await?;
+
+        let mut qb: QueryBuilder<sqlx::Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM project_tasks");
+        push_task_conditions(&mut qb, project_id, &params, query);
+        let total: (i64,) = qb.build_query_as().fetch_one(pool).await?;
 
         Ok((tasks, total.0))
     }
@@ -277,25 +468,185 @@ impl TaskRepository {
         pool: &PgPool,
         project_id: i64,
         params: TaskQueryParams,
+        query: &TaskListQuery,
     ) -> AppResult<Vec<Task>> {
-        let task_name_pattern = params.task_name.as_ref().map(|t| format!("%{}%", t));
-        let tasks = sqlx::query_as::<_, Task>(
-            &format!(
-                r#"SELECT {}
-               FROM project_tasks
-               WHERE project_id = $1
-                 AND ($2::TEXT IS NULL OR task_name ILIKE $2)
-                 AND ($3::BIGINT IS NULL OR parent_id = $3)
+        let mut qb: QueryBuilder<sqlx::Postgres> =
+            QueryBuilder::new(format!("SELECT {} FROM project_tasks", TASK_COLUMNS));
+        push_task_conditions(&mut qb, project_id, &params, query);
+        push_task_order(&mut qb, query);
+        let tasks = qb.build_query_as::<Task>().fetch_all(pool).await?;
+
+        Ok(tasks)
+    }
+
+    /// 递归 CTE 沿 parent_id 查询任务树（root_id 为空时从顶层任务开始），按层级与顺序返回扁平列表
+    pub async fn get_task_tree(
+        pool: &PgPool,
+        project_id: i64,
+        root_id: Option<i64>,
+    ) -> AppResult<Vec<Task>> {
+        let sql = format!(
+            r#"WITH RECURSIVE tree AS (
+                   SELECT {}, ARRAY[id] AS path
+                   FROM project_tasks
+                   WHERE project_id = $1
+                     AND (CASE WHEN $2::BIGINT IS NULL THEN parent_id IS NULL ELSE id = $2 END)
+                   UNION ALL
+                   SELECT {}, tree.path || t.id
+                   FROM project_tasks t
+                   JOIN tree ON t.parent_id = tree.id
+                   WHERE t.project_id = $1 AND NOT t.id = ANY(tree.path)
+               )
+               SELECT {} FROM tree
+               ORDER BY array_length(path, 1) ASC, "order" ASC NULLS LAST, create_date_time DESC"#,
+            TASK_COLUMNS, TASK_COLUMNS_T, TASK_COLUMNS,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(project_id)
+            .bind(root_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(tasks)
+    }
+
+    /// 锁定项目中的任务（task_ids 为空时锁定全部任务，按 id 顺序加锁以避免死锁），按默认列表顺序返回；需在事务中调用
+    pub async fn lock_tasks(
+        conn: &mut PgConnection,
+        project_id: i64,
+        task_ids: Option<&[i64]>,
+    ) -> AppResult<Vec<Task>> {
+        let sql = format!(
+            r#"WITH locked AS (
+                   SELECT id FROM project_tasks
+                   WHERE project_id = $1 AND ($2::BIGINT[] IS NULL OR id = ANY($2))
+                   ORDER BY id
+                   FOR UPDATE
+               )
+               SELECT {} FROM project_tasks
+               WHERE id IN (SELECT id FROM locked)
                ORDER BY "order" ASC NULLS LAST, create_date_time DESC"#,
-                TASK_COLUMNS,
-            ),
+            TASK_COLUMNS,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(project_id)
+            .bind(task_ids)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(tasks)
+    }
+
+    /// 锁定任务及其全部祖先任务（按 id 顺序加锁以避免死锁），返回被锁定的任务 id；需在事务中调用
+    pub async fn lock_task_ancestors(
+        conn: &mut PgConnection,
+        project_id: i64,
+        task_ids: &[i64],
+    ) -> AppResult<Vec<i64>> {
+        let rows: Vec<(i64,)> = sqlx::query_as(
+            r#"WITH RECURSIVE ancestors AS (
+                   SELECT id, parent_id FROM project_tasks
+                   WHERE project_id = $1 AND id = ANY($2)
+                   UNION
+                   SELECT t.id, t.parent_id
+                   FROM project_tasks t
+                   JOIN ancestors a ON t.id = a.parent_id
+                   WHERE t.project_id = $1
+               )
+               SELECT id FROM project_tasks
+               WHERE id IN (SELECT id FROM ancestors)
+               ORDER BY id
+               FOR UPDATE"#,
         )
         .bind(project_id)
-        .bind(&task_name_pattern)
-        .bind(params.parent_id)
+        .bind(task_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// 以 root_ids 为根的子树中的全部任务（含根）
+    pub async fn get_subtrees(
+        conn: &mut PgConnection,
+        project_id: i64,
+        root_ids: &[i64],
+    ) -> AppResult<Vec<Task>> {
+        let sql = format!(
+            r#"WITH RECURSIVE tree AS (
+                   SELECT id FROM project_tasks
+                   WHERE project_id = $1 AND id = ANY($2)
+                   UNION
+                   SELECT t.id
+                   FROM project_tasks t
+                   JOIN tree ON t.parent_id = tree.id
+                   WHERE t.project_id = $1
+               )
+               SELECT {} FROM project_tasks
+               WHERE id IN (SELECT id FROM tree)
+               ORDER BY "order" ASC NULLS LAST, create_date_time DESC"#,
+            TASK_COLUMNS,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(project_id)
+            .bind(root_ids)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(tasks)
+    }
+
+    pub async fn get_tasks_by_types(
+        pool: &PgPool,
+        project_id: i64,
+        task_types: &[i32],
+    ) -> AppResult<Vec<Task>> {
+        let sql = format!(
+            r#"SELECT {} FROM project_tasks
+               WHERE project_id = $1 AND task_type = ANY($2)
+               ORDER BY start_date_time ASC"#,
+            TASK_COLUMNS,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(project_id)
+            .bind(task_types)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(tasks)
+    }
+
+    /// 返回 task_ids 中 user_id 为负责人（创建者或被指派人）的任务 ID
+    pub async fn get_owned_task_ids(
+        pool: &PgPool,
+        user_id: i64,
+        task_ids: &[i64],
+    ) -> AppResult<Vec<i64>> {
+        let ids: Vec<(i64,)> = sqlx::query_as(
+            r#"SELECT t.id FROM project_tasks t
+               WHERE t.id = ANY($2)
+                 AND (t.creator_id = $1
+                      OR EXISTS (SELECT 1 FROM project_task_assignees a
+                                 WHERE a.task_id = t.id AND a.user_id = $1))"#,
+        )
+        .bind(user_id)
+        .bind(task_ids)
         .fetch_all(pool)
         .await?;
 
+        Ok(ids.into_iter().map(|(id,)| id).collect())
+    }
+
+    pub async fn get_tasks_by_ids(pool: &PgPool, task_ids: &[i64]) -> AppResult<Vec<Task>> {
+        let sql = format!(
+            r#"SELECT {} FROM project_tasks WHERE id = ANY($1)"#,
+            TASK_COLUMNS,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(task_ids)
+            .fetch_all(pool)
+            .await?;
+
         Ok(tasks)
     }
 
@@ -312,21 +663,35 @@ impl TaskRepository {
         Ok(task)
     }
 
-    pub async fn create_task(
+    /// 查询任务并校验其属于指定项目，不存在或属于其他项目时返回 NotFound
+    pub async fn get_project_task(
         pool: &PgPool,
+        project_id: i64,
+        task_id: i64,
+    ) -> AppResult<Task> {
+        Self::get_task_by_id(pool, task_id)
+            .await?
+            .filter(|t| t.project_id.0 == project_id)
+            .ok_or_else(|| AppError::NotFound(format!("Task not found: {}", task_id)))
+    }
+
+    pub async fn create_task(
+        conn: &mut PgConnection,
         id: i64,
         project_id: i64,
         params: CreateTaskParams,
         creator_id: i64,
     ) -> AppResult<Task> {
         let custom_attrs = params.custom_attributes.unwrap_or(serde_json::json!({}));
+        // 新任务进入项目工作流的初始状态（项目尚无工作流时为 NULL）
         let sql = format!(
             r#"INSERT INTO project_tasks
                (id, task_name, parent_id, project_id, "order", custom_attributes,
-                start_date_time, end_date_time, task_type,
+                start_date_time, end_date_time, task_type, percent_complete, status_id,
                 creator_id, create_date_time)
                VALUES ($1, $2, $3, $4, $5, $6,
-                       $7, $8, $9,
+                       $7, $8, $9, $11,
+                       (SELECT id FROM project_workflow_statuses WHERE project_id = $4 AND is_initial),
                        $10, CURRENT_TIMESTAMP){}"#,
             TASK_RETURNING,
         );
@@ -341,14 +706,16 @@ impl TaskRepository {
         .bind(params.end_date_time)
         .bind(params.task_type)
         .bind(creator_id)
-        .fetch_one(pool)
+        .bind(params.percent_complete.unwrap_or(0.0))
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(task)
     }
 
-    pub async fn batch_create_tasks(
-        pool: &PgPool,
+    /// 批量插入任务
+    pub async fn insert_tasks(
+        conn: &mut PgConnection,
         tasks_with_ids: Vec<(i64, CreateTaskParams)>,
         project_id: i64,
         creator_id: i64,
@@ -360,7 +727,7 @@ impl TaskRepository {
         let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
             r#"INSERT INTO project_tasks
                (id, task_name, parent_id, project_id, "order", custom_attributes,
-                start_date_time, end_date_time, task_type,
+                start_date_time, end_date_time, task_type, percent_complete, status_id,
                 creator_id, create_date_time) "#,
         );
 
@@ -379,96 +746,187 @@ impl TaskRepository {
                 .push_bind(params.start_date_time)
                 .push_bind(params.end_date_time)
                 .push_bind(params.task_type)
+                .push_bind(params.percent_complete.unwrap_or(0.0))
+                .push("(SELECT id FROM project_workflow_statuses WHERE project_id = ")
+                .push_bind_unseparated(project_id)
+                .push_unseparated(" AND is_initial)")
                 .push_bind(creator_id)
                 .push("CURRENT_TIMESTAMP");
         });
 
         qb.push(TASK_RETURNING);
 
-        let tasks = qb.build_query_as::<Task>().fetch_all(pool).await?;
+        let tasks = qb.build_query_as::<Task>().fetch_all(&mut *conn).await?;
 
         Ok(tasks)
     }
 
     pub async fn update_task(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         task_id: i64,
         params: UpdateTaskParams,
         updater_id: i64,
     ) -> AppResult<Task> {
-        // 动态构建 SET 子句
-        let mut qb: QueryBuilder<sqlx::Postgres> =
-            QueryBuilder::new("UPDATE project_tasks SET ");
-        let mut has_set = false;
+        let task = build_update_task_query(task_id, &params, updater_id)
+            .build_query_as::<Task>()
+            .fetch_one(&mut *conn)
+            .await?;
 
-        // NOT NULL 字段
-        if let Some(ref name) = params.task_name {
-            qb.push("task_name = ");
-            qb.push_bind(name.clone());
-            has_set = true;
-        }
+        Ok(task)
+    }
 
-        // 可空字段：双层 Option
-        if let Some(ref pid_opt) = params.parent_id {
-            if has_set { qb.push(", "); }
-            qb.push("parent_id = ");
-            qb.push_bind(pid_opt.map(|id| id.0));
-            has_set = true;
+    /// 批量更新任务，按输入顺序返回；需在事务中调用以保证整体回滚
+    pub async fn batch_update_tasks(
+        conn: &mut PgConnection,
+        updates: Vec<(i64, UpdateTaskParams)>,
+        updater_id: i64,
+    ) -> AppResult<Vec<Task>> {
+        let mut tasks = Vec::with_capacity(updates.len());
+        for (task_id, params) in &updates {
+            let task = build_update_task_query(*task_id, params, updater_id)
+                .build_query_as::<Task>()
+                .fetch_one(&mut *conn)
+                .await?;
+            tasks.push(task);
         }
 
-        // NOT NULL 字段
-        if let Some(ref ord) = params.order {
-            if has_set { qb.push(", "); }
-            qb.push("\"order\" = ");
-            qb.push_bind(*ord);
-            has_set = true;
-        }
+        Ok(tasks)
+    }
 
-        if let Some(ref sdt) = params.start_date_time {
-            if has_set { qb.push(", "); }
-            qb.push("start_date_time = ");
-            qb.push_bind(*sdt);
-            has_set = true;
+    /// 批量更新任务起止时间（单条 UPDATE，整体原子生效）
+    pub async fn batch_update_task_dates(
+        conn: &mut PgConnection,
+        dates: Vec<(i64, chrono::NaiveDateTime, chrono::NaiveDateTime)>,
+        updater_id: i64,
+    ) -> AppResult<Vec<Task>> {
+        if dates.is_empty() {
+            return Ok(vec![]);
         }
 
-        if let Some(ref edt) = params.end_date_time {
-            if has_set { qb.push(", "); }
-            qb.push("end_date_time = ");
-            qb.push_bind(*edt);
-            has_set = true;
+        let mut ids = Vec::with_capacity(dates.len());
+        let mut starts = Vec::with_capacity(dates.len());
+        let mut ends = Vec::with_capacity(dates.len());
+        for (id, start, end) in dates {
+            ids.push(id);
+            starts.push(start);
+            ends.push(end);
         }
 
-        if let Some(ref tt) = params.task_type {
-            if has_set { qb.push(", "); }
-            qb.push("task_type = ");
-            qb.push_bind(*tt);
-            has_set = true;
-        }
+        let sql = format!(
+            r#"UPDATE project_tasks
+               SET start_date_time = data.new_start,
+                   end_date_time = data.new_end,
+                   updater_id = $4,
+                   update_date_time = CURRENT_TIMESTAMP
+               FROM unnest($1::bigint[], $2::timestamp[], $3::timestamp[])
+                   AS data(task_id, new_start, new_end)
+               WHERE project_tasks.id = data.task_id{}"#,
+            TASK_RETURNING,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(&ids)
+            .bind(&starts)
+            .bind(&ends)
+            .bind(updater_id)
+            .fetch_all(&mut *conn)
+            .await?;
 
-        if let Some(ref ca) = params.custom_attributes {
-            if has_set { qb.push(", "); }
-            qb.push("custom_attributes = ");
-            qb.push_bind(ca.clone());
-            has_set = true;
-        }
+        Ok(tasks)
+    }
 
-        if has_set { qb.push(", "); }
-        qb.push("updater_id = ");
-        qb.push_bind(updater_id);
-        qb.push(", update_date_time = CURRENT_TIMESTAMP WHERE id = ");
-        qb.push_bind(task_id);
-        qb.push(TASK_RETURNING);
+    /// 更新任务的工作流状态；任务当前状态已不是 expected_from（并发流转）时返回 Conflict
+    pub async fn update_task_status(
+        conn: &mut PgConnection,
+        task_id: i64,
+        expected_from: Option<i64>,
+        status_id: i64,
+        updater_id: i64,
+    ) -> AppResult<Task> {
+        let sql = format!(
+            r#"UPDATE project_tasks
+               SET status_id = $2, updater_id = $3, update_date_time = CURRENT_TIMESTAMP
+               WHERE id = $1 AND status_id IS NOT DISTINCT FROM $4{}"#,
+            TASK_RETURNING,
+        );
+        sqlx::query_as::<_, Task>(&sql)
+            .bind(task_id)
+            .bind(status_id)
+            .bind(updater_id)
+            .bind(expected_from)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Task status was changed concurrently, please retry".to_string())
+            })
+    }
 
-        let task = qb
-            .build_query_as::<Task>()
-            .fetch_one(pool)
+    /// 同一父任务下兄弟任务的最大 order；没有兄弟任务时返回 None
+    pub async fn get_max_child_order(
+        conn: &mut PgConnection,
+        project_id: i64,
+        parent_id: Option<i64>,
+    ) -> AppResult<Option<f64>> {
+        let result: (Option<f64>,) = sqlx::query_as(
+            r#"SELECT MAX("order") FROM project_tasks
+               WHERE project_id = $1 AND parent_id IS NOT DISTINCT FROM $2"#,
+        )
+        .bind(project_id)
+        .bind(parent_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(result.0)
+    }
+
+    /// 移动任务：更新父任务并批量写入 order，返回 order 有变化的全部任务；需在事务中调用
+    pub async fn move_task(
+        conn: &mut PgConnection,
+        task_id: i64,
+        parent_id: Option<i64>,
+        orders: Vec<(i64, f64)>,
+        updater_id: i64,
+    ) -> AppResult<Vec<Task>> {
+        let (ids, values): (Vec<i64>, Vec<f64>) = orders.into_iter().unzip();
+
+        let sql = format!(
+            r#"UPDATE project_tasks
+               SET "order" = data.new_order,
+                   updater_id = $3,
+                   update_date_time = CURRENT_TIMESTAMP
+               FROM unnest($1::bigint[], $2::float8[]) AS data(task_id, new_order)
+               WHERE project_tasks.id = data.task_id{}"#,
+            TASK_RETURNING,
+        );
+        let mut tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(&ids)
+            .bind(&values)
+            .bind(updater_id)
+            .fetch_all(&mut *conn)
             .await?;
 
-        Ok(task)
+        let sql = format!(
+            r#"UPDATE project_tasks
+               SET parent_id = $2, updater_id = $3, update_date_time = CURRENT_TIMESTAMP
+               WHERE id = $1{}"#,
+            TASK_RETURNING,
+        );
+        let task = sqlx::query_as::<_, Task>(&sql)
+            .bind(task_id)
+            .bind(parent_id)
+            .bind(updater_id)
+            .fetch_one(&mut *conn)
+            .await?;
+        match tasks.iter_mut().find(|t| t.id == task.id) {
+            Some(t) => *t = task,
+            None => tasks.push(task),
+        }
+
+        Ok(tasks)
     }
 
-    pub async fn delete_task(pool: &PgPool, task_id: i64) -> AppResult<()> {
-        sqlx::query(
+    /// 删除任务及其子任务，返回被删除的任务
+    pub async fn delete_task(conn: &mut PgConnection, task_id: i64) -> AppResult<Vec<Task>> {
+        let sql = format!(
             r#"
             WITH RECURSIVE subtree AS (
                 SELECT id, project_id
@@ -482,20 +940,26 @@ impl TaskRepository {
             )
             DELETE FROM project_tasks
             WHERE id IN (SELECT id FROM subtree)
-            "#,
-        )
-        .bind(task_id)
-        .execute(pool)
-        .await?;
+            {}"#,
+            TASK_RETURNING,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(task_id)
+            .fetch_all(&mut *conn)
+            .await?;
 
-        Ok(())
+        Ok(tasks)
     }
 
-    pub async fn batch_delete_tasks(pool: &PgPool, task_ids: Vec<i64>) -> AppResult<()> {
+    /// 批量删除任务及其子任务，返回被删除的任务
+    pub async fn batch_delete_tasks(
+        conn: &mut PgConnection,
+        task_ids: Vec<i64>,
+    ) -> AppResult<Vec<Task>> {
         if task_ids.is_empty() {
-            return Ok(());
+            return Ok(vec![]);
         }
-        sqlx::query(
+        let sql = format!(
             r#"
             WITH RECURSIVE roots AS (
                 SELECT id, project_id
@@ -513,12 +977,30 @@ impl TaskRepository {
             )
             DELETE FROM project_tasks
             WHERE id IN (SELECT DISTINCT id FROM subtree)
-            "#,
-        )
-        .bind(&task_ids)
-        .execute(pool)
-        .await?;
+            {}"#,
+            TASK_RETURNING,
+        );
+        let tasks = sqlx::query_as::<_, Task>(&sql)
+            .bind(&task_ids)
+            .fetch_all(&mut *conn)
+            .await?;
 
-        Ok(())
+        Ok(tasks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_recursive_columns_match_task_columns() {
+        let anchor: Vec<&str> = TASK_COLUMNS.split(',').map(str::trim).collect();
+        let recursive: Vec<&str> = TASK_COLUMNS_T.split(',').map(str::trim).collect();
+        assert_eq!(anchor.len(), recursive.len());
+        for (a, r) in anchor.iter().zip(&recursive) {
+            assert_eq!(Some(*a), r.strip_prefix("t."));
+        }
+        assert_eq!(TASK_RETURNING, format!(" RETURNING {}", TASK_COLUMNS));
     }
 }
@@ -52,6 +52,26 @@ pub fn task_routes(state: AppState) -> Router {
             "/projects/{project_id}/tasks/all",
             get(handlers::get_all_tasks),
         )
+        .route(
+            "/projects/{project_id}/tasks/tree",
+            get(handlers::get_task_tree),
+        )
+        .route(
+            "/projects/{project_id}/tasks/critical-path",
+            get(handlers::get_critical_path),
+        )
+        .route(
+            "/projects/{project_id}/tasks/earned-value",
+            get(handlers::get_earned_value),
+        )
+        .route(
+            "/projects/{project_id}/tasks/schedule",
+            post(handlers::apply_schedule),
+        )
+        .route(
+            "/projects/{project_id}/tasks/schedule/preview",
+            post(handlers::preview_schedule),
+        )
         .route("/projects/{project_id}/tasks", post(handlers::create_task))
         .route(
             "/projects/{project_id}/tasks/{task_id}",
@@ -65,10 +85,18 @@ pub fn task_routes(state: AppState) -> Router {
             "/projects/{project_id}/tasks/{task_id}",
             delete(handlers::delete_task),
         )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/move",
+            post(handlers::move_task),
+        )
         .route(
             "/projects/{project_id}/tasks/batch-create",
             post(handlers::batch_create_tasks),
         )
+        .route(
+            "/projects/{project_id}/tasks/batch-update",
+            post(handlers::batch_update_tasks),
+        )
         .route(
             "/projects/{project_id}/tasks/batch-delete",
             post(handlers::batch_delete_tasks),
@@ -0,0 +1,291 @@
+use crate::common::error::{field_error, AppError, AppResult};
+use crate::common::id::Id;
+use crate::modules::business::project::task::critical_path::topological_order;
+use crate::modules::business::project::task::dependency::models::{DependencyType, TaskDependency};
+use crate::modules::business::project::task::models::{Task, TaskType};
+use crate::modules::holiday::calendar::{WorkingCalendar, MAX_WORKING_DAYS};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 单个任务的排期变更
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleChange {
+    pub task_id: Id,
+    pub task_name: String,
+    pub old_start_date_time: NaiveDateTime,
+    pub old_end_date_time: NaiveDateTime,
+    pub new_start_date_time: NaiveDateTime,
+    pub new_end_date_time: NaiveDateTime,
+}
+
+/// 任务工期（工作日）：start 与 end 所在日期的闭区间内的工作日数，至少为 1
+pub fn working_duration(calendar: &WorkingCalendar, task: &Task) -> AppResult<i64> {
+    working_days_between(calendar, task.start_date_time.date(), task.end_date_time.date())
+}
+
+/// [start, end] 闭区间内的工作日数，至少为 1
+pub fn working_days_between(
+    calendar: &WorkingCalendar,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> AppResult<i64> {
+    Ok(calendar.count_working_days(start, end)?.max(1))
+}
+
+fn out_of_range(field: &str, message: String) -> AppError {
+    AppError::ValidationError(
+        message.clone(),
+        vec![field_error(field, "out_of_range", message)],
+    )
+}
+
+/// 计算移动 / 调整工期后的排期：
+///
+/// 1. 源任务开始日期顺延到工作日，结束日期按工期（工作日）推算；
+/// 2. 按拓扑顺序处理其所有下游任务，仅在依赖约束被违反时向后推移，保持各自工期不变；
+/// 3. lag_days 按工作日计算，开始 / 结束的时刻（时分秒）保持不变；里程碑的结束时间与开始时间相同。
+///
+/// 返回日期发生变化的任务列表（含源任务）。
+pub fn reschedule(
+    tasks: &[Task],
+    dependencies: &[TaskDependency],
+    calendar: &WorkingCalendar,
+    task_id: i64,
+    new_start: Option<NaiveDateTime>,
+    duration_days: Option<i64>,
+) -> AppResult<Vec<ScheduleChange>> {
+    let task_ids: Vec<i64> = tasks.iter().map(|t| t.id.0).collect();
+    let order = topological_order(&task_ids, dependencies)?;
+    let index: HashMap<i64, usize> = task_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let source = *index
+        .get(&task_id)
+        .ok_or_else(|| AppError::NotFound(format!("Task not found: {}", task_id)))?;
+
+    let mut incoming: Vec<Vec<&TaskDependency>> = vec![Vec::new(); tasks.len()];
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+    for dep in dependencies {
+        if let (Some(&from), Some(&to)) = (
+            index.get(&dep.predecessor_id.0),
+            index.get(&dep.successor_id.0),
+        ) {
+            if (dep.lag_days as i64).abs() > MAX_WORKING_DAYS {
+                return Err(out_of_range(
+                    "lagDays",
+                    format!(
+                        "Dependency {} lag must be between -{} and {} working days",
+                        dep.id, MAX_WORKING_DAYS, MAX_WORKING_DAYS
+                    ),
+                ));
+            }
+            incoming[to].push(dep);
+            successors[from].push(to);
+        }
+    }
+
+    let mut starts: Vec<NaiveDate> = tasks.iter().map(|t| t.start_date_time.date()).collect();
+    let mut ends: Vec<NaiveDate> = tasks.iter().map(|t| t.end_date_time.date()).collect();
+    let mut start_times: Vec<NaiveDateTime> = tasks.iter().map(|t| t.start_date_time).collect();
+
+    // 源任务
+    let duration = match duration_days {
+        Some(duration) => duration,
+        None => working_duration(calendar, &tasks[source])?,
+    };
+    let is_zero_duration = |task: &Task| {
+        TaskType::from_i32(task.task_type).is_some_and(TaskType::is_zero_duration)
+    };
+    if is_zero_duration(&tasks[source]) && duration != 1 {
+        let message = "Milestone or checkpoint duration cannot be changed".to_string();
+        return Err(AppError::ValidationError(
+            message.clone(),
+            vec![field_error("durationDays", "zero_duration", message)],
+        ));
+    }
+    if duration < 1 {
+        return Err(out_of_range(
+            "durationDays",
+            "Duration must be at least 1 working day".to_string(),
+        ));
+    }
+    if duration > MAX_WORKING_DAYS {
+        return Err(out_of_range(
+            "durationDays",
+            format!("Duration must not exceed {} working days", MAX_WORKING_DAYS),
+        ));
+    }
+    if let Some(start) = new_start {
+        start_times[source] = start;
+    }
+    starts[source] = calendar.next_working_day(start_times[source].date())?;
+    ends[source] = calendar.add_working_days(starts[source], duration - 1)?;
+
+    // 标记所有下游任务
+    let mut affected = vec![false; tasks.len()];
+    affected[source] = true;
+    for &i in &order {
+        if affected[i] {
+            for &j in &successors[i] {
+                affected[j] = true;
+            }
+        }
+    }
+
+    for &i in &order {
+        if i == source || !affected[i] {
+            continue;
+        }
+        let duration = working_duration(calendar, &tasks[i])?;
+        let required_start = incoming[i]
+            .iter()
+            .map(|dep| {
+                let p = index[&dep.predecessor_id.0];
+                let lag = dep.lag_days as i64;
+                let dependency_type = DependencyType::from_i32(dep.dependency_type)
+                    .unwrap_or(DependencyType::FinishToStart);
+                match dependency_type {
+                    DependencyType::FinishToStart => calendar.add_working_days(ends[p], lag + 1),
+                    DependencyType::StartToStart => calendar.add_working_days(starts[p], lag),
+                    DependencyType::FinishToFinish => calendar.add_working_days(
+                        calendar.add_working_days(ends[p], lag)?,
+                        -(duration - 1),
+                    ),
+                    DependencyType::StartToFinish => calendar.add_working_days(
+                        calendar.add_working_days(starts[p], lag - 1)?,
+                        -(duration - 1),
+                    ),
+                }
+            })
+            .collect::<AppResult<Vec<NaiveDate>>>()?
+            .into_iter()
+            .max();
+
+        if let Some(required) = required_start {
+            if required > starts[i] {
+                starts[i] = calendar.next_working_day(required)?;
+                ends[i] = calendar.add_working_days(starts[i], duration - 1)?;
+            }
+        }
+    }
+
+    let changes = tasks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, task)| {
+            let new_start = starts[i].and_time(start_times[i].time());
+            // 里程碑与检查点为时间点，结束时间始终与开始时间相同
+            let new_end = if is_zero_duration(task) {
+                new_start
+            } else {
+                ends[i].and_time(task.end_date_time.time())
+            };
+            if new_start == task.start_date_time && new_end == task.end_date_time {
+                return None;
+            }
+            Some(ScheduleChange {
+                task_id: task.id,
+                task_name: task.task_name.clone(),
+                old_start_date_time: task.start_date_time,
+                old_end_date_time: task.end_date_time,
+                new_start_date_time: new_start,
+                new_end_date_time: new_end,
+            })
+        })
+        .collect();
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::holiday::models::HolidayType;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    fn task(id: i64, start_day: u32, end_day: u32) -> Task {
+        Task {
+            start_date_time: date(start_day).and_hms_opt(9, 0, 0).unwrap(),
+            end_date_time: date(end_day).and_hms_opt(18, 0, 0).unwrap(),
+            ..Task::test(id)
+        }
+    }
+
+    #[test]
+    fn test_shift_successor_over_weekend_and_holiday() {
+        // 2026-01-05 周一；1/12 为假期
+        let calendar = WorkingCalendar::new([(date(12), HolidayType::PublicHoliday)]);
+        let tasks = vec![task(1, 5, 7), task(2, 8, 9)];
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToStart, 0)];
+
+        // 任务 1 工期延长到 4 个工作日：1/5 - 1/8，任务 2 顺延到 1/9 - 1/13（跳过周末与 1/12）
+        let changes = reschedule(&tasks, &deps, &calendar, 1, None, Some(4)).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].new_end_date_time.date(), date(8));
+        assert_eq!(changes[1].new_start_date_time.date(), date(9));
+        assert_eq!(changes[1].new_end_date_time.date(), date(13));
+        assert_eq!(changes[1].new_start_date_time.time(), tasks[1].start_date_time.time());
+    }
+
+    #[test]
+    fn test_successor_with_slack_is_not_moved() {
+        let calendar = WorkingCalendar::default();
+        let tasks = vec![task(1, 5, 6), task(2, 12, 13)];
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToStart, 0)];
+
+        let start = date(6).and_hms_opt(9, 0, 0).unwrap();
+        let changes = reschedule(&tasks, &deps, &calendar, 1, Some(start), None).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].new_end_date_time.date(), date(7));
+    }
+
+    #[test]
+    fn test_milestone_and_checkpoint_keep_zero_duration() {
+        let calendar = WorkingCalendar::default();
+        for task_type in [TaskType::Milestone, TaskType::Checkpoint] {
+            let mut point = task(2, 7, 7);
+            point.task_type = task_type.as_i32();
+            point.end_date_time = point.start_date_time;
+            let tasks = vec![task(1, 5, 6), point];
+            let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToStart, 0)];
+
+            let changes = reschedule(&tasks, &deps, &calendar, 1, None, Some(3)).unwrap();
+            assert_eq!(changes[1].new_start_date_time.date(), date(8));
+            assert_eq!(changes[1].new_end_date_time, changes[1].new_start_date_time);
+            assert!(matches!(
+                reschedule(&tasks, &deps, &calendar, 2, None, Some(2)),
+                Err(AppError::ValidationError(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_start_on_weekend_is_snapped() {
+        let calendar = WorkingCalendar::default();
+        let tasks = vec![task(1, 5, 5)];
+        let start = date(10).and_hms_opt(9, 0, 0).unwrap();
+        let changes = reschedule(&tasks, &[], &calendar, 1, Some(start), None).unwrap();
+        assert_eq!(changes[0].new_start_date_time.date(), date(12));
+        assert_eq!(changes[0].new_end_date_time.date(), date(12));
+    }
+
+    #[test]
+    fn test_rejects_unbounded_duration_and_lag() {
+        let calendar = WorkingCalendar::default();
+        let tasks = vec![task(1, 5, 6), task(2, 7, 8)];
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToStart, 0)];
+        assert!(matches!(
+            reschedule(&tasks, &deps, &calendar, 1, None, Some(i64::MAX)),
+            Err(AppError::ValidationError(..))
+        ));
+
+        let deps = vec![TaskDependency::test(1, 2, DependencyType::FinishToStart, i32::MAX)];
+        assert!(matches!(
+            reschedule(&tasks, &deps, &calendar, 1, None, Some(3)),
+            Err(AppError::ValidationError(..))
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::models::{Task, TaskRollup, TaskTreeNode, TaskType};
+use crate::modules::business::project::task::scheduler::working_duration;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use chrono::NaiveDateTime;
+use serde_json::Value;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+fn numeric_value(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+    .filter(|n| n.is_finite())
+}
+
+/// 按 `parent_id` 分组：父任务不在列表中的任务作为根节点，子节点保持输入顺序。
+/// 环中的任务无法从根节点到达。
+pub fn group_by_parent<T: Borrow<Task>>(tasks: Vec<T>) -> (Vec<T>, HashMap<i64, Vec<T>>) {
+    let ids: HashSet<i64> = tasks.iter().map(|t| t.borrow().id.0).collect();
+    let mut children: HashMap<i64, Vec<T>> = HashMap::new();
+    let mut roots = Vec::new();
+    for task in tasks {
+        match task.borrow().parent_id.filter(|p| ids.contains(&p.0)) {
+            Some(parent_id) => children.entry(parent_id.0).or_default().push(task),
+            None => roots.push(task),
+        }
+    }
+    (roots, children)
+}
+
+/// 构建任务树，根节点与子节点按 [`group_by_parent`] 分组
+///
+/// `numeric_attributes` 为项目中 number 类型的属性名，用于汇总；完成百分比按叶子任务工期（工作日，
+/// 由 `calendar` 计算）加权，里程碑与检查点权重为 0。环中的任务无法从根节点到达，会被忽略。
+pub fn build_task_tree(
+    tasks: Vec<Task>,
+    numeric_attributes: &[String],
+    calendar: &WorkingCalendar,
+) -> AppResult<Vec<TaskTreeNode>> {
+    let (roots, mut children) = group_by_parent(tasks);
+
+    roots
+        .into_iter()
+        .map(|task| build_node(task, &mut children, numeric_attributes, calendar))
+        .collect()
+}
+
+fn build_node(
+    task: Task,
+    children: &mut HashMap<i64, Vec<Task>>,
+    numeric_attributes: &[String],
+    calendar: &WorkingCalendar,
+) -> AppResult<TaskTreeNode> {
+    let child_nodes = children
+        .remove(&task.id.0)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_node(child, children, numeric_attributes, calendar))
+        .collect::<AppResult<Vec<TaskTreeNode>>>()?;
+
+    let rollup = if child_nodes.is_empty() {
+        let zero_duration =
+            TaskType::from_i32(task.task_type).is_some_and(TaskType::is_zero_duration);
+        TaskRollup {
+            start_date_time: task.start_date_time,
+            end_date_time: task.end_date_time,
+            child_count: 0,
+            descendant_count: 0,
+            attributes: numeric_attributes
+                .iter()
+                .filter_map(|name| {
+                    numeric_value(task.custom_attributes.get(name.as_str()))
+                        .map(|v| (name.clone(), v))
+                })
+                .collect(),
+            // 里程碑与检查点不占工期，不参与进度加权（与挣值计算一致）
+            work_days: if zero_duration {
+                0
+            } else {
+                working_duration(calendar, &task)?
+            },
+            progress: task.percent_complete.clamp(0.0, 100.0),
+        }
+    } else {
+        rollup_children(&child_nodes)
+    };
+
+    Ok(TaskTreeNode {
+        task,
+        rollup,
+        children: child_nodes,
+    })
+}
+
+fn rollup_children(child_nodes: &[TaskTreeNode]) -> TaskRollup {
+    let mut attributes: BTreeMap<String, f64> = BTreeMap::new();
+    let mut weighted = 0.0;
+    let mut work_days = 0;
+    for child in child_nodes {
+        for (name, value) in &child.rollup.attributes {
+            *attributes.entry(name.clone()).or_default() += value;
+        }
+        weighted += child.rollup.progress * child.rollup.work_days as f64;
+        work_days += child.rollup.work_days;
+    }
+
+    TaskRollup {
+        start_date_time: child_nodes
+            .iter()
+            .map(|c| c.rollup.start_date_time)
+            .min()
+            .unwrap_or_default(),
+        end_date_time: child_nodes
+            .iter()
+            .map(|c| c.rollup.end_date_time)
+            .max()
+            .unwrap_or_default(),
+        child_count: child_nodes.len() as i64,
+        descendant_count: child_nodes
+            .iter()
+            .map(|c| c.rollup.descendant_count + 1)
+            .sum(),
+        attributes,
+        work_days,
+        progress: if work_days > 0 {
+            weighted / work_days as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+/// 起止时间与子任务汇总不一致的父任务：(task_id, 新开始时间, 新结束时间)
+pub fn parent_date_changes(nodes: &[TaskTreeNode]) -> Vec<(i64, NaiveDateTime, NaiveDateTime)> {
+    let mut changes = Vec::new();
+    let mut stack: Vec<&TaskTreeNode> = nodes.iter().collect();
+    while let Some(node) = stack.pop() {
+        // 里程碑与检查点为固定时间点，不随子任务同步
+        if !node.children.is_empty()
+            && !TaskType::from_i32(node.task.task_type).is_some_and(TaskType::is_zero_duration)
+            && (node.task.start_date_time != node.rollup.start_date_time
+                || node.task.end_date_time != node.rollup.end_date_time)
+        {
+            changes.push((
+                node.task.id.0,
+                node.rollup.start_date_time,
+                node.rollup.end_date_time,
+            ));
+        }
+        stack.extend(node.children.iter());
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::id::Id;
+    use crate::modules::holiday::models::HolidayType;
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn task(id: i64, parent_id: Option<i64>, start_day: u32, end_day: u32, attrs: Value) -> Task {
+        let date = |day| NaiveDate::from_ymd_opt(2026, 1, day).unwrap();
+        Task {
+            parent_id: parent_id.map(Id),
+            custom_attributes: attrs,
+            start_date_time: date(start_day).and_hms_opt(0, 0, 0).unwrap(),
+            end_date_time: date(end_day).and_hms_opt(0, 0, 0).unwrap(),
+            ..Task::test(id)
+        }
+    }
+
+    #[test]
+    fn test_build_tree_rollups() {
+        let mut tasks = vec![
+            task(1, None, 10, 11, json!({})),
+            task(2, Some(1), 5, 7, json!({"cost": 3})),
+            task(3, Some(1), 7, 13, json!({"cost": "4.5"})),
+            task(4, Some(3), 8, 9, json!({"cost": 2})),
+            task(5, Some(99), 1, 2, json!({})),
+        ];
+        tasks[1].percent_complete = 100.0;
+        // 父任务自身的进度不参与汇总
+        tasks[2].percent_complete = 80.0;
+        let attributes = vec!["cost".to_string()];
+        // 2026-01-06 为假期
+        let holiday = NaiveDate::from_ymd_opt(2026, 1, 6).unwrap();
+        let calendar = WorkingCalendar::new([(holiday, HolidayType::CompanyHoliday)]);
+        let tree = build_task_tree(tasks, &attributes, &calendar).unwrap();
+
+        assert_eq!(tree.len(), 2);
+        let root = &tree[0];
+        assert_eq!(root.rollup.child_count, 2);
+        assert_eq!(root.rollup.descendant_count, 3);
+        assert_eq!(root.rollup.start_date_time.date().to_string(), "2026-01-05");
+        assert_eq!(root.rollup.end_date_time.date().to_string(), "2026-01-09");
+        // 任务 3 的汇总来自其子任务 4，自身的 cost 不计入
+        assert_eq!(root.rollup.attributes["cost"], 5.0);
+        // 任务 2 工期 2 个工作日（01-06 为假期）完成 100%，任务 3 汇总工期 2 个工作日完成 0%
+        assert_eq!(root.rollup.work_days, 4);
+        assert_eq!(root.children[1].rollup.progress, 0.0);
+        assert!((root.rollup.progress - 50.0).abs() < 1e-9);
+
+        let changes = parent_date_changes(&tree);
+        let mut changed: Vec<i64> = changes.iter().map(|c| c.0).collect();
+        changed.sort();
+        assert_eq!(changed, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_zero_duration_leaves_have_no_progress_weight() {
+        let mut tasks = vec![
+            task(1, None, 5, 9, json!({})),
+            task(2, Some(1), 5, 9, json!({})),
+            task(3, Some(1), 9, 9, json!({})),
+            task(4, Some(1), 9, 9, json!({})),
+        ];
+        tasks[1].percent_complete = 100.0;
+        tasks[2].task_type = TaskType::Milestone.as_i32();
+        tasks[3].task_type = TaskType::Checkpoint.as_i32();
+        let tree = build_task_tree(tasks, &[], &WorkingCalendar::default()).unwrap();
+
+        let root = &tree[0];
+        assert_eq!(root.children[1].rollup.work_days, 0);
+        assert_eq!(root.children[2].rollup.work_days, 0);
+        assert_eq!(root.rollup.work_days, 5);
+        assert!((root.rollup.progress - 100.0).abs() < 1e-9);
+    }
+}
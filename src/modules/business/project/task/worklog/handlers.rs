@@ -0,0 +1,367 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{field_error, AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::{ApiResponse, PaginatedResponse};
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::permission::repository::ProjectPermissionResolver;
+use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::business::project::task::worklog::models::{
+    CreateWorklogParams, NewWorklog, Timesheet, TimesheetQueryParams, UpdateWorklogParams,
+    Worklog, WorklogExportParams, WorklogQueryParams, MAX_EXPORT_DAYS, MAX_MINUTES_PER_DAY,
+    MAX_NOTE_LENGTH,
+};
+use crate::modules::business::project::task::worklog::report::{
+    build_timesheet, export_csv, week_start,
+};
+use crate::modules::business::project::task::worklog::repository::WorklogRepository;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use crate::modules::holiday::holiday_calendar::repository::HolidayCalendarRepository;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{Duration, NaiveDate};
+use sqlx::{PgConnection, PgPool};
+
+/// 查询工时记录并校验其属于当前项目的指定任务
+async fn get_task_worklog(
+    pool: &PgPool,
+    project_id: i64,
+    task_id: Id,
+    worklog_id: Id,
+) -> AppResult<Worklog> {
+    WorklogRepository::get_worklog_by_id(pool, worklog_id.0)
+        .await?
+        .filter(|w| w.project_id.0 == project_id && w.task_id == task_id)
+        .ok_or_else(|| AppError::NotFound(format!("Worklog not found: {}", worklog_id)))
+}
+
+/// 校验工时记录：时长、备注长度；按工时所属用户的假期日历判断工作日，非工作日需显式允许
+async fn validate_worklog(
+    pool: &PgPool,
+    worklog: &NewWorklog,
+    allow_non_working_day: bool,
+) -> AppResult<()> {
+    let mut errors = Vec::new();
+    if !(1..=MAX_MINUTES_PER_DAY).contains(&worklog.duration_minutes) {
+        errors.push(field_error(
+            "durationMinutes",
+            "out_of_range",
+            format!("Duration must be between 1 and {} minutes", MAX_MINUTES_PER_DAY),
+        ));
+    }
+    if worklog
+        .note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_LENGTH)
+    {
+        errors.push(field_error(
+            "note",
+            "too_long",
+            format!("Note must not exceed {} characters", MAX_NOTE_LENGTH),
+        ));
+    }
+
+    if !allow_non_working_day {
+        let calendar_id =
+            HolidayCalendarRepository::resolve_user_calendar_id(pool, worklog.user_id).await?;
+        let calendar =
+            WorkingCalendar::load_between(pool, calendar_id, worklog.work_date, worklog.work_date)
+                .await?;
+        if !calendar.is_working_day(worklog.work_date) {
+            errors.push(field_error(
+                "workDate",
+                "non_working_day",
+                format!(
+                    "{} is not a working day; set allowNonWorkingDay to log time anyway",
+                    worklog.work_date
+                ),
+            ));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            "Invalid worklog".to_string(),
+            errors,
+        ));
+    }
+    Ok(())
+}
+
+/// 校验用户当天的工时合计不超过 24 小时；需在事务中调用
+///
+/// 先锁定该用户当天的工时，避免并发登记各自通过校验后合计超限。
+async fn check_daily_limit(
+    conn: &mut PgConnection,
+    worklog: &NewWorklog,
+    exclude_id: Option<i64>,
+) -> AppResult<()> {
+    WorklogRepository::lock_user_day(conn, worklog.user_id, worklog.work_date).await?;
+    let logged =
+        WorklogRepository::get_daily_minutes(conn, worklog.user_id, worklog.work_date, exclude_id)
+            .await?;
+    if logged + worklog.duration_minutes as i64 > MAX_MINUTES_PER_DAY as i64 {
+        return Err(AppError::ValidationError(
+            "Invalid worklog".to_string(),
+            vec![field_error(
+                "durationMinutes",
+                "daily_limit_exceeded",
+                format!(
+                    "Total time logged on {} would exceed {} minutes ({} already logged)",
+                    worklog.work_date, MAX_MINUTES_PER_DAY, logged
+                ),
+            )],
+        ));
+    }
+    Ok(())
+}
+
+/// 备注去除首尾空白，空备注视为无
+fn normalize_note(note: Option<String>) -> Option<String> {
+    note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+pub async fn get_worklogs(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Query(params): Query<WorklogQueryParams>,
+) -> AppResult<Json<PaginatedResponse<Worklog>>> {
+    perm.require(Permission::TaskView)?;
+    TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    let page = params.page.unwrap_or(1);
+    let per_page = params.per_page.unwrap_or(20);
+    let (worklogs, total) =
+        WorklogRepository::get_worklogs(&state.pool, task_id.0, &params, page, per_page).await?;
+
+    Ok(Json(PaginatedResponse::new(
+        worklogs,
+        total,
+        page,
+        per_page,
+        &format!("/api/v1/projects/{}/tasks/{}/worklogs", project_id, task_id),
+    )))
+}
+
+pub async fn create_worklog(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<CreateWorklogParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<Worklog>>)> {
+    perm.require(Permission::Worklog)?;
+    TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+
+    let user_id = params.user_id.map_or(claims.sub, |id| id.0);
+    if user_id != claims.sub {
+        perm.require(Permission::WorklogManage)?;
+        let members = ProjectPermissionResolver::get_effective_member_ids(
+            &state.pool,
+            project_id.0,
+            &[user_id],
+        )
+        .await?;
+        if !members.contains(&user_id) {
+            return Err(AppError::ValidationError(
+                "Invalid worklog".to_string(),
+                vec![field_error(
+                    "userId",
+                    "not_project_member",
+                    format!("User {} is not a member of this project", user_id),
+                )],
+            ));
+        }
+    }
+
+    let worklog_id = state
+        .generate_id()
+        .map_err(|e| AppError::InternalError(format!("Failed to generate worklog ID: {}", e)))?;
+    let worklog = NewWorklog {
+        id: worklog_id,
+        task_id: task_id.0,
+        user_id,
+        work_date: params.work_date,
+        duration_minutes: params.duration_minutes,
+        note: normalize_note(params.note),
+    };
+    validate_worklog(&state.pool, &worklog, params.allow_non_working_day).await?;
+
+    let mut tx = state.pool.begin().await?;
+    check_daily_limit(&mut tx, &worklog, None).await?;
+    let worklog = WorklogRepository::create_worklog(&mut tx, worklog, claims.sub).await?;
+    tx.commit().await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(worklog))))
+}
+
+/// 修改工时：本人的记录需要 Worklog 权限，他人的记录需要 WorklogManage 权限
+pub async fn update_worklog(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id, worklog_id)): Path<(Id, Id, Id)>,
+    Json(params): Json<UpdateWorklogParams>,
+) -> AppResult<Json<ApiResponse<Worklog>>> {
+    let existing = get_task_worklog(&state.pool, project_id.0, task_id, worklog_id).await?;
+    if !perm.can_operate(
+        Permission::WorklogManage,
+        Permission::Worklog,
+        existing.user_id.0 == claims.sub,
+    ) {
+        return Err(AppError::Forbidden(
+            "You can only edit your own worklogs".to_string(),
+        ));
+    }
+
+    let worklog = NewWorklog {
+        id: existing.id.0,
+        task_id: existing.task_id.0,
+        user_id: existing.user_id.0,
+        work_date: params.work_date.unwrap_or(existing.work_date),
+        duration_minutes: params.duration_minutes.unwrap_or(existing.duration_minutes),
+        note: match params.note {
+            Some(note) => normalize_note(note),
+            None => existing.note,
+        },
+    };
+    validate_worklog(&state.pool, &worklog, params.allow_non_working_day).await?;
+
+    let mut tx = state.pool.begin().await?;
+    check_daily_limit(&mut tx, &worklog, Some(existing.id.0)).await?;
+    let worklog = WorklogRepository::update_worklog(&mut tx, worklog, claims.sub)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Worklog not found: {}", worklog_id)))?;
+    tx.commit().await?;
+    Ok(Json(ApiResponse::success(worklog)))
+}
+
+pub async fn delete_worklog(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id, worklog_id)): Path<(Id, Id, Id)>,
+) -> AppResult<StatusCode> {
+    let existing = get_task_worklog(&state.pool, project_id.0, task_id, worklog_id).await?;
+    if !perm.can_operate(
+        Permission::WorklogManage,
+        Permission::Worklog,
+        existing.user_id.0 == claims.sub,
+    ) {
+        return Err(AppError::Forbidden(
+            "You can only delete your own worklogs".to_string(),
+        ));
+    }
+
+    let deleted = WorklogRepository::delete_worklog(&state.pool, worklog_id.0).await?;
+    if !deleted {
+        return Err(AppError::NotFound(format!(
+            "Worklog not found: {}",
+            worklog_id
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 周工时表（周一至周日，跨项目）；管理员可查看任意用户
+pub async fn get_timesheet(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<TimesheetQueryParams>,
+) -> AppResult<Json<ApiResponse<Timesheet>>> {
+    let user_id = params.user_id.map_or(claims.sub, |id| id.0);
+    if user_id != claims.sub && !claims.is_admin_or_above() {
+        return Err(AppError::Forbidden(
+            "Only admins can view timesheets of other users".to_string(),
+        ));
+    }
+
+    let week_start = week_start(
+        params
+            .date
+            .unwrap_or_else(|| chrono::Local::now().date_naive()),
+    );
+    let week_end = week_start + Duration::days(6);
+    let entries =
+        WorklogRepository::get_timesheet_entries(&state.pool, user_id, week_start, week_end)
+            .await?;
+    let calendar_id = HolidayCalendarRepository::resolve_user_calendar_id(&state.pool, user_id).await?;
+    let calendar =
+        WorkingCalendar::load_between(&state.pool, calendar_id, week_start, week_end).await?;
+
+    let (days, rows) = build_timesheet(&entries, week_start, &calendar);
+    let total_minutes = days.iter().map(|d| d.total_minutes).sum();
+    Ok(Json(ApiResponse::success(Timesheet {
+        user_id: Id(user_id),
+        week_start,
+        week_end,
+        days,
+        rows,
+        total_minutes,
+    })))
+}
+
+/// 导出工时 CSV（按项目、任务、部门汇总，单位小时）
+///
+/// 管理员可导出全部工时并按部门 / 用户过滤；普通用户仅能导出自己的工时。
+pub async fn export_worklogs(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<WorklogExportParams>,
+) -> AppResult<Response> {
+    if params.date_from > params.date_to {
+        return Err(AppError::BadRequest(
+            "dateFrom must not be after dateTo".to_string(),
+        ));
+    }
+    if (params.date_to - params.date_from).num_days() >= MAX_EXPORT_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Date range must not exceed {} days",
+            MAX_EXPORT_DAYS
+        )));
+    }
+
+    let user_id = if claims.is_admin_or_above() {
+        params.user_id.map(|id| id.0)
+    } else {
+        if params.department_id.is_some() || params.user_id.is_some_and(|id| id.0 != claims.sub) {
+            return Err(AppError::Forbidden(
+                "Only admins can export worklogs of other users".to_string(),
+            ));
+        }
+        Some(claims.sub)
+    };
+
+    let rows = WorklogRepository::get_export_rows(
+        &state.pool,
+        params.date_from,
+        params.date_to,
+        params.project_id.map(|id| id.0),
+        params.department_id.map(|id| id.0),
+        user_id,
+    )
+    .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                export_file_name(params.date_from, params.date_to),
+            ),
+        ],
+        export_csv(&rows),
+    )
+        .into_response())
+}
+
+fn export_file_name(from: NaiveDate, to: NaiveDate) -> String {
+    format!(
+        "attachment; filename=\"worklogs-{}-{}.csv\"",
+        from.format("%Y%m%d"),
+        to.format("%Y%m%d")
+    )
+}
@@ -0,0 +1,157 @@
+use crate::common::id::Id;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 备注最大长度（字符）
+pub const MAX_NOTE_LENGTH: usize = 2000;
+
+/// 单个用户单日可登记的最大工时（分钟）
+pub const MAX_MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// 导出的最大日期跨度（天）
+pub const MAX_EXPORT_DAYS: i64 = 366;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Worklog {
+    pub id: Id,
+    pub project_id: Id,
+    pub task_id: Id,
+    pub user_id: Id,
+    pub work_date: NaiveDate,
+    pub duration_minutes: i32,
+    pub note: Option<String>,
+    pub creator_id: Id,
+    pub updater_id: Option<Id>,
+    pub create_date_time: chrono::NaiveDateTime,
+    pub update_date_time: Option<chrono::NaiveDateTime>,
+    // JOIN 字段
+    pub username: Option<String>,
+    pub full_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWorklogParams {
+    /// 工时所属用户，默认为当前用户；代他人登记需要 WorklogManage 权限
+    pub user_id: Option<Id>,
+    pub work_date: NaiveDate,
+    pub duration_minutes: i32,
+    pub note: Option<String>,
+    /// 允许登记在非工作日（周末、假期）
+    #[serde(default)]
+    pub allow_non_working_day: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWorklogParams {
+    /// NOT NULL 字段
+    pub work_date: Option<NaiveDate>,
+    /// NOT NULL 字段
+    pub duration_minutes: Option<i32>,
+    /// 可空字段，双层 Option：None = 不更新，Some(None) = 清空，Some(Some(v)) = 更新
+    #[serde(default, deserialize_with = "crate::common::serde_helpers::double_option::deserialize")]
+    pub note: Option<Option<String>>,
+    /// 允许登记在非工作日（周末、假期）
+    #[serde(default)]
+    pub allow_non_working_day: bool,
+}
+
+/// 待写入的工时记录
+#[derive(Debug, Clone)]
+pub struct NewWorklog {
+    pub id: i64,
+    pub task_id: i64,
+    pub user_id: i64,
+    pub work_date: NaiveDate,
+    pub duration_minutes: i32,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogQueryParams {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub user_id: Option<Id>,
+    /// 日期范围（含两端）
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetQueryParams {
+    /// 所在周的任意一天，默认为今天
+    pub date: Option<NaiveDate>,
+    /// 默认为当前用户；查看他人需要管理员
+    pub user_id: Option<Id>,
+}
+
+/// 周工时表中的一条工时（按任务、日期汇总）
+#[derive(Debug, Clone, FromRow)]
+pub struct TimesheetEntry {
+    pub project_id: Id,
+    pub project_name: String,
+    pub task_id: Id,
+    pub task_name: String,
+    pub work_date: NaiveDate,
+    pub minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetDay {
+    pub date: NaiveDate,
+    pub is_working_day: bool,
+    pub total_minutes: i64,
+}
+
+/// 周工时表的一行：一个任务在一周内每天的工时（周一至周日）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimesheetRow {
+    pub project_id: Id,
+    pub project_name: String,
+    pub task_id: Id,
+    pub task_name: String,
+    pub minutes: [i64; 7],
+    pub total_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timesheet {
+    pub user_id: Id,
+    pub week_start: NaiveDate,
+    pub week_end: NaiveDate,
+    pub days: Vec<TimesheetDay>,
+    pub rows: Vec<TimesheetRow>,
+    pub total_minutes: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogExportParams {
+    /// 日期范围（含两端）
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    pub project_id: Option<Id>,
+    pub department_id: Option<Id>,
+    /// 非管理员只能导出自己的工时
+    pub user_id: Option<Id>,
+}
+
+/// 导出行：按项目、任务、部门汇总的工时；未加入部门的用户部门为空
+#[derive(Debug, Clone, FromRow)]
+pub struct WorklogExportRow {
+    pub project_id: Id,
+    pub project_name: String,
+    pub task_id: Id,
+    pub task_name: String,
+    pub department_id: Option<Id>,
+    pub department_name: Option<String>,
+    pub minutes: i64,
+}
@@ -0,0 +1,148 @@
+use crate::modules::business::project::task::worklog::models::{
+    TimesheetDay, TimesheetEntry, TimesheetRow, WorklogExportRow,
+};
+use crate::modules::holiday::calendar::WorkingCalendar;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// 所在周的周一
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// 构建周工时表：按任务成行（保持 entries 中首次出现的顺序），按天汇总
+pub fn build_timesheet(
+    entries: &[TimesheetEntry],
+    week_start: NaiveDate,
+    calendar: &WorkingCalendar,
+) -> (Vec<TimesheetDay>, Vec<TimesheetRow>) {
+    let mut days: Vec<TimesheetDay> = (0..7)
+        .map(|i| {
+            let date = week_start + Duration::days(i);
+            TimesheetDay {
+                date,
+                is_working_day: calendar.is_working_day(date),
+                total_minutes: 0,
+            }
+        })
+        .collect();
+    let mut rows: Vec<TimesheetRow> = Vec::new();
+
+    for entry in entries {
+        let offset = (entry.work_date - week_start).num_days();
+        if !(0..7).contains(&offset) {
+            continue;
+        }
+        let index = match rows.iter().position(|r| r.task_id == entry.task_id) {
+            Some(index) => index,
+            None => {
+                rows.push(TimesheetRow {
+                    project_id: entry.project_id,
+                    project_name: entry.project_name.clone(),
+                    task_id: entry.task_id,
+                    task_name: entry.task_name.clone(),
+                    minutes: [0; 7],
+                    total_minutes: 0,
+                });
+                rows.len() - 1
+            }
+        };
+        rows[index].minutes[offset as usize] += entry.minutes;
+        rows[index].total_minutes += entry.minutes;
+        days[offset as usize].total_minutes += entry.minutes;
+    }
+
+    (days, rows)
+}
+
+/// CSV 字段转义：含逗号、引号或换行时用双引号包裹；以 = + - @ 或制表符、回车开头时加 ' 前缀，
+/// 避免被表格软件当作公式
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// 生成工时导出 CSV（UTF-8 BOM，便于 Excel 正确识别中文）
+pub fn export_csv(rows: &[WorklogExportRow]) -> String {
+    let mut csv = String::from(
+        "\u{feff}project_id,project_name,task_id,task_name,department_id,department_name,hours\r\n",
+    );
+    for row in rows {
+        let fields = [
+            row.project_id.to_string(),
+            csv_field(&row.project_name),
+            row.task_id.to_string(),
+            csv_field(&row.task_name),
+            row.department_id.map(|id| id.to_string()).unwrap_or_default(),
+            csv_field(row.department_name.as_deref().unwrap_or_default()),
+            format!("{:.2}", row.minutes as f64 / 60.0),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::id::Id;
+    use crate::modules::holiday::models::HolidayType;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    fn entry(task_id: i64, day: u32, minutes: i64) -> TimesheetEntry {
+        TimesheetEntry {
+            project_id: Id(1),
+            project_name: "Demeter".to_string(),
+            task_id: Id(task_id),
+            task_name: format!("task-{}", task_id),
+            work_date: date(day),
+            minutes,
+        }
+    }
+
+    #[test]
+    fn test_timesheet_and_csv() {
+        // 2026-01-08 为周四，所在周为 01-05 ~ 01-11；01-06 为假期
+        assert_eq!(week_start(date(8)), date(5));
+        assert_eq!(week_start(date(5)), date(5));
+        let calendar = WorkingCalendar::new([(date(6), HolidayType::CompanyHoliday)]);
+        let entries = vec![entry(2, 5, 90), entry(1, 5, 30), entry(2, 9, 60), entry(2, 12, 15)];
+
+        let (days, rows) = build_timesheet(&entries, date(5), &calendar);
+        assert_eq!(days.iter().map(|d| d.total_minutes).collect::<Vec<_>>(), [120, 0, 0, 0, 60, 0, 0]);
+        assert_eq!(
+            days.iter().map(|d| d.is_working_day).collect::<Vec<_>>(),
+            [true, false, true, true, true, false, false]
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].task_id, rows[0].minutes, rows[0].total_minutes), (Id(2), [90, 0, 0, 0, 60, 0, 0], 150));
+        assert_eq!(rows[1].total_minutes, 30);
+
+        let csv = export_csv(&[WorklogExportRow {
+            project_id: Id(1),
+            project_name: "Demeter, \"core\"".to_string(),
+            task_id: Id(2),
+            task_name: "=SUM(A1)".to_string(),
+            department_id: None,
+            department_name: None,
+            minutes: 100,
+        }]);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("1,\"Demeter, \"\"core\"\"\",2,'=SUM(A1),,,1.67")
+        );
+        assert_eq!(csv_field("\t=1+1"), "'\t=1+1");
+        assert_eq!(csv_field("\r=1+1"), "\"'\r=1+1\"");
+    }
+}
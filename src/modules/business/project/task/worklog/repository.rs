@@ -0,0 +1,250 @@
+use crate::common::error::{AppError, AppResult};
+use crate::modules::business::project::task::worklog::models::{
+    NewWorklog, TimesheetEntry, Worklog, WorklogExportRow, WorklogQueryParams,
+};
+use chrono::NaiveDate;
+use sqlx::{PgConnection, PgPool};
+
+pub struct WorklogRepository;
+
+/// project_task_worklogs 表 SELECT 列（含用户 JOIN 字段）
+const WORKLOG_COLUMNS: &str = r#"w.id, w.project_id, w.task_id, w.user_id, w.work_date,
+    w.duration_minutes, w.note, w.creator_id, w.updater_id, w.create_date_time, w.update_date_time,
+    u.username, u.full_name"#;
+
+/// 任务工时列表过滤条件
+const TASK_WORKLOG_CONDITIONS: &str = r#"WHERE w.task_id = $1
+      AND ($2::BIGINT IS NULL OR w.user_id = $2)
+      AND ($3::DATE IS NULL OR w.work_date >= $3)
+      AND ($4::DATE IS NULL OR w.work_date <= $4)"#;
+
+impl WorklogRepository {
+    /// 分页查询任务的工时记录（按日期倒序）
+    pub async fn get_worklogs(
+        pool: &PgPool,
+        task_id: i64,
+        params: &WorklogQueryParams,
+        page: i64,
+        per_page: i64,
+    ) -> AppResult<(Vec<Worklog>, i64)> {
+        let offset = (page - 1) * per_page;
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_worklogs w
+               LEFT JOIN users u ON u.id = w.user_id
+               {}
+               ORDER BY w.work_date DESC, w.create_date_time DESC
+               LIMIT $5 OFFSET $6"#,
+            WORKLOG_COLUMNS, TASK_WORKLOG_CONDITIONS,
+        );
+        let worklogs = sqlx::query_as::<_, Worklog>(&sql)
+            .bind(task_id)
+            .bind(params.user_id)
+            .bind(params.date_from)
+            .bind(params.date_to)
+            .bind(per_page)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        let total: (i64,) = sqlx::query_as(&format!(
+            "SELECT COUNT(*) FROM project_task_worklogs w {}",
+            TASK_WORKLOG_CONDITIONS,
+        ))
+        .bind(task_id)
+        .bind(params.user_id)
+        .bind(params.date_from)
+        .bind(params.date_to)
+        .fetch_one(pool)
+        .await?;
+
+        Ok((worklogs, total.0))
+    }
+
+    pub async fn get_worklog_by_id(pool: &PgPool, worklog_id: i64) -> AppResult<Option<Worklog>> {
+        let mut conn = pool.acquire().await?;
+        Self::find_worklog(&mut conn, worklog_id).await
+    }
+
+    async fn find_worklog(conn: &mut PgConnection, worklog_id: i64) -> AppResult<Option<Worklog>> {
+        let sql = format!(
+            r#"SELECT {}
+               FROM project_task_worklogs w
+               LEFT JOIN users u ON u.id = w.user_id
+               WHERE w.id = $1"#,
+            WORKLOG_COLUMNS,
+        );
+        let worklog = sqlx::query_as::<_, Worklog>(&sql)
+            .bind(worklog_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(worklog)
+    }
+
+    /// 锁定用户某天的工时（事务级 advisory lock），同一用户同一天的登记串行执行；需在事务中调用
+    pub async fn lock_user_day(
+        conn: &mut PgConnection,
+        user_id: i64,
+        work_date: NaiveDate,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"SELECT pg_advisory_xact_lock(
+                   hashtextextended(format('worklog:%s:%s', $1::BIGINT, $2::DATE), 0)
+               )"#,
+        )
+        .bind(user_id)
+        .bind(work_date)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 用户当天已登记的工时合计（分钟），可排除正在修改的记录
+    pub async fn get_daily_minutes(
+        conn: &mut PgConnection,
+        user_id: i64,
+        work_date: NaiveDate,
+        exclude_id: Option<i64>,
+    ) -> AppResult<i64> {
+        let total: (Option<i64>,) = sqlx::query_as(
+            r#"SELECT SUM(duration_minutes)::BIGINT
+               FROM project_task_worklogs
+               WHERE user_id = $1 AND work_date = $2
+                 AND ($3::BIGINT IS NULL OR id <> $3)"#,
+        )
+        .bind(user_id)
+        .bind(work_date)
+        .bind(exclude_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(total.0.unwrap_or(0))
+    }
+
+    /// 新增工时记录，project_id 取自任务
+    pub async fn create_worklog(
+        conn: &mut PgConnection,
+        worklog: NewWorklog,
+        creator_id: i64,
+    ) -> AppResult<Worklog> {
+        sqlx::query(
+            r#"INSERT INTO project_task_worklogs
+               (id, project_id, task_id, user_id, work_date, duration_minutes, note,
+                creator_id, create_date_time)
+               SELECT $1, t.project_id, t.id, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP
+               FROM project_tasks t
+               WHERE t.id = $2"#,
+        )
+        .bind(worklog.id)
+        .bind(worklog.task_id)
+        .bind(worklog.user_id)
+        .bind(worklog.work_date)
+        .bind(worklog.duration_minutes)
+        .bind(&worklog.note)
+        .bind(creator_id)
+        .execute(&mut *conn)
+        .await?;
+
+        Self::find_worklog(conn, worklog.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))
+    }
+
+    pub async fn update_worklog(
+        conn: &mut PgConnection,
+        worklog: NewWorklog,
+        updater_id: i64,
+    ) -> AppResult<Option<Worklog>> {
+        let result = sqlx::query(
+            r#"UPDATE project_task_worklogs
+               SET work_date = $2, duration_minutes = $3, note = $4,
+                   updater_id = $5, update_date_time = CURRENT_TIMESTAMP
+               WHERE id = $1"#,
+        )
+        .bind(worklog.id)
+        .bind(worklog.work_date)
+        .bind(worklog.duration_minutes)
+        .bind(&worklog.note)
+        .bind(updater_id)
+        .execute(&mut *conn)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Self::find_worklog(conn, worklog.id).await
+    }
+
+    pub async fn delete_worklog(pool: &PgPool, worklog_id: i64) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM project_task_worklogs WHERE id = $1")
+            .bind(worklog_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 用户在 [from, to] 内按任务、日期汇总的工时
+    pub async fn get_timesheet_entries(
+        pool: &PgPool,
+        user_id: i64,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<TimesheetEntry>> {
+        let entries = sqlx::query_as::<_, TimesheetEntry>(
+            r#"SELECT p.id AS project_id, p.project_name, t.id AS task_id, t.task_name,
+                   w.work_date, SUM(w.duration_minutes)::BIGINT AS minutes
+               FROM project_task_worklogs w
+               JOIN project_tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = w.project_id
+               WHERE w.user_id = $1 AND w.work_date BETWEEN $2 AND $3
+               GROUP BY p.id, p.project_name, t.id, t.task_name, w.work_date
+               ORDER BY p.project_name ASC, t.task_name ASC, t.id ASC, w.work_date ASC"#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// 按项目、任务、部门汇总 [from, to] 内的工时
+    pub async fn get_export_rows(
+        pool: &PgPool,
+        from: NaiveDate,
+        to: NaiveDate,
+        project_id: Option<i64>,
+        department_id: Option<i64>,
+        user_id: Option<i64>,
+    ) -> AppResult<Vec<WorklogExportRow>> {
+        let rows = sqlx::query_as::<_, WorklogExportRow>(
+            r#"SELECT p.id AS project_id, p.project_name, t.id AS task_id, t.task_name,
+                   d.id AS department_id, d.department_name,
+                   SUM(w.duration_minutes)::BIGINT AS minutes
+               FROM project_task_worklogs w
+               JOIN project_tasks t ON t.id = w.task_id
+               JOIN projects p ON p.id = w.project_id
+               LEFT JOIN user_departments ud ON ud.user_id = w.user_id
+               LEFT JOIN departments d ON d.id = ud.department_id
+               WHERE w.work_date BETWEEN $1 AND $2
+                 AND ($3::BIGINT IS NULL OR w.project_id = $3)
+                 AND ($4::BIGINT IS NULL OR d.id = $4)
+                 AND ($5::BIGINT IS NULL OR w.user_id = $5)
+               GROUP BY p.id, p.project_name, t.id, t.task_name, d.id, d.department_name
+               ORDER BY p.project_name ASC, t.task_name ASC, t.id ASC, d.department_name ASC NULLS LAST"#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(project_id)
+        .bind(department_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::task::worklog::handlers;
+use axum::{
+    middleware,
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn task_worklog_routes(state: AppState) -> Router {
+    let project_routes = Router::new()
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/worklogs",
+            get(handlers::get_worklogs),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/worklogs",
+            post(handlers::create_worklog),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/worklogs/{worklog_id}",
+            put(handlers::update_worklog),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/worklogs/{worklog_id}",
+            delete(handlers::delete_worklog),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ));
+
+    // 周工时表与导出：跨项目，仅需登录
+    let my_routes = Router::new()
+        .route("/worklogs/timesheet", get(handlers::get_timesheet))
+        .route("/worklogs/export", get(handlers::export_worklogs));
+
+    Router::new()
+        .merge(project_routes)
+        .merge(my_routes)
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,306 @@
+use crate::common::error::{field_error, AppResult, FieldError};
+use crate::modules::business::project::permission::models::ProjectRole;
+use crate::modules::business::project::workflow::models::{
+    NewWorkflow, NewWorkflowStatus, NewWorkflowTransition, SaveWorkflowParams, StatusCategory,
+    Workflow, WorkflowStatusItem, WorkflowTransition, WorkflowTransitionItem, MAX_STATUSES,
+    MAX_STATUS_NAME_LENGTH,
+};
+use std::collections::{HashMap, HashSet};
+
+/// 新建项目的默认工作流：To Do -> In Progress -> Done，完成与重新打开仅限维护者及以上
+pub fn default_workflow() -> SaveWorkflowParams {
+    let status = |name: &str, category, is_initial| WorkflowStatusItem {
+        id: None,
+        status_name: name.to_string(),
+        category,
+        is_initial,
+    };
+    let contributors = vec![
+        ProjectRole::Owner,
+        ProjectRole::Admin,
+        ProjectRole::Maintainer,
+        ProjectRole::Member,
+    ];
+    let maintainers = vec![ProjectRole::Owner, ProjectRole::Admin, ProjectRole::Maintainer];
+    let transition = |from: &str, to: &str, roles: &Vec<ProjectRole>| WorkflowTransitionItem {
+        from_status: from.to_string(),
+        to_status: to.to_string(),
+        allowed_roles: roles.clone(),
+    };
+
+    SaveWorkflowParams {
+        statuses: vec![
+            status("To Do", StatusCategory::Todo, true),
+            status("In Progress", StatusCategory::InProgress, false),
+            status("Done", StatusCategory::Done, false),
+        ],
+        transitions: vec![
+            transition("To Do", "In Progress", &contributors),
+            transition("In Progress", "To Do", &contributors),
+            transition("In Progress", "Done", &contributors),
+            transition("To Do", "Done", &maintainers),
+            transition("Done", "In Progress", &maintainers),
+        ],
+    }
+}
+
+/// 校验工作流定义；existing_ids 为项目现有状态 id，状态项引用的 id 必须在其中
+pub fn validate_workflow(params: &SaveWorkflowParams, existing_ids: &HashSet<i64>) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if params.statuses.is_empty() || params.statuses.len() > MAX_STATUSES {
+        errors.push(field_error(
+            "statuses".to_string(),
+            "invalid_length",
+            format!("A workflow must have between 1 and {} statuses", MAX_STATUSES),
+        ));
+    }
+
+    let mut names = HashSet::new();
+    let mut ids = HashSet::new();
+    for (i, status) in params.statuses.iter().enumerate() {
+        let name = status.status_name.trim();
+        if name.is_empty() || name.chars().count() > MAX_STATUS_NAME_LENGTH {
+            errors.push(field_error(
+                format!("statuses[{}].statusName", i),
+                "invalid_length",
+                format!(
+                    "Status name must be between 1 and {} characters",
+                    MAX_STATUS_NAME_LENGTH
+                ),
+            ));
+        } else if !names.insert(name) {
+            errors.push(field_error(
+                format!("statuses[{}].statusName", i),
+                "duplicate",
+                format!("Duplicate status name: {}", name),
+            ));
+        }
+        if let Some(id) = status.id {
+            if !existing_ids.contains(&id.0) || !ids.insert(id.0) {
+                errors.push(field_error(
+                    format!("statuses[{}].id", i),
+                    "invalid_reference",
+                    format!("Status {} does not belong to this workflow or is duplicated", id),
+                ));
+            }
+        }
+    }
+
+    let initial_count = params.statuses.iter().filter(|s| s.is_initial).count();
+    if !params.statuses.is_empty() && initial_count != 1 {
+        errors.push(field_error(
+            "statuses".to_string(),
+            "invalid_initial",
+            "Exactly one status must be marked as initial".to_string(),
+        ));
+    }
+
+    let mut pairs = HashSet::new();
+    for (i, transition) in params.transitions.iter().enumerate() {
+        let from = transition.from_status.trim();
+        let to = transition.to_status.trim();
+        for (field, name) in [("fromStatus", from), ("toStatus", to)] {
+            if !names.contains(name) {
+                errors.push(field_error(
+                    format!("transitions[{}].{}", i, field),
+                    "invalid_reference",
+                    format!("Unknown status: {}", name),
+                ));
+            }
+        }
+        if from == to {
+            errors.push(field_error(
+                format!("transitions[{}].toStatus", i),
+                "invalid_transition",
+                "A transition must change the status".to_string(),
+            ));
+        } else if !pairs.insert((from, to)) {
+            errors.push(field_error(
+                format!("transitions[{}]", i),
+                "duplicate",
+                format!("Duplicate transition: {} -> {}", from, to),
+            ));
+        }
+        if transition.allowed_roles.is_empty() {
+            errors.push(field_error(
+                format!("transitions[{}].allowedRoles", i),
+                "required",
+                "At least one role must be allowed".to_string(),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// 为已校验的工作流分配 id：已有状态保留原 id，新增状态与全部流转使用新 id
+pub fn build_workflow(
+    params: &SaveWorkflowParams,
+    mut next_id: impl FnMut() -> AppResult<i64>,
+) -> AppResult<NewWorkflow> {
+    let mut statuses = Vec::with_capacity(params.statuses.len());
+    let mut ids_by_name = HashMap::new();
+    for (i, status) in params.statuses.iter().enumerate() {
+        let id = match status.id {
+            Some(id) => id.0,
+            None => next_id()?,
+        };
+        let name = status.status_name.trim().to_string();
+        ids_by_name.insert(name.clone(), id);
+        statuses.push(NewWorkflowStatus {
+            id,
+            status_name: name,
+            category: status.category,
+            is_initial: status.is_initial,
+            order: (i + 1) as f64,
+        });
+    }
+
+    let mut transitions = Vec::with_capacity(params.transitions.len());
+    for transition in &params.transitions {
+        let mut roles = transition.allowed_roles.clone();
+        roles.sort();
+        roles.dedup();
+        transitions.push(NewWorkflowTransition {
+            id: next_id()?,
+            from_status_id: ids_by_name[transition.from_status.trim()],
+            to_status_id: ids_by_name[transition.to_status.trim()],
+            allowed_roles: roles,
+        });
+    }
+
+    Ok(NewWorkflow {
+        statuses,
+        transitions,
+    })
+}
+
+impl Workflow {
+    pub fn initial_status_id(&self) -> Option<i64> {
+        self.statuses.iter().find(|s| s.is_initial).map(|s| s.id.0)
+    }
+
+    /// 任务的当前状态；未进入工作流的任务视为处于初始状态
+    pub fn current_status_id(&self, status_id: Option<i64>) -> Option<i64> {
+        status_id.or_else(|| self.initial_status_id())
+    }
+
+    pub fn status_category(&self, status_id: i64) -> Option<StatusCategory> {
+        self.statuses
+            .iter()
+            .find(|s| s.id.0 == status_id)
+            .map(|s| s.category)
+    }
+
+    /// 从 from 状态出发、当前角色可执行的流转
+    pub fn available_transitions(&self, from: i64, role: ProjectRole) -> Vec<&WorkflowTransition> {
+        self.transitions
+            .iter()
+            .filter(|t| t.from_status_id.0 == from && t.allowed_roles.contains(&role))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::id::Id;
+
+    fn to_workflow(new: &NewWorkflow) -> Workflow {
+        let time = chrono::NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        Workflow {
+            statuses: new
+                .statuses
+                .iter()
+                .map(|s| crate::modules::business::project::workflow::models::WorkflowStatus {
+                    id: Id(s.id),
+                    project_id: Id(1),
+                    status_name: s.status_name.clone(),
+                    category: s.category,
+                    is_initial: s.is_initial,
+                    order: s.order,
+                    creator_id: Id(1),
+                    create_date_time: time,
+                })
+                .collect(),
+            transitions: new
+                .transitions
+                .iter()
+                .map(|t| WorkflowTransition {
+                    id: Id(t.id),
+                    project_id: Id(1),
+                    from_status_id: Id(t.from_status_id),
+                    to_status_id: Id(t.to_status_id),
+                    allowed_roles: t.allowed_roles.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_workflow_validation_and_role_restricted_transitions() {
+        let params = default_workflow();
+        assert!(validate_workflow(&params, &HashSet::new()).is_empty());
+
+        let mut next = 100;
+        let workflow = to_workflow(
+            &build_workflow(&params, || {
+                next += 1;
+                Ok(next)
+            })
+            .unwrap(),
+        );
+        let todo = workflow.initial_status_id().unwrap();
+        assert_eq!(workflow.current_status_id(None), Some(todo));
+
+        let targets = |role| -> Vec<String> {
+            workflow
+                .available_transitions(todo, role)
+                .iter()
+                .filter_map(|t| workflow.statuses.iter().find(|s| s.id == t.to_status_id))
+                .map(|s| s.status_name.clone())
+                .collect()
+        };
+        assert_eq!(targets(ProjectRole::Member), vec!["In Progress"]);
+        assert_eq!(targets(ProjectRole::Admin), vec!["In Progress", "Done"]);
+        assert!(targets(ProjectRole::Viewer).is_empty());
+
+        let mut invalid = default_workflow();
+        invalid.statuses[1].is_initial = true;
+        invalid.statuses[0].id = Some(Id(7));
+        invalid.statuses.push(WorkflowStatusItem {
+            id: None,
+            status_name: " Done ".to_string(),
+            category: StatusCategory::Done,
+            is_initial: false,
+        });
+        invalid.transitions[0].allowed_roles.clear();
+        invalid.transitions.push(WorkflowTransitionItem {
+            from_status: "Done".to_string(),
+            to_status: "Archived".to_string(),
+            allowed_roles: vec![ProjectRole::Owner],
+        });
+        let codes: Vec<(String, String)> = validate_workflow(&invalid, &HashSet::new())
+            .into_iter()
+            .map(|e| (e.field, e.code))
+            .collect();
+        let expected = [
+            ("statuses[0].id", "invalid_reference"),
+            ("statuses[3].statusName", "duplicate"),
+            ("statuses", "invalid_initial"),
+            ("transitions[0].allowedRoles", "required"),
+            ("transitions[5].toStatus", "invalid_reference"),
+        ];
+        assert_eq!(
+            codes,
+            expected
+                .iter()
+                .map(|(f, c)| (f.to_string(), c.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+}
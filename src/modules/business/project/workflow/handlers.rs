@@ -0,0 +1,183 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::task::checkpoint::models::CheckpointDecision;
+use crate::modules::business::project::task::checkpoint::repository::CheckpointRepository;
+use crate::modules::business::project::task::history::handlers::record_history;
+use crate::modules::business::project::task::models::{Task, TaskType};
+use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::business::project::workflow::definition::{
+    build_workflow, validate_workflow,
+};
+use crate::modules::business::project::workflow::models::{
+    SaveWorkflowParams, StatusCategory, TransitionTaskParams, Workflow, WorkflowTransition,
+};
+use crate::modules::business::project::workflow::repository::WorkflowRepository;
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use std::collections::HashSet;
+
+fn id_error(e: crate::common::snowflake::SnowflakeError) -> AppError {
+    AppError::InternalError(format!("Failed to generate workflow ID: {}", e))
+}
+
+pub async fn get_workflow(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+) -> AppResult<Json<ApiResponse<Workflow>>> {
+    perm.require(Permission::ProjectView)?;
+    let workflow = WorkflowRepository::get_workflow(&state.pool, project_id.0).await?;
+    Ok(Json(ApiResponse::success(workflow)))
+}
+
+/// 整体替换项目工作流；仍有任务处于其中的状态不可删除
+pub async fn save_workflow(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path(project_id): Path<Id>,
+    Json(params): Json<SaveWorkflowParams>,
+) -> AppResult<Json<ApiResponse<Workflow>>> {
+    perm.require(Permission::ProjectEdit)?;
+    // 锁定现有状态后再检查占用，避免检查与删除之间有任务进入待删除的状态
+    let mut tx = state.pool.begin().await?;
+    let current = WorkflowRepository::lock_statuses(&mut tx, project_id.0).await?;
+    let existing_ids: HashSet<i64> = current.iter().map(|s| s.id.0).collect();
+
+    let errors = validate_workflow(&params, &existing_ids);
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            "Invalid workflow".to_string(),
+            errors,
+        ));
+    }
+
+    let kept: HashSet<i64> = params.statuses.iter().filter_map(|s| s.id).map(|id| id.0).collect();
+    let removed: Vec<i64> = existing_ids.difference(&kept).copied().collect();
+    let in_use = WorkflowRepository::get_statuses_in_use(&mut tx, &removed).await?;
+    if !in_use.is_empty() {
+        let names: Vec<&str> = current
+            .iter()
+            .filter(|s| in_use.contains(&s.id.0))
+            .map(|s| s.status_name.as_str())
+            .collect();
+        return Err(AppError::Conflict(format!(
+            "Statuses still used by tasks cannot be removed: {}",
+            names.join(", ")
+        )));
+    }
+
+    let workflow = build_workflow(&params, || state.generate_id().map_err(id_error))?;
+    WorkflowRepository::replace_workflow(&mut tx, project_id.0, &workflow, claims.sub).await?;
+    tx.commit().await?;
+    let workflow = WorkflowRepository::get_workflow(&state.pool, project_id.0).await?;
+    Ok(Json(ApiResponse::success(workflow)))
+}
+
+/// 当前用户可对任务执行的流转
+pub async fn get_task_transitions(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+) -> AppResult<Json<ApiResponse<Vec<WorkflowTransition>>>> {
+    perm.require(Permission::TaskView)?;
+    let task = TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    let workflow = WorkflowRepository::get_workflow(&state.pool, project_id.0).await?;
+    let transitions = match workflow.current_status_id(task.status_id.map(|id| id.0)) {
+        Some(from) => workflow
+            .available_transitions(from, perm.role)
+            .into_iter()
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    };
+    Ok(Json(ApiResponse::success(transitions)))
+}
+
+/// 按工作流变更任务状态：需存在 当前状态 -> 目标状态 的流转，且当前角色在允许范围内
+pub async fn transition_task(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((project_id, task_id)): Path<(Id, Id)>,
+    Json(params): Json<TransitionTaskParams>,
+) -> AppResult<Json<ApiResponse<Task>>> {
+    perm.require(Permission::TaskView)?;
+    let task = TaskRepository::get_project_task(&state.pool, project_id.0, task_id.0).await?;
+    let workflow = WorkflowRepository::get_workflow(&state.pool, project_id.0).await?;
+
+    let status_name = |id: i64| {
+        workflow
+            .statuses
+            .iter()
+            .find(|s| s.id.0 == id)
+            .map(|s| s.status_name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let to = params.to_status_id.0;
+    if !workflow.statuses.iter().any(|s| s.id.0 == to) {
+        return Err(AppError::BadRequest(format!(
+            "Status {} does not belong to this project's workflow",
+            params.to_status_id
+        )));
+    }
+    let from = workflow
+        .current_status_id(task.status_id.map(|id| id.0))
+        .ok_or_else(|| AppError::InternalError("Workflow has no initial status".to_string()))?;
+    if from == to {
+        return Err(AppError::BadRequest(format!(
+            "Task is already in status {}",
+            status_name(to)
+        )));
+    }
+
+    let transition = workflow
+        .transitions
+        .iter()
+        .find(|t| t.from_status_id.0 == from && t.to_status_id.0 == to)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Transition from {} to {} is not allowed by the workflow",
+                status_name(from),
+                status_name(to)
+            ))
+        })?;
+    if !transition.allowed_roles.contains(&perm.role) {
+        return Err(AppError::Forbidden(format!(
+            "Role {} cannot move tasks from {} to {}",
+            perm.role,
+            status_name(from),
+            status_name(to)
+        )));
+    }
+
+    // 检查点需签核通过后才能进入完成状态；在写入事务中读取签核结果
+    let mut tx = state.pool.begin().await?;
+    if TaskType::from_i32(task.task_type) == Some(TaskType::Checkpoint)
+        && workflow.status_category(to) == Some(StatusCategory::Done)
+        && CheckpointRepository::get_latest_decision(&mut tx, task.id.0).await?
+            != Some(CheckpointDecision::Approved)
+    {
+        return Err(AppError::Conflict(
+            "Checkpoint must be signed off before it can be completed".to_string(),
+        ));
+    }
+
+    let updated = TaskRepository::update_task_status(
+        &mut tx,
+        task.id.0,
+        task.status_id.map(|id| id.0),
+        to,
+        claims.sub,
+    )
+    .await?;
+    record_history(&state, &mut tx, &[task], std::slice::from_ref(&updated), claims.sub).await?;
+    tx.commit().await?;
+    Ok(Json(ApiResponse::success(updated)))
+}
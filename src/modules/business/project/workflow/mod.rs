@@ -0,0 +1,7 @@
+pub mod definition;
+pub mod handlers;
+pub mod models;
+pub mod repository;
+pub mod routes;
+
+pub use routes::*;
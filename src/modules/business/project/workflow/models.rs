@@ -0,0 +1,159 @@
+use crate::common::id::Id;
+use crate::modules::business::project::permission::models::ProjectRole;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, Row};
+
+/// 状态名最大长度（字符）
+pub const MAX_STATUS_NAME_LENGTH: usize = 64;
+
+/// 单个工作流的最大状态数
+pub const MAX_STATUSES: usize = 50;
+
+/// 状态分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    Todo = 0,
+    InProgress = 1,
+    Done = 2,
+}
+
+impl StatusCategory {
+    pub fn from_i16(v: i16) -> Option<Self> {
+        match v {
+            0 => Some(StatusCategory::Todo),
+            1 => Some(StatusCategory::InProgress),
+            2 => Some(StatusCategory::Done),
+            _ => None,
+        }
+    }
+
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStatus {
+    pub id: Id,
+    pub project_id: Id,
+    pub status_name: String,
+    pub category: StatusCategory,
+    pub is_initial: bool,
+    pub order: f64,
+    pub creator_id: Id,
+    pub create_date_time: chrono::NaiveDateTime,
+}
+
+impl FromRow<'_, PgRow> for WorkflowStatus {
+    fn from_row(row: &PgRow) -> sqlx::Result<Self> {
+        let category: i16 = row.try_get("category")?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            project_id: row.try_get("project_id")?,
+            status_name: row.try_get("status_name")?,
+            category: StatusCategory::from_i16(category).ok_or_else(|| {
+                sqlx::Error::Decode(format!("Invalid status category: {}", category).into())
+            })?,
+            is_initial: row.try_get("is_initial")?,
+            order: row.try_get("order")?,
+            creator_id: row.try_get("creator_id")?,
+            create_date_time: row.try_get("create_date_time")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowTransition {
+    pub id: Id,
+    pub project_id: Id,
+    pub from_status_id: Id,
+    pub to_status_id: Id,
+    /// 可执行该流转的项目角色
+    pub allowed_roles: Vec<ProjectRole>,
+}
+
+impl FromRow<'_, PgRow> for WorkflowTransition {
+    fn from_row(row: &PgRow) -> sqlx::Result<Self> {
+        let roles: Vec<i16> = row.try_get("allowed_roles")?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            project_id: row.try_get("project_id")?,
+            from_status_id: row.try_get("from_status_id")?,
+            to_status_id: row.try_get("to_status_id")?,
+            allowed_roles: roles
+                .into_iter()
+                .filter_map(|r| ProjectRole::from_i32(r as i32))
+                .collect(),
+        })
+    }
+}
+
+/// 项目工作流：全部状态（按 order 排序）及流转
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workflow {
+    pub statuses: Vec<WorkflowStatus>,
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+/// 保存工作流时的状态；id 为空表示新增状态，已有状态需带 id 以保留任务引用
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStatusItem {
+    pub id: Option<Id>,
+    pub status_name: String,
+    pub category: StatusCategory,
+    #[serde(default)]
+    pub is_initial: bool,
+}
+
+/// 保存工作流时的流转，按状态名引用（新增状态尚无 id）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowTransitionItem {
+    pub from_status: String,
+    pub to_status: String,
+    pub allowed_roles: Vec<ProjectRole>,
+}
+
+/// 整体替换项目工作流；未出现的已有状态与全部旧流转会被删除，状态顺序即数组顺序
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveWorkflowParams {
+    pub statuses: Vec<WorkflowStatusItem>,
+    pub transitions: Vec<WorkflowTransitionItem>,
+}
+
+/// 待写入的工作流（已分配 id）
+#[derive(Debug, Clone)]
+pub struct NewWorkflow {
+    pub statuses: Vec<NewWorkflowStatus>,
+    pub transitions: Vec<NewWorkflowTransition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWorkflowStatus {
+    pub id: i64,
+    pub status_name: String,
+    pub category: StatusCategory,
+    pub is_initial: bool,
+    pub order: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWorkflowTransition {
+    pub id: i64,
+    pub from_status_id: i64,
+    pub to_status_id: i64,
+    pub allowed_roles: Vec<ProjectRole>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionTaskParams {
+    pub to_status_id: Id,
+}
@@ -0,0 +1,157 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::workflow::models::{
+    NewWorkflow, Workflow, WorkflowStatus, WorkflowTransition,
+};
+use sqlx::{PgConnection, PgPool, QueryBuilder};
+
+pub struct WorkflowRepository;
+
+/// project_workflow_statuses 表 SELECT 列
+const STATUS_COLUMNS: &str = r#"id, project_id, status_name, category, is_initial, "order",
+    creator_id, create_date_time"#;
+
+impl WorkflowRepository {
+    pub async fn get_workflow(pool: &PgPool, project_id: i64) -> AppResult<Workflow> {
+        let statuses = sqlx::query_as::<_, WorkflowStatus>(&format!(
+            r#"SELECT {} FROM project_workflow_statuses
+               WHERE project_id = $1
+               ORDER BY "order" ASC, id ASC"#,
+            STATUS_COLUMNS,
+        ))
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let transitions = sqlx::query_as::<_, WorkflowTransition>(
+            r#"SELECT t.id, t.project_id, t.from_status_id, t.to_status_id, t.allowed_roles
+               FROM project_workflow_transitions t
+               INNER JOIN project_workflow_statuses s ON s.id = t.to_status_id
+               WHERE t.project_id = $1
+               ORDER BY t.from_status_id ASC, s."order" ASC"#,
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Workflow {
+            statuses,
+            transitions,
+        })
+    }
+
+    /// 锁定项目的全部工作流状态（FOR UPDATE），任务在事务提交前无法进入这些状态；需在事务中调用
+    pub async fn lock_statuses(
+        conn: &mut PgConnection,
+        project_id: i64,
+    ) -> AppResult<Vec<WorkflowStatus>> {
+        let statuses = sqlx::query_as::<_, WorkflowStatus>(&format!(
+            r#"SELECT {} FROM project_workflow_statuses
+               WHERE project_id = $1
+               ORDER BY id
+               FOR UPDATE"#,
+            STATUS_COLUMNS,
+        ))
+        .bind(project_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(statuses)
+    }
+
+    /// 写入工作流状态（已存在的 id 更新名称、分类与顺序）及流转
+    pub async fn insert_workflow(
+        conn: &mut PgConnection,
+        project_id: i64,
+        workflow: &NewWorkflow,
+        creator_id: i64,
+    ) -> AppResult<()> {
+        if !workflow.statuses.is_empty() {
+            let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
+                r#"INSERT INTO project_workflow_statuses
+                   (id, project_id, status_name, category, is_initial, "order", creator_id) "#,
+            );
+            qb.push_values(workflow.statuses.iter(), |mut b, status| {
+                b.push_bind(status.id)
+                    .push_bind(project_id)
+                    .push_bind(status.status_name.clone())
+                    .push_bind(status.category.as_i16())
+                    .push_bind(status.is_initial)
+                    .push_bind(status.order)
+                    .push_bind(creator_id);
+            });
+            qb.push(
+                r#" ON CONFLICT (id) DO UPDATE
+                    SET status_name = EXCLUDED.status_name,
+                        category = EXCLUDED.category,
+                        is_initial = EXCLUDED.is_initial,
+                        "order" = EXCLUDED."order""#,
+            );
+            qb.build().execute(&mut *conn).await?;
+        }
+
+        if !workflow.transitions.is_empty() {
+            let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
+                r#"INSERT INTO project_workflow_transitions
+                   (id, project_id, from_status_id, to_status_id, allowed_roles) "#,
+            );
+            qb.push_values(workflow.transitions.iter(), |mut b, transition| {
+                let roles: Vec<i16> = transition
+                    .allowed_roles
+                    .iter()
+                    .map(|r| r.as_i32() as i16)
+                    .collect();
+                b.push_bind(transition.id)
+                    .push_bind(project_id)
+                    .push_bind(transition.from_status_id)
+                    .push_bind(transition.to_status_id)
+                    .push_bind(roles);
+            });
+            qb.build().execute(&mut *conn).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 整体替换项目工作流：删除旧流转与未保留的状态后写入新定义（同一事务）
+    pub async fn replace_workflow(
+        conn: &mut PgConnection,
+        project_id: i64,
+        workflow: &NewWorkflow,
+        creator_id: i64,
+    ) -> AppResult<()> {
+        let status_ids: Vec<i64> = workflow.statuses.iter().map(|s| s.id).collect();
+
+        sqlx::query("DELETE FROM project_workflow_transitions WHERE project_id = $1")
+            .bind(project_id)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(
+            "DELETE FROM project_workflow_statuses WHERE project_id = $1 AND NOT (id = ANY($2))",
+        )
+        .bind(project_id)
+        .bind(&status_ids)
+        .execute(&mut *conn)
+        .await?;
+        Self::insert_workflow(conn, project_id, workflow, creator_id).await?;
+
+        Ok(())
+    }
+
+    /// 返回仍有任务处于其中的状态 id；应在 [`Self::lock_statuses`] 之后于同一事务中调用
+    pub async fn get_statuses_in_use(
+        conn: &mut PgConnection,
+        status_ids: &[i64],
+    ) -> AppResult<Vec<i64>> {
+        if status_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let rows: Vec<(i64,)> = sqlx::query_as(
+            "SELECT DISTINCT status_id FROM project_tasks WHERE status_id = ANY($1)",
+        )
+        .bind(status_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::business::project::workflow::handlers;
+use axum::{
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+
+pub fn workflow_routes(state: AppState) -> Router {
+    Router::new()
+        .route(
+            "/projects/{project_id}/workflow",
+            get(handlers::get_workflow),
+        )
+        .route(
+            "/projects/{project_id}/workflow",
+            put(handlers::save_workflow),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/transitions",
+            get(handlers::get_task_transitions),
+        )
+        .route(
+            "/projects/{project_id}/tasks/{task_id}/transitions",
+            post(handlers::transition_task),
+        )
+        // 项目权限中间件（需要 Claims 已注入）
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
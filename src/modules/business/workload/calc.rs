@@ -0,0 +1,158 @@
+use crate::modules::business::workload::models::{
+    UserWorkload, WorkloadAssignment, WorkloadDay,
+};
+use crate::modules::holiday::calendar::WorkingCalendar;
+use chrono::NaiveDate;
+
+/// 浮点比较容差，避免半天假等系数累加误差导致误判超负荷
+const HOURS_EPSILON: f64 = 1e-9;
+
+/// 计算用户在 [from, to] 内每天的负荷
+///
+/// 每个任务在其起止日期内的每个工作日需要一人天的工作量，由该任务的指派人平摊；
+/// 半天假按 0.5 折算，非工作日不占用工时；分配工时超过可用工时即为超负荷。
+pub fn user_days(
+    assignments: &[&WorkloadAssignment],
+    calendar: &WorkingCalendar,
+    from: NaiveDate,
+    to: NaiveDate,
+    capacity_hours: f64,
+) -> Vec<WorkloadDay> {
+    from.iter_days()
+        .take_while(|d| *d <= to)
+        .map(|date| {
+            let capacity = capacity_hours * calendar.working_day_fraction(date);
+            let mut active: Vec<&WorkloadAssignment> = if capacity > 0.0 {
+                assignments
+                    .iter()
+                    .copied()
+                    .filter(|a| a.start_date <= date && date <= a.end_date)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            active.sort_by_key(|a| a.task_id);
+            active.dedup_by_key(|a| a.task_id);
+            let allocated: f64 = active
+                .iter()
+                .map(|a| capacity / a.assignee_count.max(1) as f64)
+                .sum();
+            WorkloadDay {
+                date,
+                capacity_hours: capacity,
+                allocated_hours: allocated,
+                overallocated: allocated > capacity + HOURS_EPSILON,
+                task_ids: active.iter().map(|a| a.task_id).collect(),
+            }
+        })
+        .collect()
+}
+
+/// 汇总：(总可用工时, 总分配工时, 超负荷天数)
+pub fn summarize(days: &[WorkloadDay]) -> (f64, f64, i64) {
+    days.iter().fold((0.0, 0.0, 0), |(capacity, allocated, over), d| {
+        (
+            capacity + d.capacity_hours,
+            allocated + d.allocated_hours,
+            over + d.overallocated as i64,
+        )
+    })
+}
+
+/// 按天合并多个成员的负荷；任一成员当天超负荷即标记该天超负荷
+/// （成员之间的空闲工时不能互相抵消）
+pub fn merge_days(members: &[&UserWorkload]) -> Vec<WorkloadDay> {
+    let Some(first) = members.first() else {
+        return Vec::new();
+    };
+    (0..first.days.len())
+        .map(|i| {
+            let mut day = WorkloadDay {
+                date: first.days[i].date,
+                capacity_hours: 0.0,
+                allocated_hours: 0.0,
+                task_ids: Vec::new(),
+                overallocated: false,
+            };
+            for member in members {
+                let d = &member.days[i];
+                day.capacity_hours += d.capacity_hours;
+                day.allocated_hours += d.allocated_hours;
+                day.task_ids.extend(d.task_ids.iter().copied());
+                day.overallocated |= d.overallocated;
+            }
+            day.task_ids.sort();
+            day.task_ids.dedup();
+            day
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::id::Id;
+    use crate::modules::holiday::models::HolidayType;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn assignment(task_id: i64, start: u32, end: u32) -> WorkloadAssignment {
+        WorkloadAssignment {
+            user_id: Id(1),
+            task_id: Id(task_id),
+            start_date: date(start),
+            end_date: date(end),
+            assignee_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_user_days_honours_holidays() {
+        // 2026-10-01 ~ 10-07 国庆假期，10-09 半天假，10-10（周六）调休上班
+        let calendar = WorkingCalendar::new(
+            (1..=7)
+                .map(|d| (date(d), HolidayType::PublicHoliday))
+                .chain([
+                    (date(9), HolidayType::HalfDay),
+                    (date(10), HolidayType::MakeUpWorkday),
+                ]),
+        );
+        let a = assignment(1, 6, 12);
+        let b = assignment(2, 9, 9);
+        let days = user_days(&[&a, &b], &calendar, date(6), date(11), 8.0);
+
+        let hours: Vec<(f64, f64, bool)> = days
+            .iter()
+            .map(|d| (d.capacity_hours, d.allocated_hours, d.overallocated))
+            .collect();
+        assert_eq!(
+            hours,
+            vec![
+                (0.0, 0.0, false),
+                (0.0, 0.0, false),
+                (8.0, 8.0, false),
+                (4.0, 8.0, true),
+                (8.0, 8.0, false),
+                (0.0, 0.0, false),
+            ]
+        );
+        assert_eq!(days[3].task_ids, vec![Id(1), Id(2)]);
+        assert_eq!(summarize(&days), (20.0, 24.0, 1));
+    }
+
+    #[test]
+    fn test_user_days_splits_shared_tasks_between_assignees() {
+        let calendar = WorkingCalendar::new([]);
+        let a = WorkloadAssignment { assignee_count: 2, ..assignment(1, 12, 13) };
+        let b = WorkloadAssignment { assignee_count: 2, ..assignment(2, 13, 14) };
+        let c = assignment(3, 14, 14);
+        let days = user_days(&[&a, &b, &c], &calendar, date(12), date(14), 8.0);
+
+        let hours: Vec<(f64, bool)> =
+            days.iter().map(|d| (d.allocated_hours, d.overallocated)).collect();
+        assert_eq!(hours, vec![(4.0, false), (8.0, false), (12.0, true)]);
+        assert_eq!(days[1].task_ids, vec![Id(1), Id(2)]);
+    }
+}
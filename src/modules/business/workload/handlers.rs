@@ -0,0 +1,171 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::workload::calc;
+use crate::modules::business::workload::models::{
+    GroupWorkload, UserWorkload, WorkloadAssignment, WorkloadGroupBy, WorkloadQueryParams,
+    WorkloadReport, DEFAULT_CAPACITY_HOURS, MAX_WORKLOAD_DAYS,
+};
+use crate::modules::business::workload::repository::WorkloadRepository;
+use crate::modules::holiday::calendar::WorkingCalendar;
+use crate::modules::holiday::holiday_calendar::repository::HolidayCalendarRepository;
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use std::collections::HashMap;
+
+/// 资源负荷报表
+///
+/// 管理员可查询任意用户并按团队 / 部门汇总；普通用户仅能查询自己的负荷。
+pub async fn get_workload(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<WorkloadQueryParams>,
+) -> AppResult<Json<ApiResponse<WorkloadReport>>> {
+    if params.date_from > params.date_to {
+        return Err(AppError::BadRequest(
+            "dateFrom must not be after dateTo".to_string(),
+        ));
+    }
+    if (params.date_to - params.date_from).num_days() >= MAX_WORKLOAD_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Date range must not exceed {} days",
+            MAX_WORKLOAD_DAYS
+        )));
+    }
+    let capacity_hours = params.capacity_hours.unwrap_or(DEFAULT_CAPACITY_HOURS);
+    if !(capacity_hours > 0.0 && capacity_hours <= 24.0) {
+        return Err(AppError::BadRequest(
+            "capacityHours must be greater than 0 and at most 24".to_string(),
+        ));
+    }
+
+    let user_id = if claims.is_admin_or_above() {
+        params.user_id.map(|id| id.0)
+    } else {
+        if params.group_by != WorkloadGroupBy::User
+            || params.team_id.is_some()
+            || params.department_id.is_some()
+            || params.user_id.is_some_and(|id| id.0 != claims.sub)
+        {
+            return Err(AppError::Forbidden(
+                "Only admins can view workload of other users".to_string(),
+            ));
+        }
+        Some(claims.sub)
+    };
+
+    let users = WorkloadRepository::get_users(
+        &state.pool,
+        user_id,
+        params.team_id.map(|id| id.0),
+        params.department_id.map(|id| id.0),
+    )
+    .await?;
+    let user_ids: Vec<i64> = users.iter().map(|u| u.id.0).collect();
+
+    let assignments =
+        WorkloadRepository::get_assignments(&state.pool, &user_ids, params.date_from, params.date_to)
+            .await?;
+    let mut by_user: HashMap<i64, Vec<&WorkloadAssignment>> = HashMap::new();
+    for assignment in &assignments {
+        by_user.entry(assignment.user_id.0).or_default().push(assignment);
+    }
+
+    // 同一日历只加载一次
+    let mut user_calendar_ids: HashMap<i64, i64> = HashMap::new();
+    let mut calendars: HashMap<i64, WorkingCalendar> = HashMap::new();
+    for user in &users {
+        let calendar_id =
+            HolidayCalendarRepository::resolve_user_calendar_id(&state.pool, user.id.0).await?;
+        user_calendar_ids.insert(user.id.0, calendar_id);
+        if calendars.contains_key(&calendar_id) {
+            continue;
+        }
+        let calendar =
+            WorkingCalendar::load_between(&state.pool, calendar_id, params.date_from, params.date_to)
+                .await?;
+        calendars.insert(calendar_id, calendar);
+    }
+
+    let mut workloads = Vec::with_capacity(users.len());
+    for user in users {
+        let calendar_id = user_calendar_ids[&user.id.0];
+        let days = calc::user_days(
+            by_user.get(&user.id.0).map(Vec::as_slice).unwrap_or_default(),
+            &calendars[&calendar_id],
+            params.date_from,
+            params.date_to,
+            capacity_hours,
+        );
+        let (total_capacity_hours, total_allocated_hours, overallocated_days) =
+            calc::summarize(&days);
+        workloads.push(UserWorkload {
+            user_id: user.id,
+            username: user.username,
+            full_name: user.full_name,
+            calendar_id: Id(calendar_id),
+            total_capacity_hours,
+            total_allocated_hours,
+            overallocated_days,
+            days,
+        });
+    }
+
+    let group_id = match params.group_by {
+        WorkloadGroupBy::Team => params.team_id,
+        WorkloadGroupBy::Department => params.department_id,
+        WorkloadGroupBy::User => None,
+    };
+    let members = WorkloadRepository::get_group_members(
+        &state.pool,
+        params.group_by,
+        group_id.map(|id| id.0),
+        &user_ids,
+    )
+    .await?;
+
+    let mut groups: Vec<GroupWorkload> = Vec::new();
+    for member in members {
+        if groups.last().is_none_or(|g| g.group_id != member.group_id) {
+            groups.push(GroupWorkload {
+                group_id: member.group_id,
+                group_name: member.group_name,
+                member_ids: Vec::new(),
+                total_capacity_hours: 0.0,
+                total_allocated_hours: 0.0,
+                overallocated_user_ids: Vec::new(),
+                days: Vec::new(),
+            });
+        }
+        if let Some(group) = groups.last_mut() {
+            group.member_ids.push(member.user_id);
+        }
+    }
+    for group in &mut groups {
+        let group_members: Vec<&UserWorkload> = workloads
+            .iter()
+            .filter(|w| group.member_ids.contains(&w.user_id))
+            .collect();
+        group.overallocated_user_ids = group_members
+            .iter()
+            .filter(|w| w.overallocated_days > 0)
+            .map(|w| w.user_id)
+            .collect();
+        group.days = calc::merge_days(&group_members);
+        let (capacity, allocated, _) = calc::summarize(&group.days);
+        group.total_capacity_hours = capacity;
+        group.total_allocated_hours = allocated;
+    }
+
+    Ok(Json(ApiResponse::success(WorkloadReport {
+        date_from: params.date_from,
+        date_to: params.date_to,
+        capacity_hours,
+        users: workloads,
+        groups,
+    })))
+}
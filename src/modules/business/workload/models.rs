@@ -0,0 +1,107 @@
+use crate::common::id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 默认每个工作日的可用工时
+pub const DEFAULT_CAPACITY_HOURS: f64 = 8.0;
+
+/// 单次查询的最大天数
+pub const MAX_WORKLOAD_DAYS: i64 = 366;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkloadGroupBy {
+    #[default]
+    User,
+    Team,
+    Department,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadQueryParams {
+    pub date_from: chrono::NaiveDate,
+    pub date_to: chrono::NaiveDate,
+    #[serde(default)]
+    pub group_by: WorkloadGroupBy,
+    pub user_id: Option<Id>,
+    pub team_id: Option<Id>,
+    pub department_id: Option<Id>,
+    /// 每个工作日的可用工时，默认 8
+    pub capacity_hours: Option<f64>,
+}
+
+/// 指派记录（任务起止时间已截取到日期）
+#[derive(Debug, Clone, FromRow)]
+pub struct WorkloadAssignment {
+    pub user_id: Id,
+    pub task_id: Id,
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    /// 任务的指派人数，任务每个工作日的工作量由指派人平摊
+    pub assignee_count: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct WorkloadUser {
+    pub id: Id,
+    pub username: String,
+    pub full_name: String,
+}
+
+/// 团队 / 部门成员关系
+#[derive(Debug, Clone, FromRow)]
+pub struct WorkloadGroupMember {
+    pub group_id: Id,
+    pub group_name: String,
+    pub user_id: Id,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadDay {
+    pub date: chrono::NaiveDate,
+    pub capacity_hours: f64,
+    pub allocated_hours: f64,
+    /// 当天占用工时的任务
+    pub task_ids: Vec<Id>,
+    pub overallocated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserWorkload {
+    pub user_id: Id,
+    pub username: String,
+    pub full_name: String,
+    /// 用户适用的假期日历：所属团队 > 所属部门 > 默认日历
+    pub calendar_id: Id,
+    pub total_capacity_hours: f64,
+    pub total_allocated_hours: f64,
+    pub overallocated_days: i64,
+    pub days: Vec<WorkloadDay>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupWorkload {
+    pub group_id: Id,
+    pub group_name: String,
+    pub member_ids: Vec<Id>,
+    pub total_capacity_hours: f64,
+    pub total_allocated_hours: f64,
+    /// 存在超负荷日期的成员
+    pub overallocated_user_ids: Vec<Id>,
+    pub days: Vec<WorkloadDay>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadReport {
+    pub date_from: chrono::NaiveDate,
+    pub date_to: chrono::NaiveDate,
+    pub capacity_hours: f64,
+    pub users: Vec<UserWorkload>,
+    /// 按团队 / 部门汇总时返回
+    pub groups: Vec<GroupWorkload>,
+}
@@ -0,0 +1,103 @@
+use crate::common::error::AppResult;
+use crate::modules::business::project::task::models::TaskType;
+use crate::modules::business::workload::models::{
+    WorkloadAssignment, WorkloadGroupBy, WorkloadGroupMember, WorkloadUser,
+};
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+pub struct WorkloadRepository;
+
+impl WorkloadRepository {
+    /// 查询参与统计的在职用户，可按用户 / 团队 / 部门过滤
+    pub async fn get_users(
+        pool: &PgPool,
+        user_id: Option<i64>,
+        team_id: Option<i64>,
+        department_id: Option<i64>,
+    ) -> AppResult<Vec<WorkloadUser>> {
+        let users = sqlx::query_as::<_, WorkloadUser>(
+            r#"SELECT u.id, u.username, u.full_name
+               FROM users u
+               WHERE u.is_active = TRUE
+                 AND ($1::BIGINT IS NULL OR u.id = $1)
+                 AND ($2::BIGINT IS NULL OR EXISTS (
+                     SELECT 1 FROM user_teams ut WHERE ut.user_id = u.id AND ut.team_id = $2
+                 ))
+                 AND ($3::BIGINT IS NULL OR EXISTS (
+                     SELECT 1 FROM user_departments ud
+                     WHERE ud.user_id = u.id AND ud.department_id = $3
+                 ))
+               ORDER BY u.username ASC"#,
+        )
+        .bind(user_id)
+        .bind(team_id)
+        .bind(department_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// 查询用户所属的团队或部门
+    pub async fn get_group_members(
+        pool: &PgPool,
+        group_by: WorkloadGroupBy,
+        group_id: Option<i64>,
+        user_ids: &[i64],
+    ) -> AppResult<Vec<WorkloadGroupMember>> {
+        let sql = match group_by {
+            WorkloadGroupBy::User => return Ok(Vec::new()),
+            WorkloadGroupBy::Team => {
+                r#"SELECT t.id AS group_id, t.team_name AS group_name, ut.user_id
+                   FROM user_teams ut
+                   JOIN teams t ON t.id = ut.team_id
+                   WHERE ut.user_id = ANY($1) AND ($2::BIGINT IS NULL OR t.id = $2)
+                   ORDER BY t.team_name ASC, t.id ASC"#
+            }
+            WorkloadGroupBy::Department => {
+                r#"SELECT d.id AS group_id, d.department_name AS group_name, ud.user_id
+                   FROM user_departments ud
+                   JOIN departments d ON d.id = ud.department_id
+                   WHERE ud.user_id = ANY($1) AND ($2::BIGINT IS NULL OR d.id = $2)
+                   ORDER BY d.department_name ASC, d.id ASC"#
+            }
+        };
+        let members = sqlx::query_as::<_, WorkloadGroupMember>(sql)
+            .bind(user_ids)
+            .bind(group_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(members)
+    }
+
+    /// 查询用户在所有项目中与 [from, to] 有交集的任务指派（里程碑、检查点不占用工时）
+    pub async fn get_assignments(
+        pool: &PgPool,
+        user_ids: &[i64],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<WorkloadAssignment>> {
+        let assignments = sqlx::query_as::<_, WorkloadAssignment>(
+            r#"SELECT a.user_id, a.task_id,
+                   t.start_date_time::DATE AS start_date, t.end_date_time::DATE AS end_date,
+                   (SELECT COUNT(*) FROM project_task_assignees x
+                    WHERE x.task_id = a.task_id) AS assignee_count
+               FROM project_task_assignees a
+               JOIN project_tasks t ON t.id = a.task_id
+               WHERE a.user_id = ANY($1)
+                 AND t.end_date_time >= $2
+                 AND t.start_date_time < $3::DATE + 1
+                 AND NOT (t.task_type = ANY($4))"#,
+        )
+        .bind(user_ids)
+        .bind(from)
+        .bind(to)
+        .bind(vec![TaskType::Milestone.as_i32(), TaskType::Checkpoint.as_i32()])
+        .fetch_all(pool)
+        .await?;
+
+        Ok(assignments)
+    }
+}
@@ -0,0 +1,15 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::workload::handlers;
+use axum::{middleware, routing::get, Router};
+
+pub fn workload_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/workload", get(handlers::get_workload))
+        // JWT 认证中间件
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ))
+        .with_state(state)
+}
@@ -0,0 +1,138 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::business::project::permission::models::{Permission, ProjectPermission};
+use crate::modules::business::project::repository::ProjectRepository;
+use crate::modules::business::project::task::models::TaskType;
+use crate::modules::business::project::task::repository::TaskRepository;
+use crate::modules::calendar_feed::ical::{render_calendar, IcsEvent};
+use crate::modules::calendar_feed::models::{CalendarFeedQuery, CalendarFeedTokenResponse};
+use crate::modules::calendar_feed::repository::CalendarFeedRepository;
+use crate::modules::holiday::models::{HolidayQueryParams, HolidayType};
+use crate::modules::holiday::repository::HolidayRepository;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Duration;
+
+/// 生成 64 位十六进制随机令牌
+fn generate_feed_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+fn calendar_response(body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+async fn create_feed_token(
+    state: &AppState,
+    user_id: i64,
+) -> AppResult<CalendarFeedTokenResponse> {
+    let id = state.generate_id().map_err(|e| {
+        AppError::InternalError(format!("Failed to generate calendar feed token ID: {}", e))
+    })?;
+    let token =
+        CalendarFeedRepository::upsert_token(&state.pool, id, user_id, &generate_feed_token())
+            .await?;
+    Ok(token.into())
+}
+
+/// 获取当前用户的订阅令牌，不存在时自动创建
+pub async fn get_feed_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<ApiResponse<CalendarFeedTokenResponse>>> {
+    let token = match CalendarFeedRepository::get_token_by_user(&state.pool, claims.sub).await? {
+        Some(token) => token.into(),
+        None => create_feed_token(&state, claims.sub).await?,
+    };
+    Ok(Json(ApiResponse::success(token)))
+}
+
+/// 重置订阅令牌，旧的订阅地址立即失效
+pub async fn reset_feed_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<ApiResponse<CalendarFeedTokenResponse>>> {
+    let token = create_feed_token(&state, claims.sub).await?;
+    Ok(Json(ApiResponse::success(token)))
+}
+
+/// 假期订阅：每个假期一个全天事件，CATEGORIES 为假期类型（可被假期导入识别）
+pub async fn get_holiday_feed(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarFeedQuery>,
+) -> AppResult<Response> {
+    let params = HolidayQueryParams {
+        calendar_id: query.calendar_id,
+        ..Default::default()
+    };
+    let holidays = HolidayRepository::get_all_holidays(&state.pool, params).await?;
+    let events: Vec<IcsEvent> = holidays
+        .into_iter()
+        .map(|h| IcsEvent {
+            uid: format!("holiday-{}@demeter", h.id),
+            summary: h.holiday_name,
+            description: h.description,
+            start: h.holiday_date,
+            end: h.holiday_date + Duration::days(1),
+            categories: HolidayType::from_i32(h.holiday_type).map(|t| t.as_str().to_string()),
+        })
+        .collect();
+    Ok(calendar_response(render_calendar("Holidays", &events)))
+}
+
+/// 项目订阅：里程碑 / 检查点任务，按任务起止日期生成全天事件
+pub async fn get_project_feed(
+    State(state): State<AppState>,
+    Extension(perm): Extension<ProjectPermission>,
+    Path((_token, project_id)): Path<(String, Id)>,
+) -> AppResult<Response> {
+    perm.require(Permission::TaskView)?;
+    let project = ProjectRepository::get_project_by_id(&state.pool, project_id.0)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", project_id)))?;
+    let tasks = TaskRepository::get_tasks_by_types(
+        &state.pool,
+        project_id.0,
+        &[TaskType::Milestone.as_i32(), TaskType::Checkpoint.as_i32()],
+    )
+    .await?;
+
+    let events: Vec<IcsEvent> = tasks
+        .into_iter()
+        .map(|t| {
+            let start = t.start_date_time.date();
+            let end = t.end_date_time.date().max(start);
+            let category = match TaskType::from_i32(t.task_type) {
+                Some(TaskType::Milestone) => "MILESTONE",
+                _ => "CHECKPOINT",
+            };
+            IcsEvent {
+                uid: format!("task-{}@demeter", t.id),
+                summary: t.task_name,
+                description: Some(project.project_name.clone()),
+                start,
+                end: end + Duration::days(1),
+                categories: Some(category.to_string()),
+            }
+        })
+        .collect();
+    Ok(calendar_response(render_calendar(&project.project_name, &events)))
+}
@@ -0,0 +1,108 @@
+use chrono::{NaiveDate, Utc};
+
+/// 全天事件；`end` 为不包含的结束日期（RFC 5545 DTEND 语义）
+#[derive(Debug, Clone)]
+pub struct IcsEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub categories: Option<String>,
+}
+
+/// 转义 TEXT 值中的 \ ; , 与换行
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// 写入一行内容，超过 75 字节时按 RFC 5545 折行（不拆分 UTF-8 字符）
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// 生成 VCALENDAR 文本
+pub fn render_calendar(name: &str, events: &[IcsEvent]) -> String {
+    let dtstamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//Demeter//Calendar Feed//ZH");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(name)));
+    for event in events {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}", event.uid));
+        push_line(&mut out, &format!("DTSTAMP:{}", dtstamp));
+        push_line(
+            &mut out,
+            &format!("DTSTART;VALUE=DATE:{}", event.start.format("%Y%m%d")),
+        );
+        push_line(
+            &mut out,
+            &format!("DTEND;VALUE=DATE:{}", event.end.format("%Y%m%d")),
+        );
+        push_line(&mut out, &format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(description) = &event.description {
+            push_line(&mut out, &format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(categories) = &event.categories {
+            push_line(&mut out, &format!("CATEGORIES:{}", escape_text(categories)));
+        }
+        push_line(&mut out, "TRANSP:TRANSPARENT");
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_and_folds() {
+        let event = IcsEvent {
+            uid: "holiday-1@demeter".to_string(),
+            summary: "New Year; Day, off".to_string(),
+            description: Some("中".repeat(40)),
+            start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(2026, 1, 2).unwrap(),
+            categories: Some("public_holiday".to_string()),
+        };
+        let ics = render_calendar("Holidays", &[event]);
+
+        assert!(ics.contains("SUMMARY:New Year\\; Day\\, off\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260101\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20260102\r\n"));
+        assert!(ics.lines().all(|l| l.len() <= 75));
+        let description: String = ics
+            .split("\r\n")
+            .skip_while(|l| !l.starts_with("DESCRIPTION:"))
+            .take_while(|l| l.starts_with("DESCRIPTION:") || l.starts_with(' '))
+            .map(|l| l.strip_prefix(' ').unwrap_or(l))
+            .collect();
+        assert_eq!(description, format!("DESCRIPTION:{}", "中".repeat(40)));
+    }
+}
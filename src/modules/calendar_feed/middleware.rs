@@ -0,0 +1,87 @@
+use crate::common::error::AppError;
+use crate::common::jwt::Claims;
+use crate::modules::calendar_feed::repository::CalendarFeedRepository;
+use axum::{
+    extract::{Request, State},
+    http::Uri,
+    middleware::Next,
+    response::Response,
+};
+use sqlx::PgPool;
+
+/// 订阅地址中令牌所在段的前一段：/feeds/{token}/...
+const FEEDS_SEGMENT: &str = "feeds";
+
+/// 从订阅地址 /feeds/{token}/... 中提取令牌
+fn extract_feed_token(path: &str) -> Option<&str> {
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    segments.find(|s| *s == FEEDS_SEGMENT)?;
+    segments.next()
+}
+
+/// 用于日志的请求地址：订阅令牌替换为 ***
+pub fn redact_feed_token(uri: &Uri) -> String {
+    let path = uri.path();
+    let redacted = match extract_feed_token(path) {
+        Some(token) => path.replacen(
+            &format!("/{}/{}", FEEDS_SEGMENT, token),
+            &format!("/{}/***", FEEDS_SEGMENT),
+            1,
+        ),
+        None => path.to_string(),
+    };
+    match uri.query() {
+        Some(query) => format!("{}?{}", redacted, query),
+        None => redacted,
+    }
+}
+
+/// 日历订阅鉴权中间件：校验路径 /feeds/{token}/... 中的令牌，注入该用户的 Claims
+///
+/// 日历客户端无法携带 JWT Cookie，订阅地址改用每用户一个的长期令牌；令牌放在路径中而非查询参数，
+/// 以便请求日志统一脱敏（见 [`redact_feed_token`]）。
+/// 注入的 Claims 可与 project_permission_middleware 配合使用。
+pub async fn calendar_feed_auth_middleware(
+    State(pool): State<PgPool>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let token = extract_feed_token(request.uri().path())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| AppError::Unauthorized("Missing calendar feed token".to_string()))?;
+
+    let user = CalendarFeedRepository::get_user_by_token(&pool, token)
+        .await?
+        .filter(|u| u.is_active)
+        .ok_or_else(|| AppError::Unauthorized("Invalid calendar feed token".to_string()))?;
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    request.extensions_mut().insert(Claims {
+        sub: user.user_id,
+        role: user.role.to_string(),
+        exp: now,
+        iat: now,
+        token_type: "calendar_feed".to_string(),
+    });
+
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_feed_token() {
+        let uri: Uri = "/api/v1/feeds/abc123/projects/42/calendar.ics?x=1".parse().unwrap();
+        assert_eq!(extract_feed_token(uri.path()), Some("abc123"));
+        assert_eq!(
+            redact_feed_token(&uri),
+            "/api/v1/feeds/***/projects/42/calendar.ics?x=1"
+        );
+
+        let uri: Uri = "/api/v1/projects/42/tasks".parse().unwrap();
+        assert_eq!(extract_feed_token(uri.path()), None);
+        assert_eq!(redact_feed_token(&uri), "/api/v1/projects/42/tasks");
+    }
+}
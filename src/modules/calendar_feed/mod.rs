@@ -0,0 +1,7 @@
+pub use routes::*;
+pub mod handlers;
+pub mod ical;
+pub mod middleware;
+pub mod models;
+pub mod repository;
+pub mod routes;
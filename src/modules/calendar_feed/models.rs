@@ -0,0 +1,53 @@
+use crate::common::id::Id;
+use crate::modules::user::models::UserRole;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedToken {
+    pub id: Id,
+    pub user_id: Id,
+    pub token: String,
+    pub create_date_time: chrono::NaiveDateTime,
+}
+
+/// 订阅令牌对应的用户（用于订阅请求鉴权）
+#[derive(Debug, Clone, FromRow)]
+pub struct CalendarFeedUser {
+    pub user_id: i64,
+    pub role: UserRole,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedQuery {
+    /// 假期订阅使用的日历，不传则为默认日历
+    pub calendar_id: Option<Id>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeedTokenResponse {
+    pub token: String,
+    pub create_date_time: chrono::NaiveDateTime,
+    /// 假期订阅地址
+    pub holiday_feed_url: String,
+    /// 项目订阅地址模板，{projectId} 替换为项目 ID
+    pub project_feed_url: String,
+}
+
+impl From<CalendarFeedToken> for CalendarFeedTokenResponse {
+    fn from(t: CalendarFeedToken) -> Self {
+        Self {
+            holiday_feed_url: format!("/api/v1/feeds/{}/holidays.ics", t.token),
+            project_feed_url: format!(
+                "/api/v1/feeds/{}/projects/{{projectId}}/calendar.ics",
+                t.token
+            ),
+            token: t.token,
+            create_date_time: t.create_date_time,
+        }
+    }
+}
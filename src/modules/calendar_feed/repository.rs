@@ -0,0 +1,63 @@
+use crate::common::error::AppResult;
+use crate::modules::calendar_feed::models::{CalendarFeedToken, CalendarFeedUser};
+use sqlx::PgPool;
+
+pub struct CalendarFeedRepository;
+
+impl CalendarFeedRepository {
+    pub async fn get_token_by_user(
+        pool: &PgPool,
+        user_id: i64,
+    ) -> AppResult<Option<CalendarFeedToken>> {
+        let token = sqlx::query_as::<_, CalendarFeedToken>(
+            r#"SELECT id, user_id, token, create_date_time
+               FROM calendar_feed_tokens
+               WHERE user_id = $1"#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// 创建或替换用户的订阅令牌（每个用户仅保留一个）
+    pub async fn upsert_token(
+        pool: &PgPool,
+        id: i64,
+        user_id: i64,
+        token: &str,
+    ) -> AppResult<CalendarFeedToken> {
+        let token = sqlx::query_as::<_, CalendarFeedToken>(
+            r#"INSERT INTO calendar_feed_tokens (id, user_id, token, create_date_time)
+               VALUES ($1, $2, $3, NOW())
+               ON CONFLICT (user_id)
+               DO UPDATE SET token = EXCLUDED.token, create_date_time = NOW()
+               RETURNING id, user_id, token, create_date_time"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(token)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    pub async fn get_user_by_token(
+        pool: &PgPool,
+        token: &str,
+    ) -> AppResult<Option<CalendarFeedUser>> {
+        let user = sqlx::query_as::<_, CalendarFeedUser>(
+            r#"SELECT u.id AS user_id, u.role, u.is_active
+               FROM calendar_feed_tokens t
+               JOIN users u ON u.id = t.user_id
+               WHERE t.token = $1"#,
+        )
+        .bind(token)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
+}
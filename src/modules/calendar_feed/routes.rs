@@ -0,0 +1,49 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::jwt_auth_middleware;
+use crate::modules::business::project::permission::middleware::project_permission_middleware;
+use crate::modules::calendar_feed::handlers;
+use crate::modules::calendar_feed::middleware::calendar_feed_auth_middleware;
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+pub fn calendar_feed_routes(state: AppState) -> Router {
+    // Token management - JWT authenticated
+    let token_routes = Router::new()
+        .route("/calendar-feed/token", get(handlers::get_feed_token))
+        .route("/calendar-feed/token/reset", post(handlers::reset_feed_token))
+        .layer(middleware::from_fn_with_state(
+            state.jwt_config.clone(),
+            jwt_auth_middleware,
+        ));
+
+    // Feeds - authenticated by the token path segment since calendar clients cannot send the
+    // JWT cookie; the trace layer redacts it from request logs
+    let holiday_feed_routes = Router::new()
+        .route("/feeds/{token}/holidays.ics", get(handlers::get_holiday_feed))
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            calendar_feed_auth_middleware,
+        ));
+
+    let project_feed_routes = Router::new()
+        .route(
+            "/feeds/{token}/projects/{project_id}/calendar.ics",
+            get(handlers::get_project_feed),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            project_permission_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.pool.clone(),
+            calendar_feed_auth_middleware,
+        ));
+
+    token_routes
+        .merge(holiday_feed_routes)
+        .merge(project_feed_routes)
+        .with_state(state)
+}
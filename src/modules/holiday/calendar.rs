@@ -0,0 +1,229 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::modules::holiday::holiday_calendar::repository::HolidayCalendarRepository;
+use crate::modules::holiday::models::{Holiday, HolidayQueryParams, HolidayType};
+use crate::modules::holiday::repository::HolidayRepository;
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use sqlx::PgPool;
+use std::collections::HashSet;
+
+/// 单次推算允许的最大工作日数（约十年）
+pub const MAX_WORKING_DAYS: i64 = 3650;
+
+/// 单次推算允许的最大日期跨度（自然日），足以覆盖 MAX_WORKING_DAYS 个工作日
+pub const MAX_RANGE_DAYS: i64 = MAX_WORKING_DAYS * 2;
+
+fn date_out_of_range() -> AppError {
+    AppError::BadRequest("Date is out of the supported range".to_string())
+}
+
+/// 工作日历：默认周一至周五为工作日
+///
+/// - 法定节假日 / 公司假期：非工作日
+/// - 调休上班日：工作日（即使是周末）
+/// - 半天假：按工作日计，工时系数为 0.5
+#[derive(Debug, Clone, Default)]
+pub struct WorkingCalendar {
+    holidays: HashSet<NaiveDate>,
+    make_up_workdays: HashSet<NaiveDate>,
+    half_days: HashSet<NaiveDate>,
+}
+
+impl WorkingCalendar {
+    pub fn new(days: impl IntoIterator<Item = (NaiveDate, HolidayType)>) -> Self {
+        let mut calendar = Self::default();
+        for (date, holiday_type) in days {
+            match holiday_type {
+                HolidayType::PublicHoliday | HolidayType::CompanyHoliday => {
+                    calendar.holidays.insert(date);
+                }
+                HolidayType::MakeUpWorkday => {
+                    calendar.make_up_workdays.insert(date);
+                }
+                HolidayType::HalfDay => {
+                    calendar.half_days.insert(date);
+                }
+            }
+        }
+        calendar
+    }
+
+    /// 由 holidays 表记录构建；无法识别的旧类型值按法定节假日处理
+    pub fn from_holidays(holidays: Vec<Holiday>) -> Self {
+        Self::new(holidays.into_iter().map(|h| {
+            (
+                h.holiday_date,
+                HolidayType::from_i32(h.holiday_type).unwrap_or(HolidayType::PublicHoliday),
+            )
+        }))
+    }
+
+    /// 加载指定假期日历的全部假期
+    pub async fn load(pool: &PgPool, calendar_id: i64) -> AppResult<Self> {
+        let params = HolidayQueryParams {
+            calendar_id: Some(Id(calendar_id)),
+            ..Default::default()
+        };
+        let holidays = HolidayRepository::get_all_holidays(pool, params).await?;
+        Ok(Self::from_holidays(holidays))
+    }
+
+    /// 加载项目所使用的假期日历（未指定时为默认日历）
+    pub async fn load_for_project(pool: &PgPool, project_id: i64) -> AppResult<Self> {
+        let calendar_id =
+            HolidayCalendarRepository::resolve_project_calendar_id(pool, project_id).await?;
+        Self::load(pool, calendar_id).await
+    }
+
+    /// 仅加载 [start, end] 区间内的假期，适用于已知日期范围的计算（如统计、报表）
+    pub async fn load_between(
+        pool: &PgPool,
+        calendar_id: i64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AppResult<Self> {
+        let params = HolidayQueryParams {
+            calendar_id: Some(Id(calendar_id)),
+            start_date: Some(start),
+            end_date: Some(end),
+            ..Default::default()
+        };
+        let holidays = HolidayRepository::get_all_holidays(pool, params).await?;
+        Ok(Self::from_holidays(holidays))
+    }
+
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        if self.make_up_workdays.contains(&date) {
+            return true;
+        }
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.holidays.contains(&date)
+    }
+
+    /// 当天的工时系数：非工作日 0，半天假 0.5，其余工作日 1
+    pub fn working_day_fraction(&self, date: NaiveDate) -> f64 {
+        if !self.is_working_day(date) {
+            0.0
+        } else if self.half_days.contains(&date) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// 返回 `date` 当天（若为工作日）或之后的第一个工作日；MAX_RANGE_DAYS 内没有工作日或超出日期范围时返回错误
+    pub fn next_working_day(&self, date: NaiveDate) -> AppResult<NaiveDate> {
+        let mut d = date;
+        for _ in 0..MAX_RANGE_DAYS {
+            if self.is_working_day(d) {
+                return Ok(d);
+            }
+            d = d.checked_add_days(Days::new(1)).ok_or_else(date_out_of_range)?;
+        }
+        Err(date_out_of_range())
+    }
+
+    /// 返回 `date` 之后第 `days` 个工作日；`days` 为负数时向前推算，为 0 时返回 `date` 本身
+    ///
+    /// `days` 超过 MAX_WORKING_DAYS 或结果超出日期范围时返回错误。
+    pub fn add_working_days(&self, date: NaiveDate, days: i64) -> AppResult<NaiveDate> {
+        if days.unsigned_abs() > MAX_WORKING_DAYS as u64 {
+            return Err(AppError::BadRequest(format!(
+                "Working days must be between -{} and {}",
+                MAX_WORKING_DAYS, MAX_WORKING_DAYS
+            )));
+        }
+        let mut d = date;
+        let mut remaining = days.unsigned_abs();
+        while remaining > 0 {
+            d = if days < 0 {
+                d.checked_sub_days(Days::new(1))
+            } else {
+                d.checked_add_days(Days::new(1))
+            }
+            .ok_or_else(date_out_of_range)?;
+            if self.is_working_day(d) {
+                remaining -= 1;
+            }
+        }
+        Ok(d)
+    }
+
+    /// 统计 [start, end] 闭区间内的工作日数量，start 晚于 end 时返回 0；区间超过 MAX_RANGE_DAYS 时返回错误
+    pub fn count_working_days(&self, start: NaiveDate, end: NaiveDate) -> AppResult<i64> {
+        if start > end {
+            return Ok(0);
+        }
+        if (end - start).num_days() >= MAX_RANGE_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "Date range must not exceed {} days",
+                MAX_RANGE_DAYS
+            )));
+        }
+        Ok(start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| self.is_working_day(*d))
+            .count() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_weekends_and_holidays() {
+        // 2026-01-01 为周四
+        let calendar = WorkingCalendar::new([(date(1, 1), HolidayType::PublicHoliday)]);
+        assert!(!calendar.is_working_day(date(1, 1)));
+        assert!(calendar.is_working_day(date(1, 2)));
+        assert!(!calendar.is_working_day(date(1, 3)));
+        assert_eq!(calendar.next_working_day(date(1, 3)).unwrap(), date(1, 5));
+    }
+
+    #[test]
+    fn test_add_working_days() {
+        let calendar = WorkingCalendar::new([(date(1, 1), HolidayType::PublicHoliday)]);
+        let new_year_eve = date(12, 31).with_year(2025).unwrap();
+        assert_eq!(calendar.add_working_days(new_year_eve, 1).unwrap(), date(1, 2));
+        assert_eq!(calendar.add_working_days(date(1, 2), 1).unwrap(), date(1, 5));
+        assert_eq!(calendar.add_working_days(date(1, 5), -1).unwrap(), date(1, 2));
+        assert_eq!(calendar.add_working_days(date(1, 5), 0).unwrap(), date(1, 5));
+    }
+
+    #[test]
+    fn test_date_limits_return_errors() {
+        let calendar = WorkingCalendar::default();
+        assert!(calendar.add_working_days(NaiveDate::MAX, 1).is_err());
+        assert!(calendar.add_working_days(NaiveDate::MIN, -1).is_err());
+        assert!(calendar.add_working_days(date(1, 5), i64::MIN).is_err());
+        assert!(calendar.count_working_days(NaiveDate::MIN, NaiveDate::MAX).is_err());
+    }
+
+    #[test]
+    fn test_count_working_days() {
+        let calendar = WorkingCalendar::new([(date(1, 1), HolidayType::PublicHoliday)]);
+        assert_eq!(calendar.count_working_days(date(1, 1), date(1, 9)).unwrap(), 6);
+        assert_eq!(calendar.count_working_days(date(1, 9), date(1, 1)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_make_up_workday_and_half_day() {
+        // 2026-01-04 为周日，调休上班；1/5 半天假
+        let calendar = WorkingCalendar::new([
+            (date(1, 4), HolidayType::MakeUpWorkday),
+            (date(1, 5), HolidayType::HalfDay),
+        ]);
+        assert!(calendar.is_working_day(date(1, 4)));
+        assert!(calendar.is_working_day(date(1, 5)));
+        assert_eq!(calendar.add_working_days(date(1, 2), 1).unwrap(), date(1, 4));
+        assert_eq!(calendar.count_working_days(date(1, 3), date(1, 5)).unwrap(), 2);
+        assert_eq!(calendar.working_day_fraction(date(1, 3)), 0.0);
+        assert_eq!(calendar.working_day_fraction(date(1, 4)), 1.0);
+        assert_eq!(calendar.working_day_fraction(date(1, 5)), 0.5);
+    }
+}
@@ -1,18 +1,46 @@
 use crate::common::app_state::AppState;
-use crate::common::error::{AppError, AppResult};
+use crate::common::error::{field_error, AppError, AppResult};
 use crate::common::id::Id;
 use crate::common::jwt::Claims;
 use crate::common::response::{ApiResponse, PaginatedResponse};
+use crate::modules::holiday::calendar::{WorkingCalendar, MAX_WORKING_DAYS};
+use crate::modules::holiday::holiday_calendar::handlers::ensure_calendar_exists;
+use crate::modules::holiday::holiday_calendar::models::HolidayCalendarScope;
+use crate::modules::holiday::holiday_calendar::repository::HolidayCalendarRepository;
 use crate::modules::holiday::models::{
-    BatchCreateHolidaysParams, BatchDeleteHolidaysParams, BatchUpdateHolidaysParams,
-    CreateHolidayParams, Holiday, HolidayQueryParams, UpdateHolidayParams,
+    AddWorkingDaysQuery, AddWorkingDaysResponse, BatchCreateHolidaysParams,
+    BatchDeleteHolidaysParams, BatchUpdateHolidaysParams, CountWorkingDaysQuery,
+    CountWorkingDaysResponse, CreateHolidayParams, Holiday, HolidayImportError,
+    HolidayImportFormat, HolidayImportQuery, HolidayImportResponse, HolidayQueryParams,
+    HolidayType, IsWorkingDayQuery, IsWorkingDayResponse, UpdateHolidayParams,
+    IMPORT_FILE_FIELD_NAME,
 };
+use crate::modules::holiday::importer;
 use crate::modules::holiday::repository::HolidayRepository;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Multipart, Path, Query, State},
     http::StatusCode,
     Extension, Json,
 };
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::HashSet;
+
+/// 调休上班日只能设置在周末
+fn validate_holiday_type(field: &str, date: NaiveDate, holiday_type: HolidayType) -> AppResult<()> {
+    if holiday_type == HolidayType::MakeUpWorkday
+        && !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    {
+        return Err(AppError::ValidationError(
+            "Invalid holiday type".to_string(),
+            vec![field_error(
+                field,
+                "weekday_make_up_workday",
+                format!("Make-up workday must fall on a weekend: {}", date),
+            )],
+        ));
+    }
+    Ok(())
+}
 
 pub async fn get_holiday_list(
     State(state): State<AppState>,
@@ -54,6 +82,10 @@ pub async fn create_holiday(
     Extension(claims): Extension<Claims>,
     Json(params): Json<CreateHolidayParams>,
 ) -> AppResult<(StatusCode, Json<ApiResponse<Holiday>>)> {
+    validate_holiday_type("holidayType", params.holiday_date, params.holiday_type)?;
+    if let Some(calendar_id) = params.calendar_id {
+        ensure_calendar_exists(&state.pool, calendar_id).await?;
+    }
     let creator_id = claims.sub;
     let holiday_id = state
         .generate_id()
@@ -69,6 +101,19 @@ pub async fn update_holiday(
     Path(holiday_id): Path<Id>,
     Json(params): Json<UpdateHolidayParams>,
 ) -> AppResult<Json<ApiResponse<Holiday>>> {
+    if params.holiday_date.is_some() || params.holiday_type.is_some() {
+        let existing = HolidayRepository::get_holiday_by_id(&state.pool, holiday_id.0)
+            .await?
+            .ok_or(AppError::NotFound(format!("Holiday not found: {}", holiday_id)))?;
+        let holiday_date = params.holiday_date.unwrap_or(existing.holiday_date);
+        let holiday_type = match params.holiday_type {
+            Some(t) => Some(t),
+            None => HolidayType::from_i32(existing.holiday_type),
+        };
+        if let Some(holiday_type) = holiday_type {
+            validate_holiday_type("holidayType", holiday_date, holiday_type)?;
+        }
+    }
     let updater_id = claims.sub;
     let holiday = HolidayRepository::update_holiday(&state.pool, holiday_id.0, params, updater_id)
         .await?
@@ -101,6 +146,14 @@ pub async fn batch_create_holidays(
     Extension(claims): Extension<Claims>,
     Json(params): Json<BatchCreateHolidaysParams>,
 ) -> AppResult<(StatusCode, Json<ApiResponse<Vec<Holiday>>>)> {
+    for (i, holiday) in params.holidays.iter().enumerate() {
+        let field = format!("holidays[{}].holidayType", i);
+        validate_holiday_type(&field, holiday.holiday_date, holiday.holiday_type)?;
+    }
+    let calendar_ids: HashSet<Id> = params.holidays.iter().filter_map(|h| h.calendar_id).collect();
+    for calendar_id in calendar_ids {
+        ensure_calendar_exists(&state.pool, calendar_id).await?;
+    }
     let creator_id = claims.sub;
     let mut holiday_ids = Vec::new();
     for _ in 0..params.holidays.len() {
@@ -126,14 +179,225 @@ pub async fn batch_update_holidays(
 ) -> AppResult<Json<ApiResponse<Vec<Holiday>>>> {
     let updater_id = claims.sub;
     let holiday_ids: Vec<i64> = params.ids.into_iter().map(|id| id.0).collect();
+    if let Some(holiday_type) = params.holiday_type {
+        for holiday in HolidayRepository::get_holidays_by_ids(&state.pool, &holiday_ids).await? {
+            validate_holiday_type("holidayType", holiday.holiday_date, holiday_type)?;
+        }
+    }
     let holidays = HolidayRepository::batch_update_holidays(
         &state.pool,
         holiday_ids,
         params.holiday_name,
         params.description,
-        params.holiday_type,
+        params.holiday_type.map(HolidayType::as_i32),
         updater_id,
     )
     .await?;
     Ok(Json(ApiResponse::success(holidays)))
 }
+
+pub async fn add_working_days(
+    State(state): State<AppState>,
+    Query(scope): Query<HolidayCalendarScope>,
+    Query(params): Query<AddWorkingDaysQuery>,
+) -> AppResult<Json<ApiResponse<AddWorkingDaysResponse>>> {
+    if params.days.abs() > MAX_WORKING_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "Days must be between -{} and {}",
+            MAX_WORKING_DAYS, MAX_WORKING_DAYS
+        )));
+    }
+    let calendar_id = HolidayCalendarRepository::resolve_calendar_id(&state.pool, &scope).await?;
+    let calendar = WorkingCalendar::load(&state.pool, calendar_id).await?;
+    let result = calendar.add_working_days(params.date, params.days)?;
+    Ok(Json(ApiResponse::success(AddWorkingDaysResponse {
+        date: params.date,
+        days: params.days,
+        result,
+    })))
+}
+
+pub async fn count_working_days(
+    State(state): State<AppState>,
+    Query(scope): Query<HolidayCalendarScope>,
+    Query(params): Query<CountWorkingDaysQuery>,
+) -> AppResult<Json<ApiResponse<CountWorkingDaysResponse>>> {
+    if params.start_date > params.end_date {
+        return Err(AppError::BadRequest(
+            "startDate must not be after endDate".to_string(),
+        ));
+    }
+    let calendar_id = HolidayCalendarRepository::resolve_calendar_id(&state.pool, &scope).await?;
+    let calendar =
+        WorkingCalendar::load_between(&state.pool, calendar_id, params.start_date, params.end_date)
+            .await?;
+    let working_days = calendar.count_working_days(params.start_date, params.end_date)?;
+    Ok(Json(ApiResponse::success(CountWorkingDaysResponse {
+        start_date: params.start_date,
+        end_date: params.end_date,
+        working_days,
+    })))
+}
+
+pub async fn is_working_day(
+    State(state): State<AppState>,
+    Query(scope): Query<HolidayCalendarScope>,
+    Query(params): Query<IsWorkingDayQuery>,
+) -> AppResult<Json<ApiResponse<IsWorkingDayResponse>>> {
+    let calendar_id = HolidayCalendarRepository::resolve_calendar_id(&state.pool, &scope).await?;
+    let calendar =
+        WorkingCalendar::load_between(&state.pool, calendar_id, params.date, params.date).await?;
+    Ok(Json(ApiResponse::success(IsWorkingDayResponse {
+        date: params.date,
+        is_working_day: calendar.is_working_day(params.date),
+        working_day_fraction: calendar.working_day_fraction(params.date),
+    })))
+}
+
+/// 根据文件的 Content-Type 或扩展名推断导入格式
+fn detect_import_format(content_type: &str, file_name: &str) -> Option<HolidayImportFormat> {
+    let file_name = file_name.to_ascii_lowercase();
+    if content_type.starts_with("text/calendar") || file_name.ends_with(".ics") {
+        Some(HolidayImportFormat::Ics)
+    } else if content_type.starts_with("text/csv") || file_name.ends_with(".csv") {
+        Some(HolidayImportFormat::Csv)
+    } else {
+        None
+    }
+}
+
+/// 导入 iCalendar / CSV 假期文件（multipart/form-data，文件字段名为 file）
+///
+/// - preview=true：仅返回解析结果、重复项与错误
+/// - 否则存在任何错误时整体拒绝（ValidationError，逐行列出），重复日期跳过，其余批量创建
+pub async fn import_holidays(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<HolidayImportQuery>,
+    mut multipart: Multipart,
+) -> AppResult<(StatusCode, Json<ApiResponse<HolidayImportResponse>>)> {
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() != Some(IMPORT_FILE_FIELD_NAME) {
+            continue;
+        }
+        let detected = detect_import_format(
+            field.content_type().unwrap_or(""),
+            field.file_name().unwrap_or(""),
+        );
+        file = Some((detected, field.text().await?));
+        break;
+    }
+    let (detected, body) = file.ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "No file provided in field '{}'",
+            IMPORT_FILE_FIELD_NAME
+        ))
+    })?;
+    let format = params.format.or(detected).ok_or_else(|| {
+        AppError::BadRequest("Unable to determine import format, specify format=ics|csv".to_string())
+    })?;
+    if body.trim().is_empty() {
+        return Err(AppError::BadRequest("Import file is empty".to_string()));
+    }
+    if let Some(calendar_id) = params.calendar_id {
+        ensure_calendar_exists(&state.pool, calendar_id).await?;
+    }
+
+    let default_type = params.holiday_type.unwrap_or(HolidayType::PublicHoliday);
+    let parsed = match format {
+        HolidayImportFormat::Ics => importer::parse_ics(&body, default_type),
+        HolidayImportFormat::Csv => importer::parse_csv(&body, default_type),
+    };
+    let mut errors = parsed.errors;
+
+    let mut candidates = Vec::with_capacity(parsed.rows.len());
+    for row in parsed.rows {
+        match validate_holiday_type("holidayType", row.holiday_date, row.holiday_type) {
+            Ok(()) => candidates.push(row),
+            Err(AppError::ValidationError(message, fields)) => errors.push(HolidayImportError {
+                line: row.line,
+                message: fields.into_iter().next().map_or(message, |f| f.message),
+            }),
+            Err(e) => return Err(e),
+        }
+    }
+    errors.sort_by_key(|e| e.line);
+
+    // 与已有假期及文件中前面的行去重
+    let mut seen: HashSet<NaiveDate> = HashSet::new();
+    if let (Some(start), Some(end)) = (
+        candidates.iter().map(|r| r.holiday_date).min(),
+        candidates.iter().map(|r| r.holiday_date).max(),
+    ) {
+        let existing = HolidayRepository::get_all_holidays(
+            &state.pool,
+            HolidayQueryParams {
+                calendar_id: params.calendar_id,
+                start_date: Some(start),
+                end_date: Some(end),
+                ..Default::default()
+            },
+        )
+        .await?;
+        seen.extend(existing.into_iter().map(|h| h.holiday_date));
+    }
+    let (rows, duplicates): (Vec<_>, Vec<_>) =
+        candidates.into_iter().partition(|r| seen.insert(r.holiday_date));
+
+    if params.preview {
+        return Ok((
+            StatusCode::OK,
+            Json(ApiResponse::success(HolidayImportResponse {
+                preview: true,
+                rows,
+                duplicates,
+                errors,
+                created: Vec::new(),
+            })),
+        ));
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::ValidationError(
+            format!("Import file contains {} invalid row(s)", errors.len()),
+            errors
+                .into_iter()
+                .map(|e| field_error(format!("line {}", e.line), "invalid_row", e.message))
+                .collect(),
+        ));
+    }
+
+    let creator_id = claims.sub;
+    let mut holiday_ids = Vec::with_capacity(rows.len());
+    for _ in 0..rows.len() {
+        let id = state
+            .generate_id()
+            .map_err(|e| AppError::InternalError(format!("Failed to generate holiday ID: {}", e)))?;
+        holiday_ids.push(id);
+    }
+    let create_params = rows
+        .iter()
+        .map(|r| CreateHolidayParams {
+            calendar_id: params.calendar_id,
+            holiday_name: r.holiday_name.clone(),
+            description: r.description.clone(),
+            holiday_date: r.holiday_date,
+            holiday_type: r.holiday_type,
+        })
+        .collect();
+    let created =
+        HolidayRepository::batch_create_holidays(&state.pool, holiday_ids, create_params, creator_id)
+            .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success(HolidayImportResponse {
+            preview: false,
+            rows,
+            duplicates,
+            errors: Vec::new(),
+            created,
+        })),
+    ))
+}
@@ -0,0 +1,150 @@
+use crate::common::app_state::AppState;
+use crate::common::error::{AppError, AppResult};
+use crate::common::id::Id;
+use crate::common::jwt::Claims;
+use crate::common::response::ApiResponse;
+use crate::modules::holiday::holiday_calendar::models::{
+    CreateHolidayCalendarParams, HolidayCalendar, UpdateHolidayCalendarParams,
+    DEFAULT_HOLIDAY_CALENDAR_ID,
+};
+use crate::modules::holiday::holiday_calendar::repository::HolidayCalendarRepository;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use sqlx::PgPool;
+
+/// 校验日历存在，供假期 / 项目 / 组织等引用日历的接口使用
+pub async fn ensure_calendar_exists(pool: &PgPool, calendar_id: Id) -> AppResult<()> {
+    if !HolidayCalendarRepository::exists_calendar(pool, calendar_id.0).await? {
+        return Err(AppError::BadRequest(format!(
+            "Holiday calendar not found: {}",
+            calendar_id
+        )));
+    }
+    Ok(())
+}
+
+pub async fn get_all_calendars(
+    State(state): State<AppState>,
+) -> AppResult<Json<ApiResponse<Vec<HolidayCalendar>>>> {
+    let calendars = HolidayCalendarRepository::get_all_calendars(&state.pool).await?;
+    Ok(Json(ApiResponse::success(calendars)))
+}
+
+pub async fn get_calendar_by_id(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Id>,
+) -> AppResult<Json<ApiResponse<HolidayCalendar>>> {
+    let calendar = HolidayCalendarRepository::get_calendar_by_id(&state.pool, calendar_id.0)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Holiday calendar not found: {}",
+            calendar_id
+        )))?;
+    Ok(Json(ApiResponse::success(calendar)))
+}
+
+pub async fn create_calendar(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(params): Json<CreateHolidayCalendarParams>,
+) -> AppResult<(StatusCode, Json<ApiResponse<HolidayCalendar>>)> {
+    if params.calendar_name.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Calendar name cannot be empty".to_string(),
+        ));
+    }
+    if let Some(source_id) = params.copy_from_calendar_id {
+        ensure_calendar_exists(&state.pool, source_id).await?;
+    }
+
+    let generate_id = || {
+        state.generate_id().map_err(|e| {
+            AppError::InternalError(format!("Failed to generate holiday calendar ID: {}", e))
+        })
+    };
+    let calendar_id = generate_id()?;
+
+    let mut tx = state.pool.begin().await?;
+    let calendar =
+        HolidayCalendarRepository::create_calendar(&mut tx, calendar_id, &params, claims.sub)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict(format!(
+                    "Holiday calendar already exists: {}",
+                    params.calendar_name
+                ))
+            })?;
+
+    if let Some(source_id) = params.copy_from_calendar_id {
+        // 在事务内锁定源假期后再分配 ID，保证复制的是同一份快照
+        let source_ids = HolidayCalendarRepository::lock_holiday_ids(&mut tx, source_id.0).await?;
+        let holiday_ids = source_ids
+            .iter()
+            .map(|_| generate_id())
+            .collect::<AppResult<Vec<i64>>>()?;
+        HolidayCalendarRepository::copy_holidays(
+            &mut tx,
+            calendar_id,
+            &source_ids,
+            &holiday_ids,
+            claims.sub,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(calendar))))
+}
+
+pub async fn update_calendar(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(calendar_id): Path<Id>,
+    Json(params): Json<UpdateHolidayCalendarParams>,
+) -> AppResult<Json<ApiResponse<HolidayCalendar>>> {
+    if let Some(ref name) = params.calendar_name {
+        if name.trim().is_empty() {
+            return Err(AppError::BadRequest(
+                "Calendar name cannot be empty".to_string(),
+            ));
+        }
+        if HolidayCalendarRepository::exists_calendar_name(&state.pool, name, Some(calendar_id.0))
+            .await?
+        {
+            return Err(AppError::Conflict(format!(
+                "Holiday calendar already exists: {}",
+                name
+            )));
+        }
+    }
+    let calendar =
+        HolidayCalendarRepository::update_calendar(&state.pool, calendar_id.0, params, claims.sub)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Holiday calendar not found: {}",
+                calendar_id
+            )))?;
+    Ok(Json(ApiResponse::success(calendar)))
+}
+
+pub async fn delete_calendar(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Id>,
+) -> AppResult<StatusCode> {
+    if calendar_id.0 == DEFAULT_HOLIDAY_CALENDAR_ID {
+        return Err(AppError::BadRequest(
+            "The default holiday calendar cannot be deleted".to_string(),
+        ));
+    }
+    let deleted = HolidayCalendarRepository::delete_calendar(&state.pool, calendar_id.0).await?;
+    if !deleted {
+        return Err(AppError::NotFound(format!(
+            "Holiday calendar not found: {}",
+            calendar_id
+        )));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
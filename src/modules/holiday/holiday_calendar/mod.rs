@@ -0,0 +1,5 @@
+pub use routes::*;
+pub mod handlers;
+pub mod models;
+pub mod repository;
+pub mod routes;
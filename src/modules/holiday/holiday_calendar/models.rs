@@ -0,0 +1,48 @@
+use crate::common::id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 默认假期日历 ID（迁移中创建，不可删除）
+pub const DEFAULT_HOLIDAY_CALENDAR_ID: i64 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayCalendar {
+    pub id: Id,
+    pub calendar_name: String,
+    pub description: Option<String>,
+    pub is_default: bool,
+    pub creator_id: Id,
+    pub updater_id: Option<Id>,
+    pub create_date_time: chrono::NaiveDateTime,
+    pub update_date_time: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateHolidayCalendarParams {
+    pub calendar_name: String,
+    pub description: Option<String>,
+    /// 从已有日历复制全部假期
+    pub copy_from_calendar_id: Option<Id>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateHolidayCalendarParams {
+    /// NOT NULL 字段
+    pub calendar_name: Option<String>,
+    /// 可空字段，双层 Option：None = 不更新，Some(None) = 清空，Some(Some(v)) = 更新
+    #[serde(default, deserialize_with = "crate::common::serde_helpers::double_option::deserialize")]
+    pub description: Option<Option<String>>,
+}
+
+/// 指定工作日计算使用的日历，优先级：calendarId > projectId > teamId > departmentId，均未指定时使用默认日历
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayCalendarScope {
+    pub calendar_id: Option<Id>,
+    pub project_id: Option<Id>,
+    pub team_id: Option<Id>,
+    pub department_id: Option<Id>,
+}
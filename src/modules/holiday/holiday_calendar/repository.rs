@@ -0,0 +1,259 @@
+use crate::common::error::AppResult;
+use crate::modules::holiday::holiday_calendar::models::{
+    CreateHolidayCalendarParams, HolidayCalendar, HolidayCalendarScope,
+    UpdateHolidayCalendarParams, DEFAULT_HOLIDAY_CALENDAR_ID,
+};
+use sqlx::{PgConnection, PgPool};
+use sqlx::QueryBuilder;
+
+pub struct HolidayCalendarRepository;
+
+/// holiday_calendars 表 SELECT 列
+fn calendar_columns() -> String {
+    format!(
+        "id, calendar_name, description, id = {} AS is_default, \
+         creator_id, updater_id, create_date_time, update_date_time",
+        DEFAULT_HOLIDAY_CALENDAR_ID,
+    )
+}
+
+/// holiday_calendars 表 RETURNING 列
+fn calendar_returning() -> String {
+    format!(" RETURNING {}", calendar_columns())
+}
+
+impl HolidayCalendarRepository {
+    pub async fn get_all_calendars(pool: &PgPool) -> AppResult<Vec<HolidayCalendar>> {
+        let calendars = sqlx::query_as::<_, HolidayCalendar>(&format!(
+            "SELECT {} FROM holiday_calendars ORDER BY id = {} DESC, calendar_name ASC",
+            calendar_columns(),
+            DEFAULT_HOLIDAY_CALENDAR_ID,
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(calendars)
+    }
+
+    pub async fn get_calendar_by_id(
+        pool: &PgPool,
+        calendar_id: i64,
+    ) -> AppResult<Option<HolidayCalendar>> {
+        let calendar = sqlx::query_as::<_, HolidayCalendar>(&format!(
+            "SELECT {} FROM holiday_calendars WHERE id = $1",
+            calendar_columns(),
+        ))
+        .bind(calendar_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(calendar)
+    }
+
+    pub async fn exists_calendar(pool: &PgPool, calendar_id: i64) -> AppResult<bool> {
+        let result: (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM holiday_calendars WHERE id = $1)")
+                .bind(calendar_id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(result.0)
+    }
+
+    pub async fn exists_calendar_name(
+        pool: &PgPool,
+        calendar_name: &str,
+        exclude_id: Option<i64>,
+    ) -> AppResult<bool> {
+        let result: (bool,) = sqlx::query_as(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM holiday_calendars
+                   WHERE calendar_name = $1 AND ($2::BIGINT IS NULL OR id <> $2)
+               )"#,
+        )
+        .bind(calendar_name)
+        .bind(exclude_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.0)
+    }
+
+    /// 创建日历；同名日历已存在时返回 None（并发插入同名日历由唯一约束兜底）
+    pub async fn create_calendar(
+        conn: &mut PgConnection,
+        calendar_id: i64,
+        params: &CreateHolidayCalendarParams,
+        creator_id: i64,
+    ) -> AppResult<Option<HolidayCalendar>> {
+        let calendar = sqlx::query_as::<_, HolidayCalendar>(&format!(
+            "INSERT INTO holiday_calendars (id, calendar_name, description, creator_id, create_date_time) \
+             SELECT $1, $2, $3, $4, NOW() \
+             WHERE NOT EXISTS (SELECT 1 FROM holiday_calendars WHERE calendar_name = $2){}",
+            calendar_returning(),
+        ))
+        .bind(calendar_id)
+        .bind(&params.calendar_name)
+        .bind(&params.description)
+        .bind(creator_id)
+        .fetch_optional(&mut *conn)
+        .await;
+
+        match calendar {
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Ok(None),
+            result => Ok(result?),
+        }
+    }
+
+    /// 锁定日历下的全部假期（FOR SHARE），按日期顺序返回其 ID，复制期间源假期不会被修改或删除
+    pub async fn lock_holiday_ids(
+        conn: &mut PgConnection,
+        calendar_id: i64,
+    ) -> AppResult<Vec<i64>> {
+        let ids: Vec<(i64,)> = sqlx::query_as(
+            "SELECT id FROM holidays WHERE calendar_id = $1 ORDER BY holiday_date, id FOR SHARE",
+        )
+        .bind(calendar_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(ids.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// 将 source_ids 对应的假期逐条复制到目标日历，新 ID 与 source_ids 一一对应
+    pub async fn copy_holidays(
+        conn: &mut PgConnection,
+        calendar_id: i64,
+        source_ids: &[i64],
+        holiday_ids: &[i64],
+        creator_id: i64,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"INSERT INTO holidays (id, calendar_id, holiday_name, description, holiday_date,
+                                     holiday_type, creator_id, create_date_time)
+               SELECT ids.new_id, $3, h.holiday_name, h.description, h.holiday_date,
+                      h.holiday_type, $4, NOW()
+               FROM UNNEST($1::BIGINT[], $2::BIGINT[]) AS ids(source_id, new_id)
+               JOIN holidays h ON h.id = ids.source_id"#,
+        )
+        .bind(source_ids)
+        .bind(holiday_ids)
+        .bind(calendar_id)
+        .bind(creator_id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_calendar(
+        pool: &PgPool,
+        calendar_id: i64,
+        params: UpdateHolidayCalendarParams,
+        updater_id: i64,
+    ) -> AppResult<Option<HolidayCalendar>> {
+        let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("UPDATE holiday_calendars SET ");
+        let mut has_set = false;
+
+        if let Some(ref name) = params.calendar_name {
+            qb.push("calendar_name = ");
+            qb.push_bind(name.clone());
+            has_set = true;
+        }
+
+        if let Some(ref desc_opt) = params.description {
+            if has_set { qb.push(", "); }
+            qb.push("description = ");
+            qb.push_bind(desc_opt.clone());
+            has_set = true;
+        }
+
+        if has_set { qb.push(", "); }
+        qb.push("updater_id = ");
+        qb.push_bind(updater_id);
+        qb.push(", update_date_time = NOW() WHERE id = ");
+        qb.push_bind(calendar_id);
+        qb.push(calendar_returning());
+
+        let calendar = qb
+            .build_query_as::<HolidayCalendar>()
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(calendar)
+    }
+
+    /// 删除日历：其假期级联删除，引用它的项目 / 部门 / 团队回退到默认日历
+    pub async fn delete_calendar(pool: &PgPool, calendar_id: i64) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM holiday_calendars WHERE id = $1")
+            .bind(calendar_id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 解析项目使用的日历，未设置时为默认日历
+    pub async fn resolve_project_calendar_id(pool: &PgPool, project_id: i64) -> AppResult<i64> {
+        let row: Option<(Option<i64>,)> =
+            sqlx::query_as("SELECT holiday_calendar_id FROM projects WHERE id = $1")
+                .bind(project_id)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(row
+            .and_then(|(id,)| id)
+            .unwrap_or(DEFAULT_HOLIDAY_CALENDAR_ID))
+    }
+
+    /// 解析用户适用的日历：所属团队（最早加入且设置了日历的团队） -> 所属部门 -> 默认日历
+    pub async fn resolve_user_calendar_id(pool: &PgPool, user_id: i64) -> AppResult<i64> {
+        let row: (i64,) = sqlx::query_as(
+            r#"SELECT COALESCE(
+                   (SELECT t.holiday_calendar_id FROM user_teams ut
+                    JOIN teams t ON t.id = ut.team_id
+                    WHERE ut.user_id = $1 AND t.holiday_calendar_id IS NOT NULL
+                    ORDER BY ut.create_date_time ASC, ut.id ASC
+                    LIMIT 1),
+                   (SELECT d.holiday_calendar_id FROM user_departments ud
+                    JOIN departments d ON d.id = ud.department_id
+                    WHERE ud.user_id = $1),
+                   $2
+               )"#,
+        )
+        .bind(user_id)
+        .bind(DEFAULT_HOLIDAY_CALENDAR_ID)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row.0)
+    }
+
+    /// 按 HolidayCalendarScope 的优先级解析日历 ID
+    pub async fn resolve_calendar_id(pool: &PgPool, scope: &HolidayCalendarScope) -> AppResult<i64> {
+        if let Some(calendar_id) = scope.calendar_id {
+            return Ok(calendar_id.0);
+        }
+        if let Some(project_id) = scope.project_id {
+            return Self::resolve_project_calendar_id(pool, project_id.0).await;
+        }
+
+        let row: Option<(Option<i64>,)> = if let Some(team_id) = scope.team_id {
+            sqlx::query_as("SELECT holiday_calendar_id FROM teams WHERE id = $1")
+                .bind(team_id.0)
+                .fetch_optional(pool)
+                .await?
+        } else if let Some(department_id) = scope.department_id {
+            sqlx::query_as("SELECT holiday_calendar_id FROM departments WHERE id = $1")
+                .bind(department_id.0)
+                .fetch_optional(pool)
+                .await?
+        } else {
+            None
+        };
+
+        Ok(row
+            .and_then(|(id,)| id)
+            .unwrap_or(DEFAULT_HOLIDAY_CALENDAR_ID))
+    }
+}
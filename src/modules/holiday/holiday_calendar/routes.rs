@@ -0,0 +1,32 @@
+use crate::common::app_state::AppState;
+use crate::common::middleware::{admin_auth_middleware, jwt_auth_middleware};
+use crate::modules::holiday::holiday_calendar::handlers;
+use axum::{
+    middleware,
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub fn holiday_calendar_routes(state: AppState) -> Router {
+    let auth_layer = middleware::from_fn_with_state(
+        state.jwt_config.clone(),
+        jwt_auth_middleware,
+    );
+
+    // Read routes - accessible to all authenticated users
+    let read_routes = Router::new()
+        .route("/holiday-calendars", get(handlers::get_all_calendars))
+        .route("/holiday-calendars/{id}", get(handlers::get_calendar_by_id));
+
+    // Write routes - admin only
+    let write_routes = Router::new()
+        .route("/holiday-calendars", post(handlers::create_calendar))
+        .route("/holiday-calendars/{id}", put(handlers::update_calendar))
+        .route("/holiday-calendars/{id}", delete(handlers::delete_calendar))
+        .layer(middleware::from_fn(admin_auth_middleware));
+
+    read_routes
+        .merge(write_routes)
+        .layer(auth_layer)
+        .with_state(state)
+}
@@ -0,0 +1,353 @@
+use crate::modules::holiday::models::{HolidayImportError, HolidayImportRow, HolidayType};
+use chrono::{Duration, NaiveDate};
+
+/// 单个 VEVENT 最多展开的天数
+const MAX_EVENT_DAYS: i64 = 366;
+
+/// 解析结果：成功解析的行 + 解析错误
+#[derive(Debug, Default)]
+pub struct ParsedHolidays {
+    pub rows: Vec<HolidayImportRow>,
+    pub errors: Vec<HolidayImportError>,
+}
+
+impl ParsedHolidays {
+    fn error(&mut self, line: usize, message: impl Into<String>) {
+        self.errors.push(HolidayImportError {
+            line,
+            message: message.into(),
+        });
+    }
+}
+
+fn parse_holiday_type(value: &str) -> Option<HolidayType> {
+    let value = value.trim();
+    if let Ok(v) = value.parse::<i32>() {
+        return HolidayType::from_i32(v);
+    }
+    match value.to_ascii_lowercase().as_str() {
+        "public_holiday" => Some(HolidayType::PublicHoliday),
+        "company_holiday" => Some(HolidayType::CompanyHoliday),
+        "make_up_workday" => Some(HolidayType::MakeUpWorkday),
+        "half_day" => Some(HolidayType::HalfDay),
+        _ => None,
+    }
+}
+
+// ──────────────── iCalendar ────────────────
+
+/// 反转义 iCalendar TEXT 值
+fn unescape_ics_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// 解析 DATE 值（YYYYMMDD）；VALUE 参数不为 DATE，或未指定 VALUE 且带时间部分时视为非全天事件，返回 None
+fn parse_ics_date(params: &str, value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    let value_type = params
+        .split(';')
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("VALUE"))
+        .map(|(_, v)| v.trim());
+    match value_type {
+        Some(v) if !v.eq_ignore_ascii_case("DATE") => return None,
+        None if value.contains('T') => return None,
+        _ => {}
+    }
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+/// 解析 iCalendar 文本中的全天 VEVENT；多天事件（DTEND 为次日之后）按天展开
+///
+/// CATEGORIES 若为假期类型名称（如 make_up_workday）则使用该类型，否则使用 `default_type`。
+pub fn parse_ics(content: &str, default_type: HolidayType) -> ParsedHolidays {
+    let mut result = ParsedHolidays::default();
+
+    // 展开折行：以空格或制表符开头的行是上一行的延续
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (i, raw) in content.lines().enumerate() {
+        if let Some(rest) = raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')) {
+            if let Some((_, last)) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push((i + 1, raw.trim_end_matches('\r').to_string()));
+    }
+
+    let mut in_event = false;
+    let mut event_line = 0;
+    let mut summary: Option<String> = None;
+    let mut description: Option<String> = None;
+    let mut category: Option<HolidayType> = None;
+    let mut start: Option<NaiveDate> = None;
+    let mut end: Option<NaiveDate> = None;
+    let mut all_day = true;
+
+    for (line_no, line) in lines {
+        let Some((name_part, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = name_part.split_once(';').unwrap_or((name_part, ""));
+        let name = name.to_ascii_uppercase();
+
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") => {
+                in_event = true;
+                event_line = line_no;
+                summary = None;
+                description = None;
+                category = None;
+                start = None;
+                end = None;
+                all_day = true;
+            }
+            ("END", "VEVENT") if in_event => {
+                in_event = false;
+                let Some(holiday_name) = summary.take().filter(|s| !s.trim().is_empty()) else {
+                    result.error(event_line, "VEVENT is missing SUMMARY");
+                    continue;
+                };
+                if !all_day {
+                    result.error(event_line, format!("'{}' is not an all-day event", holiday_name));
+                    continue;
+                }
+                let Some(first) = start else {
+                    result.error(event_line, format!("'{}' is missing DTSTART", holiday_name));
+                    continue;
+                };
+                // DTEND 为不包含的结束日期；缺省时为单日事件
+                let last = end.map(|d| d - Duration::days(1)).unwrap_or(first).max(first);
+                if (last - first).num_days() >= MAX_EVENT_DAYS {
+                    result.error(
+                        event_line,
+                        format!("'{}' spans more than {} days", holiday_name, MAX_EVENT_DAYS),
+                    );
+                    continue;
+                }
+                for date in first.iter_days().take_while(|d| *d <= last) {
+                    result.rows.push(HolidayImportRow {
+                        line: event_line,
+                        holiday_name: holiday_name.trim().to_string(),
+                        description: description.clone(),
+                        holiday_date: date,
+                        holiday_type: category.unwrap_or(default_type),
+                    });
+                }
+            }
+            ("SUMMARY", v) if in_event => summary = Some(unescape_ics_text(v)),
+            ("DESCRIPTION", v) if in_event => {
+                description = Some(unescape_ics_text(v)).filter(|d| !d.is_empty())
+            }
+            ("CATEGORIES", v) if in_event => {
+                category = v.split(',').find_map(parse_holiday_type);
+            }
+            ("DTSTART", v) if in_event => match parse_ics_date(params, v) {
+                Some(d) => start = Some(d),
+                None => all_day = false,
+            },
+            ("DTEND", v) if in_event => match parse_ics_date(params, v) {
+                Some(d) => end = Some(d),
+                None => all_day = false,
+            },
+            _ => {}
+        }
+    }
+
+    result
+}
+
+// ──────────────── CSV ────────────────
+
+/// 拆分一行 CSV，支持双引号包裹与 "" 转义
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// 解析 CSV：首行为表头，必须包含 holiday_date、holiday_name 列，
+/// 可选 holiday_type（整数或类型名称，缺省使用 `default_type`）与 description 列。
+/// 表头同时接受 camelCase（holidayDate / holidayName / ...）。
+pub fn parse_csv(content: &str, default_type: HolidayType) -> ParsedHolidays {
+    let mut result = ParsedHolidays::default();
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim_start_matches('\u{feff}').trim_end_matches('\r')))
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let Some((header_line, header)) = lines.next() else {
+        result.error(1, "CSV file is empty");
+        return result;
+    };
+    let columns: Vec<String> = split_csv_line(header)
+        .into_iter()
+        .map(|c| c.to_ascii_lowercase().replace('_', ""))
+        .collect();
+    let column = |name: &str| columns.iter().position(|c| c == name);
+    let (Some(date_col), Some(name_col)) = (column("holidaydate"), column("holidayname")) else {
+        result.error(
+            header_line,
+            "CSV header must contain holiday_date and holiday_name columns",
+        );
+        return result;
+    };
+    let type_col = column("holidaytype");
+    let description_col = column("description");
+
+    for (line_no, line) in lines {
+        let fields = split_csv_line(line);
+        let get = |col: usize| fields.get(col).map(String::as_str).unwrap_or("");
+
+        let date_value = get(date_col);
+        let Some(holiday_date) = NaiveDate::parse_from_str(date_value, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(date_value, "%Y/%m/%d"))
+            .or_else(|_| NaiveDate::parse_from_str(date_value, "%Y%m%d"))
+            .ok()
+        else {
+            result.error(line_no, format!("Invalid holiday_date: '{}'", date_value));
+            continue;
+        };
+
+        let holiday_name = get(name_col);
+        if holiday_name.is_empty() {
+            result.error(line_no, "holiday_name cannot be empty");
+            continue;
+        }
+
+        let holiday_type = match type_col.map(get).filter(|v| !v.is_empty()) {
+            None => default_type,
+            Some(v) => match parse_holiday_type(v) {
+                Some(t) => t,
+                None => {
+                    result.error(line_no, format!("Invalid holiday_type: '{}'", v));
+                    continue;
+                }
+            },
+        };
+
+        result.rows.push(HolidayImportRow {
+            line: line_no,
+            holiday_name: holiday_name.to_string(),
+            description: description_col
+                .map(get)
+                .filter(|d| !d.is_empty())
+                .map(str::to_string),
+            holiday_date,
+            holiday_type,
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_ics_all_day_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20260101\r\n\
+DTEND;VALUE=DATE:20260102\r\n\
+SUMMARY:New Year\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20260217\r\n\
+DTEND;VALUE=DATE:20260220\r\n\
+SUMMARY:Spring Fest\r\n ival\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20260214\r\n\
+SUMMARY:Make-up workday\r\n\
+CATEGORIES:make_up_workday\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20260301T090000Z\r\n\
+SUMMARY:Meeting\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+        let parsed = parse_ics(ics, HolidayType::PublicHoliday);
+
+        assert_eq!(parsed.rows.len(), 5);
+        assert_eq!(parsed.rows[0].holiday_date, date(1, 1));
+        assert_eq!(parsed.rows[1].holiday_name, "Spring Festival");
+        assert_eq!(parsed.rows[3].holiday_date, date(2, 19));
+        assert_eq!(parsed.rows[4].holiday_type, HolidayType::MakeUpWorkday);
+        assert_eq!(parsed.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_ics_date_time_value_is_not_all_day() {
+        let ics = "BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE-TIME:20260301T090000\r\n\
+DTEND;VALUE=DATE-TIME:20260301T100000\r\n\
+SUMMARY:Meeting\r\n\
+END:VEVENT\r\n";
+        let parsed = parse_ics(ics, HolidayType::PublicHoliday);
+        assert!(parsed.rows.is_empty());
+        assert_eq!(parsed.errors.len(), 1);
+
+        assert_eq!(parse_ics_date("VALUE=DATE", "20260301"), Some(date(3, 1)));
+        assert_eq!(parse_ics_date("TZID=Asia/Shanghai;VALUE=DATE-TIME", "20260301T090000"), None);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "holiday_date,holiday_name,holiday_type,description\n\
+2026-01-01,New Year,1,\n\
+2026-02-14,\"Make-up, workday\",make_up_workday,\"Saturday \"\"work\"\"\"\n\
+2026-13-01,Bad date,1,\n\
+2026-05-01,Labour Day,9,\n\
+2026-10-01,National Day,,\n";
+        let parsed = parse_csv(csv, HolidayType::CompanyHoliday);
+
+        assert_eq!(parsed.rows.len(), 3);
+        assert_eq!(parsed.rows[1].holiday_name, "Make-up, workday");
+        assert_eq!(parsed.rows[1].description.as_deref(), Some("Saturday \"work\""));
+        assert_eq!(parsed.rows[1].holiday_type, HolidayType::MakeUpWorkday);
+        assert_eq!(parsed.rows[2].holiday_type, HolidayType::CompanyHoliday);
+        assert_eq!(parsed.errors.len(), 2);
+        assert_eq!(parsed.errors[0].line, 4);
+    }
+
+    #[test]
+    fn test_parse_csv_requires_header() {
+        let parsed = parse_csv("date,name\n2026-01-01,New Year\n", HolidayType::PublicHoliday);
+        assert!(parsed.rows.is_empty());
+        assert_eq!(parsed.errors.len(), 1);
+    }
+}
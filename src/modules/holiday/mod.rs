@@ -1,5 +1,8 @@
 pub use routes::*;
+pub mod calendar;
 pub mod handlers;
+pub mod holiday_calendar;
+pub mod importer;
 pub mod models;
 pub mod repository;
 pub mod routes;
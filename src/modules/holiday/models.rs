@@ -2,13 +2,113 @@ use crate::common::id::Id;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+/// 假期类型
+/// 数值与前端保持一致: PublicHoliday(1), CompanyHoliday(2), MakeUpWorkday(3), HalfDay(4)
+/// 序列化为整数，与 `Holiday.holiday_type` 一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HolidayType {
+    /// 法定节假日（不上班）
+    PublicHoliday = 1,
+    /// 公司假期（不上班）
+    CompanyHoliday = 2,
+    /// 调休上班日（即使是周末也上班）
+    MakeUpWorkday = 3,
+    /// 半天假（按工作日计，工时减半）
+    HalfDay = 4,
+}
+
+impl Serialize for HolidayType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i32(self.as_i32())
+    }
+}
+
+/// 自定义反序列化：同时接受整数 (1-4) 和字符串 ("public_holiday"/"company_holiday"/...)
+impl<'de> Deserialize<'de> for HolidayType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de;
+
+        struct HolidayTypeVisitor;
+
+        impl<'de> de::Visitor<'de> for HolidayTypeVisitor {
+            type Value = HolidayType;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an integer 1-4 or a string holiday type (public_holiday/company_holiday/make_up_workday/half_day)")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<HolidayType, E> {
+                HolidayType::from_i32(v as i32)
+                    .ok_or_else(|| de::Error::custom(format!("invalid holiday type: {v}. Valid: 1-4")))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<HolidayType, E> {
+                HolidayType::from_i32(v as i32)
+                    .ok_or_else(|| de::Error::custom(format!("invalid holiday type: {v}. Valid: 1-4")))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<HolidayType, E> {
+                // 查询字符串中的数字同样以字符串形式出现
+                if let Ok(n) = v.parse::<i64>() {
+                    return self.visit_i64(n);
+                }
+                match v {
+                    "public_holiday" => Ok(HolidayType::PublicHoliday),
+                    "company_holiday" => Ok(HolidayType::CompanyHoliday),
+                    "make_up_workday" => Ok(HolidayType::MakeUpWorkday),
+                    "half_day" => Ok(HolidayType::HalfDay),
+                    _ => Err(de::Error::custom(format!(
+                        "invalid holiday type: '{v}'. Valid: public_holiday/company_holiday/make_up_workday/half_day"
+                    ))),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(HolidayTypeVisitor)
+    }
+}
+
+impl HolidayType {
+    pub fn from_i32(v: i32) -> Option<Self> {
+        match v {
+            1 => Some(HolidayType::PublicHoliday),
+            2 => Some(HolidayType::CompanyHoliday),
+            3 => Some(HolidayType::MakeUpWorkday),
+            4 => Some(HolidayType::HalfDay),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        self as i32
+    }
+
+    /// 反序列化同样接受的 snake_case 字符串
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HolidayType::PublicHoliday => "public_holiday",
+            HolidayType::CompanyHoliday => "company_holiday",
+            HolidayType::MakeUpWorkday => "make_up_workday",
+            HolidayType::HalfDay => "half_day",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Holiday {
     pub id: Id,
+    pub calendar_id: Id,
     pub holiday_name: String,
     pub description: Option<String>,
     pub holiday_date: chrono::NaiveDate,
+    /// 1=public_holiday, 2=company_holiday, 3=make_up_workday, 4=half_day
     pub holiday_type: i32,
     pub creator_id: Id,
     pub updater_id: Option<Id>,
@@ -19,10 +119,12 @@ pub struct Holiday {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateHolidayParams {
+    /// 所属假期日历，不传则为默认日历
+    pub calendar_id: Option<Id>,
     pub holiday_name: String,
     pub description: Option<String>,
     pub holiday_date: chrono::NaiveDate,
-    pub holiday_type: i32,
+    pub holiday_type: HolidayType,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,12 +138,14 @@ pub struct UpdateHolidayParams {
     /// NOT NULL 字段
     pub holiday_date: Option<chrono::NaiveDate>,
     /// NOT NULL 字段
-    pub holiday_type: Option<i32>,
+    pub holiday_type: Option<HolidayType>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HolidayQueryParams {
+    /// 假期日历，不传则为默认日历
+    pub calendar_id: Option<Id>,
     pub page: Option<i64>,
     pub per_page: Option<i64>,
     pub holiday_name: Option<String>,
@@ -66,5 +170,111 @@ pub struct BatchUpdateHolidaysParams {
     pub ids: Vec<Id>,
     pub holiday_name: Option<String>,
     pub description: Option<String>,
-    pub holiday_type: Option<i32>,
+    pub holiday_type: Option<HolidayType>,
+}
+
+// ──────────────── 工作日计算 ────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddWorkingDaysQuery {
+    pub date: chrono::NaiveDate,
+    /// 可为负数，表示向前推算
+    pub days: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddWorkingDaysResponse {
+    pub date: chrono::NaiveDate,
+    pub days: i64,
+    pub result: chrono::NaiveDate,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountWorkingDaysQuery {
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountWorkingDaysResponse {
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    /// [startDate, endDate] 闭区间内的工作日数
+    pub working_days: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsWorkingDayQuery {
+    pub date: chrono::NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsWorkingDayResponse {
+    pub date: chrono::NaiveDate,
+    pub is_working_day: bool,
+    /// 工时系数：非工作日 0，半天假 0.5，其余工作日 1
+    pub working_day_fraction: f64,
+}
+
+// ──────────────── 导入 ────────────────
+
+/// 导入文件的 multipart 字段名
+pub const IMPORT_FILE_FIELD_NAME: &str = "file";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HolidayImportFormat {
+    Ics,
+    Csv,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayImportQuery {
+    /// 导入到的假期日历，不传则为默认日历
+    pub calendar_id: Option<Id>,
+    /// 未指定时根据文件的 Content-Type（text/calendar、text/csv）或扩展名（.ics、.csv）推断
+    pub format: Option<HolidayImportFormat>,
+    /// 为 true 时仅解析并返回结果，不写入数据库
+    #[serde(default)]
+    pub preview: bool,
+    /// 文件中未指定类型时使用的假期类型，默认为法定节假日
+    pub holiday_type: Option<HolidayType>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayImportRow {
+    /// 来源文件中的行号（iCalendar 为 BEGIN:VEVENT 所在行）
+    pub line: usize,
+    pub holiday_name: String,
+    pub description: Option<String>,
+    pub holiday_date: chrono::NaiveDate,
+    pub holiday_type: HolidayType,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayImportError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HolidayImportResponse {
+    pub preview: bool,
+    /// 待导入（预览）或已导入的行
+    pub rows: Vec<HolidayImportRow>,
+    /// 与已有假期或文件中前面的行日期重复，导入时跳过
+    pub duplicates: Vec<HolidayImportRow>,
+    pub errors: Vec<HolidayImportError>,
+    /// 实际创建的假期记录，预览模式下为空
+    pub created: Vec<Holiday>,
 }
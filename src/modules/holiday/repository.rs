@@ -1,4 +1,5 @@
 use crate::common::error::AppResult;
+use crate::modules::holiday::holiday_calendar::models::DEFAULT_HOLIDAY_CALENDAR_ID;
 use crate::modules::holiday::models::{
     CreateHolidayParams, Holiday, HolidayQueryParams, UpdateHolidayParams,
 };
@@ -8,11 +9,11 @@ use sqlx::QueryBuilder;
 pub struct HolidayRepository;
 
 /// holidays 表 SELECT 列
-const HOLIDAY_COLUMNS: &str = "id, holiday_name, description, holiday_date, holiday_type, \
+const HOLIDAY_COLUMNS: &str = "id, calendar_id, holiday_name, description, holiday_date, holiday_type, \
     creator_id, updater_id, create_date_time, update_date_time";
 
 /// holidays 表 RETURNING 列
-const HOLIDAY_RETURNING: &str = " RETURNING id, holiday_name, description, holiday_date, holiday_type, \
+const HOLIDAY_RETURNING: &str = " RETURNING id, calendar_id, holiday_name, description, holiday_date, holiday_type, \
     creator_id, updater_id, create_date_time, update_date_time";
 
 impl HolidayRepository {
@@ -24,6 +25,7 @@ impl HolidayRepository {
         let page_size = params.per_page.unwrap_or(10);
         let offset = (page - 1) * page_size;
         let holiday_name_pattern = params.holiday_name.as_ref().map(|h| format!("%{}%", h));
+        let calendar_id = params.calendar_id.map_or(DEFAULT_HOLIDAY_CALENDAR_ID, |id| id.0);
         let holidays = sqlx::query_as::<_, Holiday>(
             &format!(
                 r#"
@@ -33,8 +35,9 @@ impl HolidayRepository {
                   AND ($2::SMALLINT IS NULL OR holiday_type = $2)
                   AND ($3::DATE IS NULL OR holiday_date >= $3)
                   AND ($4::DATE IS NULL OR holiday_date <= $4)
+                  AND calendar_id = $5
                 ORDER BY holiday_date DESC
-                LIMIT $5 OFFSET $6
+                LIMIT $6 OFFSET $7
                 "#,
                 HOLIDAY_COLUMNS,
             ),
@@ -43,6 +46,7 @@ impl HolidayRepository {
         .bind(params.holiday_type)
         .bind(params.start_date)
         .bind(params.end_date)
+        .bind(calendar_id)
         .bind(page_size)
         .bind(offset)
         .fetch_all(pool)
@@ -56,12 +60,14 @@ impl HolidayRepository {
               AND ($2::SMALLINT IS NULL OR holiday_type = $2)
               AND ($3::DATE IS NULL OR holiday_date >= $3)
               AND ($4::DATE IS NULL OR holiday_date <= $4)
+              AND calendar_id = $5
             "#,
         )
         .bind(&holiday_name_pattern)
         .bind(params.holiday_type)
         .bind(params.start_date)
         .bind(params.end_date)
+        .bind(calendar_id)
         .fetch_one(pool)
         .await?;
 
@@ -73,6 +79,7 @@ impl HolidayRepository {
         params: HolidayQueryParams,
     ) -> AppResult<Vec<Holiday>> {
         let holiday_name_pattern = params.holiday_name.as_ref().map(|h| format!("%{}%", h));
+        let calendar_id = params.calendar_id.map_or(DEFAULT_HOLIDAY_CALENDAR_ID, |id| id.0);
 
         let holidays = sqlx::query_as::<_, Holiday>(
             &format!(
@@ -83,6 +90,7 @@ impl HolidayRepository {
                   AND ($2::SMALLINT IS NULL OR holiday_type = $2)
                   AND ($3::DATE IS NULL OR holiday_date >= $3)
                   AND ($4::DATE IS NULL OR holiday_date <= $4)
+                  AND calendar_id = $5
                 ORDER BY holiday_date DESC
                 "#,
                 HOLIDAY_COLUMNS,
@@ -92,6 +100,7 @@ impl HolidayRepository {
         .bind(params.holiday_type)
         .bind(params.start_date)
         .bind(params.end_date)
+        .bind(calendar_id)
         .fetch_all(pool)
         .await?;
 
@@ -112,6 +121,20 @@ impl HolidayRepository {
         Ok(holiday)
     }
 
+    pub async fn get_holidays_by_ids(pool: &PgPool, holiday_ids: &[i64]) -> AppResult<Vec<Holiday>> {
+        let holidays = sqlx::query_as::<_, Holiday>(
+            &format!(
+                "SELECT {} FROM holidays WHERE id = ANY($1) ORDER BY holiday_date",
+                HOLIDAY_COLUMNS,
+            ),
+        )
+        .bind(holiday_ids)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(holidays)
+    }
+
     pub async fn create_holiday(
         pool: &PgPool,
         holiday_id: i64,
@@ -119,17 +142,18 @@ impl HolidayRepository {
         creator_id: i64,
     ) -> AppResult<Holiday> {
         let sql = format!(
-            "INSERT INTO holidays (id, holiday_name, description, holiday_date, holiday_type, \
-             creator_id, create_date_time) \
-             VALUES ($1, $2, $3, $4, $5, $6, NOW()){}",
+            "INSERT INTO holidays (id, calendar_id, holiday_name, description, holiday_date, \
+             holiday_type, creator_id, create_date_time) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, NOW()){}",
             HOLIDAY_RETURNING,
         );
         let holiday = sqlx::query_as::<_, Holiday>(&sql)
         .bind(holiday_id)
+        .bind(params.calendar_id.map_or(DEFAULT_HOLIDAY_CALENDAR_ID, |id| id.0))
         .bind(&params.holiday_name)
         .bind(&params.description)
         .bind(params.holiday_date)
-        .bind(params.holiday_type)
+        .bind(params.holiday_type.as_i32())
         .bind(creator_id)
         .fetch_one(pool)
         .await?;
@@ -173,7 +197,7 @@ impl HolidayRepository {
         if let Some(ref h_type) = params.holiday_type {
             if has_set { qb.push(", "); }
             qb.push("holiday_type = ");
-            qb.push_bind(*h_type);
+            qb.push_bind(h_type.as_i32());
             has_set = true;
         }
 
@@ -226,18 +250,19 @@ impl HolidayRepository {
         }
 
         let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
-            "INSERT INTO holidays (id, holiday_name, description, holiday_date, holiday_type, \
-             creator_id, create_date_time) ",
+            "INSERT INTO holidays (id, calendar_id, holiday_name, description, holiday_date, \
+             holiday_type, creator_id, create_date_time) ",
         );
 
         qb.push_values(
             holiday_ids.iter().zip(params.iter()),
             |mut b, (id, param)| {
                 b.push_bind(*id)
+                    .push_bind(param.calendar_id.map_or(DEFAULT_HOLIDAY_CALENDAR_ID, |id| id.0))
                     .push_bind(param.holiday_name.clone())
                     .push_bind(param.description.clone())
                     .push_bind(param.holiday_date)
-                    .push_bind(param.holiday_type)
+                    .push_bind(param.holiday_type.as_i32())
                     .push_bind(creator_id)
                     .push("NOW()");
             },
@@ -17,7 +17,19 @@ pub fn holiday_routes(state: AppState) -> Router {
     let read_routes = Router::new()
         .route("/holidays", get(handlers::get_holiday_list))
         .route("/holidays/all", get(handlers::get_all_holidays))
-        .route("/holidays/{id}", get(handlers::get_holiday_by_id));
+        .route("/holidays/{id}", get(handlers::get_holiday_by_id))
+        .route(
+            "/holidays/working-days/add",
+            get(handlers::add_working_days),
+        )
+        .route(
+            "/holidays/working-days/count",
+            get(handlers::count_working_days),
+        )
+        .route(
+            "/holidays/working-days/is-working-day",
+            get(handlers::is_working_day),
+        );
 
     // Write routes - admin only
     let write_routes = Router::new()
@@ -36,6 +48,7 @@ pub fn holiday_routes(state: AppState) -> Router {
             "/holidays/batch-update",
             post(handlers::batch_update_holidays),
         )
+        .route("/holidays/import", post(handlers::import_holidays))
         .layer(middleware::from_fn(admin_auth_middleware));
 
     read_routes
@@ -1,6 +1,7 @@
 // 模块统一导出
 pub mod auth;
 pub mod business;
+pub mod calendar_feed;
 pub mod holiday;
 pub mod organization;
 pub mod user;
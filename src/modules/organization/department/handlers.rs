@@ -3,6 +3,7 @@ use crate::common::error::{AppError, AppResult};
 use crate::common::id::Id;
 use crate::common::jwt::Claims;
 use crate::common::response::{ApiResponse, PaginatedResponse};
+use crate::modules::holiday::holiday_calendar::handlers::ensure_calendar_exists;
 use crate::modules::organization::department::models::{
     BatchDeleteDepartmentsParams, CreateDepartmentParams, Department, DepartmentQueryParams,
     UpdateDepartmentParams,
@@ -87,6 +88,9 @@ pub async fn update_department(
     Path(department_id): Path<Id>,
     Json(params): Json<UpdateDepartmentParams>,
 ) -> AppResult<Json<ApiResponse<Department>>> {
+    if let Some(Some(calendar_id)) = params.holiday_calendar_id {
+        ensure_calendar_exists(&state.pool, calendar_id).await?;
+    }
     let updater_id = claims.sub;
     let department =
         DepartmentRepository::update_department(&state.pool, department_id.0, params, updater_id)
@@ -8,10 +8,10 @@ use sqlx::QueryBuilder;
 pub struct DepartmentRepository;
 
 /// departments 表 SELECT 列
-const DEPT_COLUMNS: &str = "id, department_name, description, creator_id, updater_id, create_date_time, update_date_time";
+const DEPT_COLUMNS: &str = "id, department_name, description, holiday_calendar_id, creator_id, updater_id, create_date_time, update_date_time";
 
 /// departments 表 RETURNING 列
-const DEPT_RETURNING: &str = " RETURNING id, department_name, description, creator_id, updater_id, create_date_time, update_date_time";
+const DEPT_RETURNING: &str = " RETURNING id, department_name, description, holiday_calendar_id, creator_id, updater_id, create_date_time, update_date_time";
 
 impl DepartmentRepository {
     pub async fn get_department_list(
@@ -153,6 +153,13 @@ impl DepartmentRepository {
             has_set = true;
         }
 
+        if let Some(ref calendar_opt) = params.holiday_calendar_id {
+            if has_set { qb.push(", "); }
+            qb.push("holiday_calendar_id = ");
+            qb.push_bind(calendar_opt.map(|id| id.0));
+            has_set = true;
+        }
+
         if has_set { qb.push(", "); }
         qb.push("updater_id = ");
         qb.push_bind(updater_id);
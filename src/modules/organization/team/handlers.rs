@@ -3,6 +3,7 @@ use crate::common::error::{AppError, AppResult};
 use crate::common::id::Id;
 use crate::common::jwt::Claims;
 use crate::common::response::{ApiResponse, PaginatedResponse};
+use crate::modules::holiday::holiday_calendar::handlers::ensure_calendar_exists;
 use crate::modules::organization::team::models::{
     BatchDeleteTeamsParams, CreateTeamParams, Team, TeamQueryParams, UpdateTeamParams,
 };
@@ -76,6 +77,9 @@ pub async fn update_team(
     Path(team_id): Path<Id>,
     Json(params): Json<UpdateTeamParams>,
 ) -> AppResult<Json<ApiResponse<Team>>> {
+    if let Some(Some(calendar_id)) = params.holiday_calendar_id {
+        ensure_calendar_exists(&state.pool, calendar_id).await?;
+    }
     let updater_id = claims.sub;
     let team = TeamRepository::update_team(&state.pool, team_id.0, params, updater_id)
         .await?
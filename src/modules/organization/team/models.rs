@@ -8,6 +8,8 @@ pub struct Team {
     pub id: Id,
     pub team_name: String,
     pub description: Option<String>,
+    /// 使用的假期日历，NULL 表示默认日历
+    pub holiday_calendar_id: Option<Id>,
     pub creator_id: Id,
     pub updater_id: Option<Id>,
     pub create_date_time: chrono::NaiveDateTime,
@@ -29,6 +31,9 @@ pub struct UpdateTeamParams {
     /// 可空字段，双层 Option：None = 不更新，Some(None) = 清空，Some(Some(v)) = 更新
     #[serde(default, deserialize_with = "crate::common::serde_helpers::double_option::deserialize")]
     pub description: Option<Option<String>>,
+    /// 可空字段，双层 Option：Some(None) = 恢复为默认假期日历
+    #[serde(default, deserialize_with = "crate::common::serde_helpers::double_option::deserialize")]
+    pub holiday_calendar_id: Option<Option<Id>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -8,10 +8,10 @@ use sqlx::QueryBuilder;
 pub struct TeamRepository;
 
 /// teams 表 SELECT 列
-const TEAM_COLUMNS: &str = "id, team_name, description, creator_id, updater_id, create_date_time, update_date_time";
+const TEAM_COLUMNS: &str = "id, team_name, description, holiday_calendar_id, creator_id, updater_id, create_date_time, update_date_time";
 
 /// teams 表 RETURNING 列
-const TEAM_RETURNING: &str = " RETURNING id, team_name, description, creator_id, updater_id, create_date_time, update_date_time";
+const TEAM_RETURNING: &str = " RETURNING id, team_name, description, holiday_calendar_id, creator_id, updater_id, create_date_time, update_date_time";
 
 impl TeamRepository {
     pub async fn get_team_list(
@@ -141,6 +141,13 @@ impl TeamRepository {
             has_set = true;
         }
 
+        if let Some(ref calendar_opt) = params.holiday_calendar_id {
+            if has_set { qb.push(", "); }
+            qb.push("holiday_calendar_id = ");
+            qb.push_bind(calendar_opt.map(|id| id.0));
+            has_set = true;
+        }
+
         if has_set { qb.push(", "); }
         qb.push("updater_id = ");
         qb.push_bind(updater_id);
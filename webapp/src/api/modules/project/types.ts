@@ -282,6 +282,10 @@ export interface ProjectTask {
   startDateTime: string;
   endDateTime: string;
   taskType: number;
+  /** 完成百分比 0 ~ 100 */
+  percentComplete: number;
+  /** 工作流状态，null 表示尚未进入工作流（视为初始状态） */
+  statusId: string | null;
   creatorId: string;
   updaterId: string | null;
   createDateTime: string;
@@ -295,6 +299,7 @@ export interface CreateProjectTaskParams {
   startDateTime: string;
   endDateTime: string;
   taskType: number;
+  percentComplete?: number;
   customAttributes?: JsonValue | null;
 }
 
@@ -314,6 +319,7 @@ export interface UpdateProjectTaskParams {
   startDateTime?: string;
   endDateTime?: string;
   taskType?: number;
+  percentComplete?: number;
   customAttributes?: JsonValue;
 }
 
@@ -351,6 +357,14 @@ export const ProjectPermission = {
   TASK_DELETE_ALL: "task_delete_all",
   TASK_DELETE_OWN: "task_delete_own",
   TASK_BATCH_OPERATE: "task_batch_operate",
+  // 评论
+  TASK_COMMENT: "task_comment",
+  TASK_COMMENT_MANAGE: "task_comment_manage",
+  // 工时
+  WORKLOG: "worklog",
+  WORKLOG_MANAGE: "worklog_manage",
+  // 检查点
+  CHECKPOINT_APPROVE: "checkpoint_approve",
 } as const;
 
 export type ProjectPermission = (typeof ProjectPermission)[keyof typeof ProjectPermission];